@@ -1,7 +1,178 @@
 use anyhow::{Context, Result};
-use reqwest::{Client, StatusCode};
+use console::style;
+use futures_util::stream::{self, Stream, StreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use crate::auth::{AuthConfig, ServerCapabilities, TokenResponse};
+use crate::config::LocalLayout;
 use crate::utils::logger;
+pub use crate::utils::http::{
+    build_client, default_user_agent, max_redirects, proxy, proxy_from_env, request_error, request_timeout,
+    set_max_redirects, set_proxy, set_request_timeout, timeout_from_env, ClientOptions,
+    DEFAULT_MAX_REDIRECTS, DEFAULT_REQUEST_TIMEOUT_SECS,
+};
+
+/// Default number of documents downloaded at the same time
+pub const DEFAULT_DOWNLOAD_CONCURRENCY: usize = 8;
+
+static DOWNLOAD_CONCURRENCY: AtomicUsize = AtomicUsize::new(DEFAULT_DOWNLOAD_CONCURRENCY);
+
+/// Set how many document downloads may be in flight at once (at least 1)
+pub fn set_download_concurrency(concurrency: usize) {
+    DOWNLOAD_CONCURRENCY.store(concurrency.max(1), Ordering::Relaxed);
+}
+
+pub fn download_concurrency() -> usize {
+    DOWNLOAD_CONCURRENCY.load(Ordering::Relaxed)
+}
+
+/// Set by --offline: commands that can work from local state skip the server
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Retries after the first attempt for connection errors and 502/503/504
+pub const MAX_RETRIES: u32 = 3;
+
+const RETRY_BASE_DELAY_MS: u64 = 500;
+
+/// Cleared by --no-retry
+static RETRY_ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub fn set_retry_enabled(enabled: bool) {
+    RETRY_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn retry_enabled() -> bool {
+    RETRY_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Backoff before retry number `attempt` (1-based): 500ms, 1s, 2s
+fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_millis(RETRY_BASE_DELAY_MS << attempt.saturating_sub(1).min(16))
+}
+
+/// Gateway errors that usually clear up on their own
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(status, StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT)
+}
+
+/// Header that lets the server recognize a repeated create request
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Whether a request may be sent again after it possibly reached the server
+/// GET, HEAD, PUT and DELETE are idempotent; other methods only when they carry an Idempotency-Key.
+fn is_idempotent(method: &Method, headers: &HeaderMap) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::PUT | Method::DELETE)
+        || headers.contains_key(IDEMPOTENCY_KEY_HEADER)
+}
+
+trait SendWithRetry {
+    /// `send()` that retries transient failures with exponential backoff
+    /// Requests that are not idempotent are only retried when the connection failed, since a
+    /// timeout or gateway error may come after the server already handled them.
+    async fn send_with_retry(self) -> Result<Response>;
+}
+
+impl SendWithRetry for RequestBuilder {
+    async fn send_with_retry(self) -> Result<Response> {
+        let idempotent = self.try_clone()
+            .and_then(|builder| builder.build().ok())
+            .is_some_and(|request| is_idempotent(request.method(), request.headers()));
+        let mut attempt = 0;
+        loop {
+            // Streaming bodies cannot be replayed, so those requests are sent once
+            let retry = if retry_enabled() && attempt < MAX_RETRIES { self.try_clone() } else { None };
+            let Some(request) = retry else {
+                return self.send().await.map_err(request_error);
+            };
+
+            let reason = match request.send().await {
+                Ok(response) if idempotent && is_transient_status(response.status()) => format!("HTTP {}", response.status()),
+                Err(e) if e.is_connect() || (idempotent && e.is_timeout()) => e.to_string(),
+                result => return result.map_err(request_error),
+            };
+
+            attempt += 1;
+            let delay = retry_delay(attempt);
+            logger::debug("retry", &format!(
+                "{} (retry {}/{} in {}ms)", reason, attempt, MAX_RETRIES, delay.as_millis()
+            ));
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Store a refreshed token in ~/.teamturbo-cli/config.toml
+fn save_refreshed_token(server_url: &str, refreshed: &TokenResponse) -> Result<()> {
+    let mut config = crate::config::CliConfig::load()?;
+    let auth = config.get_auth_mut(server_url)
+        .with_context(|| format!("No saved login for {}", server_url))?;
+    auth.access_token = refreshed.access_token.clone();
+    auth.token_type = refreshed.token_type.clone();
+    auth.expires_at = refreshed.expires_at.clone();
+    config.save()
+}
+
+/// Maximum number of characters of a server response kept in error reports
+const ERROR_BODY_PREVIEW_CHARS: usize = 200;
+
+/// Upper bound on pages fetched for one listing, in case the server keeps returning a next page
+const MAX_PAGES: u32 = 1000;
+
+/// Failure reported by the server while downloading a document
+/// Carried inside `anyhow::Error`; use `downcast_ref::<DownloadError>()` to inspect it.
+#[derive(Debug, thiserror::Error)]
+pub enum DownloadError {
+    #[error("Document not found: {uuid}")]
+    NotFound { uuid: String },
+    #[error("Token is invalid or expired")]
+    Unauthorized,
+    #[error("API error: {message}")]
+    Api { message: String },
+    #[error("Failed to download document: {status}")]
+    Http { status: StatusCode, body: String },
+}
+
+impl DownloadError {
+    /// HTTP status code of the failed response
+    pub fn status(&self) -> u16 {
+        match self {
+            DownloadError::NotFound { .. } => StatusCode::NOT_FOUND.as_u16(),
+            DownloadError::Unauthorized => StatusCode::UNAUTHORIZED.as_u16(),
+            DownloadError::Api { .. } => StatusCode::OK.as_u16(),
+            DownloadError::Http { status, .. } => status.as_u16(),
+        }
+    }
+
+    /// Start of the server response body, if one was returned
+    pub fn body(&self) -> Option<&str> {
+        match self {
+            DownloadError::Http { body, .. } if !body.is_empty() => Some(body),
+            _ => None,
+        }
+    }
+}
+
+/// Truncate text to `max_chars` characters on a UTF-8 boundary
+fn preview(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((pos, _)) => format!("{}...", &text[..pos]),
+        None => text.to_string(),
+    }
+}
 
 #[derive(Debug, Deserialize)]
 struct ApiResponse<T> {
@@ -23,11 +194,33 @@ struct DocumentResponse {
     document: Option<DocumentContent>,
 }
 
+/// Tokens expiring within this many seconds are refreshed before the next request
+pub const TOKEN_REFRESH_WINDOW_SECS: i64 = 300;
+
+/// A token refresh is attempted at most once per command invocation
+static TOKEN_REFRESH_ATTEMPTED: AtomicBool = AtomicBool::new(false);
+
+/// Access token in use by an `ApiClient` and its clones
+#[derive(Debug)]
+struct TokenState {
+    access_token: String,
+    /// Known only for clients built with `from_auth`; None disables refreshing
+    expires_at: Option<DateTime<Utc>>,
+    /// Server key in ~/.teamturbo-cli/config.toml that a refreshed token is saved under
+    server_url: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct ApiClient {
     base_url: String,
-    token: String,
+    token: Arc<Mutex<TokenState>>,
     client: Client,
+    /// Document content bytes downloaded and uploaded (shared by clones)
+    transferred: Arc<AtomicU64>,
+    /// Category tree from /api/docuram/categories, fetched on first lookup (shared by clones)
+    categories: Arc<Mutex<Option<Arc<Vec<serde_json::Value>>>>>,
+    /// Feedback target type ("document" or "category") per UUID (shared by clones)
+    target_types: Arc<Mutex<HashMap<String, &'static str>>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -60,6 +253,9 @@ pub struct DocuramConfig {
     pub requires: Vec<DocumentInfo>,
     pub dependencies: Vec<Dependency>,
     pub category_tree: Option<CategoryTree>,
+    /// Fields not modeled here, kept so rewriting docuram.json does not drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl DocuramConfig {
@@ -76,7 +272,6 @@ impl DocuramConfig {
     /// Save to docuram.json
     pub fn save(&self) -> Result<()> {
         use std::path::PathBuf;
-        use std::fs;
         use anyhow::Context;
 
         let path = PathBuf::from("docuram.json");
@@ -84,7 +279,7 @@ impl DocuramConfig {
         let content = serde_json::to_string_pretty(self)
             .context("Failed to serialize docuram config")?;
 
-        fs::write(&path, content)
+        crate::utils::write_file_atomic(&path, &content)
             .context("Failed to write docuram.json")?;
 
         Ok(())
@@ -98,6 +293,9 @@ pub struct ProjectInfo {
     pub description: Option<String>,
     pub url: String,
     pub created_at: String,
+    /// Fields not modeled here, kept so rewriting docuram.json does not drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -111,6 +309,9 @@ pub struct DocuramInfo {
     pub category_path: String,
     pub task_id: Option<i64>,
     pub task_name: Option<String>,
+    /// Fields not modeled here, kept so rewriting docuram.json does not drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 // Custom deserializer to support both old format (required/optional) and new format (array)
@@ -165,6 +366,9 @@ pub struct DocumentInfo {
     pub path: String,
     pub checksum: String,
     pub is_required: bool,
+    /// Fields not modeled here, kept so rewriting docuram.json does not drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl DocumentInfo {
@@ -183,7 +387,10 @@ impl DocumentInfo {
     /// Preserves the subdirectory structure within each type directory
     /// For example: docuram/organic/subdir/doc.md, docuram/impl/feature/doc.md
     /// Dependencies are placed in dependencies/ (at project root) with their category structure
-    pub fn local_path(&self, working_category_path: &str) -> String {
+    /// Documents of merged categories go under docuram/<category_path>/
+    pub fn local_path(&self, layout: &LocalLayout) -> String {
+        let working_category_path = layout.working_category_path.as_str();
+
         // Extract the relative path after "docuram/" from the original path
         let path_without_docuram = self.path.strip_prefix("docuram/").unwrap_or(&self.path);
 
@@ -220,6 +427,9 @@ impl DocumentInfo {
                     .unwrap_or("");
 
                 format!("docuram/{}/{}", subdir_path, relative_path)
+            } else if layout.is_merged(&self.category_path) {
+                // Document from a category merged with `init --merge`, namespaced by its path
+                format!("docuram/{}/{}", self.category_path, relative_path)
             } else {
                 // Document is directly in working category, use doc_type mapping
                 let subdir = match self.doc_type.as_str() {
@@ -292,6 +502,9 @@ pub struct DocumentContent {
 pub struct DocumentUpdate {
     pub content: String,
     pub change_summary: Option<String>,
+    /// New title (only sent when the document was renamed locally)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -307,13 +520,107 @@ pub struct DocumentCreate {
 
 impl ApiClient {
     pub fn new(base_url: String, token: String) -> Self {
+        let client = build_client(ClientOptions { same_origin_redirects: true, ..Default::default() })
+            .expect("Failed to create HTTP client");
+
         Self {
+            token: Arc::new(Mutex::new(TokenState {
+                access_token: token,
+                expires_at: None,
+                server_url: base_url.clone(),
+            })),
             base_url: base_url.trim_end_matches('/').to_string(),
-            token,
-            client: Client::new(),
+            client,
+            transferred: Arc::new(AtomicU64::new(0)),
+            categories: Arc::new(Mutex::new(None)),
+            target_types: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Create a client from saved credentials
+    /// The token is refreshed through /api/cli/auth/refresh when it is about to expire.
+    pub fn from_auth(server_url: String, auth: &AuthConfig) -> Self {
+        let client = Self::new(server_url, auth.access_token.clone());
+        client.token.lock().expect("token lock poisoned").expires_at = DateTime::parse_from_rfc3339(&auth.expires_at)
+            .ok()
+            .map(|expires_at| expires_at.with_timezone(&Utc));
+        client
+    }
+
+    /// Token for the next request, refreshed first if it expires within the refresh window
+    async fn bearer_token(&self) -> String {
+        let (token, server_url, expiring) = {
+            let state = self.token.lock().expect("token lock poisoned");
+            let expiring = state.expires_at.is_some_and(|expires_at| {
+                expires_at - Utc::now() < chrono::Duration::seconds(TOKEN_REFRESH_WINDOW_SECS)
+            });
+            (state.access_token.clone(), state.server_url.clone(), expiring)
+        };
+
+        if !expiring || TOKEN_REFRESH_ATTEMPTED.swap(true, Ordering::Relaxed) {
+            return token;
+        }
+
+        match self.refresh_token(&token).await {
+            Ok(Some(refreshed)) => {
+                let expires_at = DateTime::parse_from_rfc3339(&refreshed.expires_at)
+                    .ok()
+                    .map(|expires_at| expires_at.with_timezone(&Utc));
+                {
+                    let mut state = self.token.lock().expect("token lock poisoned");
+                    state.access_token = refreshed.access_token.clone();
+                    state.expires_at = expires_at;
+                }
+                if let Err(e) = save_refreshed_token(&server_url, &refreshed) {
+                    eprintln!("{}", style(format!("⚠ Token refreshed but could not be saved: {}", e)).yellow());
+                }
+                logger::debug("auth", &format!("Token refreshed, now valid until {}", refreshed.expires_at));
+                refreshed.access_token
+            }
+            // Server has no refresh endpoint: keep using the current token
+            Ok(None) => token,
+            Err(e) => {
+                logger::debug("auth", &format!("Token refresh failed: {}", e));
+                token
+            }
+        }
+    }
+
+    /// Exchange the current token for a new one (None if the server does not support refreshing)
+    async fn refresh_token(&self, token: &str) -> Result<Option<TokenResponse>> {
+        let url = format!("{}/api/cli/auth/refresh", self.base_url);
+        logger::http_request("POST", &url);
+
+        let response = self.client
+            .post(&url)
+            .bearer_auth(token)
+            .send_with_retry()
+            .await
+            .context("Failed to refresh token")?;
+
+        let status = response.status();
+        logger::http_response(status.as_u16(), &url);
+
+        match status {
+            StatusCode::OK => {
+                let refreshed = response.json::<TokenResponse>().await
+                    .context("Failed to parse token refresh response")?;
+                Ok(Some(refreshed))
+            }
+            StatusCode::NOT_FOUND => Ok(None),
+            status => anyhow::bail!("Token refresh failed: HTTP {}", status),
+        }
+    }
+
+    /// Total document content bytes transferred so far
+    pub fn bytes_transferred(&self) -> u64 {
+        self.transferred.load(Ordering::Relaxed)
+    }
+
+    fn record_transfer(&self, bytes: usize) {
+        self.transferred.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
     /// Verify the token is valid
     pub async fn verify(&self) -> Result<VerifyResponse> {
         let url = format!("{}/api/cli/auth/verify", self.base_url);
@@ -321,8 +628,8 @@ impl ApiClient {
 
         let response = self.client
             .get(&url)
-            .bearer_auth(&self.token)
-            .send()
+            .bearer_auth(self.bearer_token().await)
+            .send_with_retry()
             .await
             .context("Failed to verify token")?;
 
@@ -346,14 +653,48 @@ impl ApiClient {
         }
     }
 
+    /// Probe the server for supported CLI features
+    /// Servers that predate the endpoint (404) report empty capabilities.
+    pub async fn get_capabilities(&self) -> Result<ServerCapabilities> {
+        let url = format!("{}/api/cli/capabilities", self.base_url);
+        logger::http_request("GET", &url);
+
+        let response = self.client
+            .get(&url)
+            .bearer_auth(self.bearer_token().await)
+            .send_with_retry()
+            .await
+            .context("Failed to fetch server capabilities")?;
+
+        let status = response.status().as_u16();
+        logger::http_response(status, &url);
+
+        match response.status() {
+            StatusCode::OK => {
+                let data = response.json::<ServerCapabilities>()
+                    .await
+                    .context("Failed to parse capabilities response")?;
+                logger::debug("capabilities", &format!("Server features: {:?}", data.features));
+                Ok(data)
+            }
+            StatusCode::NOT_FOUND => {
+                logger::debug("capabilities", "Server does not advertise capabilities");
+                Ok(ServerCapabilities::default())
+            }
+            status => {
+                anyhow::bail!("Unexpected status code: {}", status)
+            }
+        }
+    }
+
     /// Logout and revoke the token
     pub async fn logout(&self) -> Result<()> {
         let url = format!("{}/api/cli/auth/logout", self.base_url);
 
         let response = self.client
             .delete(&url)
-            .bearer_auth(&self.token)
-            .send()
+            .bearer_auth(self.bearer_token().await)
+            .send_with_retry()
             .await
             .context("Failed to logout")?;
 
@@ -371,15 +712,13 @@ impl ApiClient {
     /// Get docuram config from URL
     pub async fn get_docuram_config(&self, config_url: &str) -> Result<DocuramConfig> {
         logger::http_request("GET", config_url);
-        if logger::is_verbose() {
-            println!("[HTTP] Request Headers:");
-            println!("  Authorization: Bearer {}...", &self.token[..20.min(self.token.len())]);
-        }
+        let token = self.bearer_token().await;
+        logger::debug("HTTP", "Authorization: Bearer ***");
 
         let response = self.client
             .get(config_url)
-            .bearer_auth(&self.token)
-            .send()
+            .bearer_auth(&token)
+            .send_with_retry()
             .await
             .context("Failed to fetch docuram config")?;
 
@@ -392,19 +731,8 @@ impl ApiClient {
                 let body_text = response.text().await
                     .context("Failed to read response body")?;
 
-                if logger::is_verbose() {
-                    println!("[HTTP] Response Body (first 1500 chars):");
-                    let preview = if body_text.len() > 1500 {
-                        // Use char_indices to find safe UTF-8 boundary
-                        let truncate_pos = body_text.char_indices()
-                            .nth(1500)
-                            .map(|(pos, _)| pos)
-                            .unwrap_or(body_text.len());
-                        format!("{}...", &body_text[..truncate_pos])
-                    } else {
-                        body_text.clone()
-                    };
-                    println!("{}", preview);
+                if logger::is_verbose_bodies() {
+                    logger::debug("HTTP", &format!("Response body (first 1500 chars):\n{}", preview(&body_text, 1500)));
                 }
 
                 // Parse the API response wrapper
@@ -436,17 +764,51 @@ impl ApiClient {
     /// Download document content
     pub async fn download_document(&self, uuid: &str) -> Result<DocumentContent> {
         let url = format!("{}/api/docuram/documents/{}", self.base_url, uuid);
-        logger::http_request("GET", &url);
+        self.fetch_document(&url, uuid).await
+    }
+
+    /// Download several documents, up to `download_concurrency()` at a time
+    /// Results arrive in completion order, paired with the UUID they belong to.
+    pub fn download_documents<'a>(&'a self, uuids: &'a [String]) -> impl Stream<Item = (&'a str, Result<DocumentContent>)> + 'a {
+        stream::iter(uuids)
+            .map(move |uuid| async move { (uuid.as_str(), self.download_document(uuid).await) })
+            .buffer_unordered(download_concurrency())
+    }
+
+    /// Download a historical version of a document
+    /// A missing version fails with the server's error message when it sent one.
+    pub async fn download_document_version(&self, uuid: &str, version: i64) -> Result<DocumentContent> {
+        let url = format!("{}/api/docuram/documents/{}/versions/{}", self.base_url, uuid, version);
+        self.fetch_document(&url, uuid).await.map_err(|e| {
+            let server_message = match e.downcast_ref::<DownloadError>() {
+                Some(DownloadError::NotFound { .. }) => {
+                    return anyhow::anyhow!("Version {} of document {} not found", version, uuid);
+                }
+                Some(DownloadError::Http { body, .. }) => serde_json::from_str::<DocumentResponse>(body)
+                    .ok()
+                    .and_then(|response| response.error_msg),
+                _ => None,
+            };
+            match server_message {
+                Some(message) => anyhow::anyhow!("Version {} of document {} is not available: {}", version, uuid, message),
+                None => e,
+            }
+        })
+    }
+
+    /// GET a document endpoint and unwrap the API response
+    async fn fetch_document(&self, url: &str, uuid: &str) -> Result<DocumentContent> {
+        logger::http_request("GET", url);
 
         let response = self.client
-            .get(&url)
-            .bearer_auth(&self.token)
-            .send()
+            .get(url)
+            .bearer_auth(self.bearer_token().await)
+            .send_with_retry()
             .await
             .context("Failed to download document")?;
 
         let status = response.status().as_u16();
-        logger::http_response(status, &url);
+        logger::http_response(status, url);
 
         match response.status() {
             StatusCode::OK => {
@@ -454,19 +816,8 @@ impl ApiClient {
                 let body_text = response.text().await
                     .context("Failed to read response body")?;
 
-                if logger::is_verbose() {
-                    println!("[HTTP] Document Response Body (first 500 chars):");
-                    let preview = if body_text.len() > 500 {
-                        // Use char_indices to find safe UTF-8 boundary
-                        let truncate_pos = body_text.char_indices()
-                            .nth(500)
-                            .map(|(pos, _)| pos)
-                            .unwrap_or(body_text.len());
-                        format!("{}...", &body_text[..truncate_pos])
-                    } else {
-                        body_text.clone()
-                    };
-                    println!("{}", preview);
+                if logger::is_verbose_bodies() {
+                    logger::debug("HTTP", &format!("Document response body (first 500 chars):\n{}", preview(&body_text, 500)));
                 }
 
                 // Parse the API response wrapper
@@ -475,24 +826,26 @@ impl ApiClient {
 
                 // Check status and extract document
                 if api_response.status != 0 {
-                    let error_msg = api_response.error_msg.unwrap_or_else(|| "Unknown error".to_string());
-                    anyhow::bail!("API error: {}", error_msg);
+                    let message = api_response.error_msg.unwrap_or_else(|| "Unknown error".to_string());
+                    return Err(DownloadError::Api { message }.into());
                 }
 
                 let doc = api_response.document
                     .context("Response missing document field")?;
 
                 logger::debug("download", &format!("Downloaded document: {} ({})", doc.title, uuid));
+                self.record_transfer(doc.content.as_ref().map(|c| c.len()).unwrap_or(0));
                 Ok(doc)
             }
             StatusCode::NOT_FOUND => {
-                anyhow::bail!("Document not found: {}", uuid)
+                Err(DownloadError::NotFound { uuid: uuid.to_string() }.into())
             }
             StatusCode::UNAUTHORIZED => {
-                anyhow::bail!("Token is invalid or expired")
+                Err(DownloadError::Unauthorized.into())
             }
             status => {
-                anyhow::bail!("Failed to download document: {}", status)
+                let body = response.text().await.unwrap_or_default();
+                Err(DownloadError::Http { status, body: preview(body.trim(), ERROR_BODY_PREVIEW_CHARS) }.into())
             }
         }
     }
@@ -503,9 +856,9 @@ impl ApiClient {
 
         let response = self.client
             .put(&url)
-            .bearer_auth(&self.token)
+            .bearer_auth(self.bearer_token().await)
             .json(&update)
-            .send()
+            .send_with_retry()
             .await
             .context("Failed to upload document")?;
 
@@ -527,6 +880,7 @@ impl ApiClient {
                 let document = api_response.document
                     .context("Response missing document field")?;
 
+                self.record_transfer(update.content.len());
                 Ok(document)
             }
             StatusCode::NOT_FOUND => {
@@ -541,15 +895,57 @@ impl ApiClient {
         }
     }
 
+    /// Move a document to another category (keeps its uuid and history)
+    pub async fn move_document(&self, uuid: &str, category_id: i64) -> Result<DocumentContent> {
+        let url = format!("{}/api/docuram/documents/{}/move", self.base_url, uuid);
+        logger::http_request("PUT", &url);
+
+        let response = self.client
+            .put(&url)
+            .bearer_auth(self.bearer_token().await)
+            .json(&serde_json::json!({ "category_id": category_id }))
+            .send_with_retry()
+            .await
+            .context("Failed to move document")?;
+
+        let status = response.status();
+        logger::http_response(status.as_u16(), &url);
+
+        match status {
+            StatusCode::OK => {
+                let api_response: DocumentResponse = response.json().await
+                    .context("Failed to parse API response")?;
+
+                if api_response.status != 0 {
+                    let error_msg = api_response.error_msg.unwrap_or_else(|| "Unknown error".to_string());
+                    anyhow::bail!("API error: {}", error_msg);
+                }
+
+                api_response.document.context("Response missing document field")
+            }
+            StatusCode::NOT_FOUND => {
+                anyhow::bail!("Document not found: {}", uuid)
+            }
+            StatusCode::UNAUTHORIZED => {
+                anyhow::bail!("Token is invalid or expired")
+            }
+            status => {
+                anyhow::bail!("Failed to move document: {}", status)
+            }
+        }
+    }
+
     /// Create a new document
-    pub async fn create_document(&self, doc: DocumentCreate) -> Result<DocumentContent> {
+    /// `idempotency_key` lets the server recognize a retried create of the same document
+    pub async fn create_document(&self, doc: DocumentCreate, idempotency_key: &str) -> Result<DocumentContent> {
         let url = format!("{}/api/docuram/documents", self.base_url);
 
         let response = self.client
             .post(&url)
-            .bearer_auth(&self.token)
+            .bearer_auth(self.bearer_token().await)
+            .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
             .json(&doc)
-            .send()
+            .send_with_retry()
             .await
             .context("Failed to create document")?;
 
@@ -573,6 +969,7 @@ impl ApiClient {
                     .context("Response missing document field")?;
 
                 logger::debug("create", &format!("Created document: {} ({})", document.title, document.uuid));
+                self.record_transfer(doc.content.len());
                 Ok(document)
             }
             StatusCode::UNAUTHORIZED => {
@@ -588,48 +985,50 @@ impl ApiClient {
         }
     }
 
-    /// Get category ID by path
-    pub async fn get_category_by_path(&self, category_path: &str) -> Result<Option<i64>> {
+    /// Category tree, fetched once and reused until `invalidate_categories`
+    async fn categories(&self) -> Result<Arc<Vec<serde_json::Value>>> {
+        if let Some(categories) = self.categories.lock().expect("categories lock poisoned").clone() {
+            return Ok(categories);
+        }
+
         let url = format!("{}/api/docuram/categories", self.base_url);
 
         let response = self.client
             .get(&url)
-            .bearer_auth(&self.token)
-            .send()
+            .bearer_auth(self.bearer_token().await)
+            .send_with_retry()
             .await
             .context("Failed to fetch categories")?;
 
-        if !response.status().is_success() {
-            return Ok(None);
-        }
-
+        let status = response.status();
         let body_text = response.text().await?;
-        let api_response: serde_json::Value = serde_json::from_str(&body_text)?;
-
-        // Extract categories array from response
-        let categories = api_response.get("categories")
-            .and_then(|c| c.as_array())
-            .context("No categories in response")?;
-
-        // Recursive function to search for category by path
-        fn find_category_id(categories: &[serde_json::Value], path: &str) -> Option<i64> {
-            for cat in categories {
-                if let Some(cat_path) = cat.get("path").and_then(|p| p.as_str()) {
-                    if cat_path == path {
-                        return cat.get("id").and_then(|id| id.as_i64());
-                    }
-                }
-                // Search in subcategories
-                if let Some(subcats) = cat.get("subcategories").and_then(|s| s.as_array()) {
-                    if let Some(id) = find_category_id(subcats, path) {
-                        return Some(id);
-                    }
-                }
-            }
-            None
-        }
+        let categories = Arc::new(parse_category_list(status, &body_text)?);
+
+        *self.categories.lock().expect("categories lock poisoned") = Some(categories.clone());
+        Ok(categories)
+    }
+
+    /// Full category hierarchy from /api/docuram/categories
+    pub async fn get_category_tree(&self) -> Result<Vec<CategoryTree>> {
+        let categories = self.categories().await?;
+
+        categories.iter()
+            .map(|category| serde_json::from_value(category.clone()).context("Failed to parse category"))
+            .collect()
+    }
 
-        Ok(find_category_id(categories, category_path))
+    /// Forget the cached category tree so the next lookup refetches it
+    pub fn invalidate_categories(&self) {
+        *self.categories.lock().expect("categories lock poisoned") = None;
+    }
+
+    /// Get category ID by path
+    pub async fn get_category_by_path(&self, category_path: &str) -> Result<Option<i64>> {
+        let categories = self.categories().await?;
+
+        Ok(find_category(&categories, category_path)
+            .and_then(|cat| cat.get("id"))
+            .and_then(|id| id.as_i64()))
     }
 
     /// Ensure category exists by path, creating it if necessary
@@ -641,9 +1040,9 @@ impl ApiClient {
 
         let response = self.client
             .post(&url)
-            .bearer_auth(&self.token)
+            .bearer_auth(self.bearer_token().await)
             .json(&serde_json::json!({ "path": category_path }))
-            .send()
+            .send_with_retry()
             .await
             .context("Failed to ensure category exists")?;
 
@@ -676,6 +1075,8 @@ impl ApiClient {
                     .context("Response missing category id")?;
 
                 logger::debug("ensure_category", &format!("Ensured category: {} (ID: {})", category_path, category_id));
+                // The category (and any parents) may have just been created
+                self.invalidate_categories();
                 Ok(category_id)
             }
             StatusCode::UNAUTHORIZED => {
@@ -695,48 +1096,63 @@ impl ApiClient {
     pub async fn get_document_versions(&self, category_uuid: &str) -> Result<Vec<DocumentInfo>> {
         let url = format!("{}/api/docuram/categories/{}/document_versions", self.base_url, category_uuid);
 
-        logger::http_request("GET", &url);
-
-        let response = self.client
-            .get(&url)
-            .bearer_auth(&self.token)
-            .send()
-            .await
-            .context("Failed to fetch document versions")?;
-
-        let status = response.status().as_u16();
-        logger::http_response(status, &url);
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch document versions: HTTP {}", status);
-        }
-
-        let body_text = response.text().await
-            .context("Failed to read response body")?;
-
-        #[derive(Deserialize)]
-        struct ApiResponse {
-            status: i32,
-            #[serde(default)]
-            error_msg: String,
-            #[serde(default)]
-            error_code: i32,
-            documents: Vec<DocumentInfo>,
-        }
-
-        let api_response: ApiResponse = serde_json::from_str(&body_text)
-            .context("Failed to parse document versions response")?;
+        self.get_all_pages(&url, "document versions", parse_document_versions).await
+    }
 
-        if api_response.status != 0 {
-            let error_msg = if api_response.error_msg.is_empty() {
-                "Unknown error".to_string()
-            } else {
-                api_response.error_msg
-            };
-            anyhow::bail!("API error: {}", error_msg);
+    /// Fetch every page of a document listing, following a `Link: rel="next"` header or the
+    /// `next_page` / `total_pages` fields of the body, and concatenate what `parse` returns
+    async fn get_all_pages(
+        &self,
+        url: &str,
+        what: &str,
+        parse: impl Fn(StatusCode, &str) -> Result<Vec<DocumentInfo>>,
+    ) -> Result<Vec<DocumentInfo>> {
+        let mut documents = Vec::new();
+        let mut page_url = url.to_string();
+        let mut seen = std::collections::HashSet::new();
+        let mut page = 1;
+
+        loop {
+            if page > MAX_PAGES {
+                anyhow::bail!("Failed to fetch {}: server returned more than {} pages", what, MAX_PAGES);
+            }
+            seen.insert(page_url.clone());
+            logger::http_request("GET", &page_url);
+
+            let response = self.client
+                .get(&page_url)
+                .bearer_auth(self.bearer_token().await)
+                .send_with_retry()
+                .await
+                .with_context(|| format!("Failed to fetch {}", what))?;
+
+            let status = response.status();
+            logger::http_response(status.as_u16(), &page_url);
+            let link = response.headers()
+                .get(reqwest::header::LINK)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string());
+            let body_text = response.text().await
+                .context("Failed to read response body")?;
+
+            documents.extend(parse(status, &body_text)?);
+
+            let body = serde_json::from_str::<serde_json::Value>(&body_text).unwrap_or_default();
+            match next_page_url(url, page, link.as_deref(), &body) {
+                Some(next) if seen.contains(&next) => {
+                    logger::debug("pagination", &format!("Next page {} was already fetched, stopping", next));
+                    break;
+                }
+                Some(next) => {
+                    page_url = next;
+                    page += 1;
+                }
+                None => break,
+            }
         }
 
-        Ok(api_response.documents)
+        logger::debug("pagination", &format!("Fetched {} from {} page(s): {} document(s)", what, page, documents.len()));
+        Ok(documents)
     }
 
     /// Delete a document by UUID
@@ -747,8 +1163,8 @@ impl ApiClient {
 
         let response = self.client
             .delete(&url)
-            .bearer_auth(&self.token)
-            .send()
+            .bearer_auth(self.bearer_token().await)
+            .send_with_retry()
             .await
             .context("Failed to delete document")?;
 
@@ -781,8 +1197,8 @@ impl ApiClient {
 
         let response = self.client
             .delete(&url)
-            .bearer_auth(&self.token)
-            .send()
+            .bearer_auth(self.bearer_token().await)
+            .send_with_retry()
             .await
             .context("Failed to delete category")?;
 
@@ -809,113 +1225,89 @@ impl ApiClient {
 
     /// Get category UUID by path
     pub async fn get_category_uuid_by_path(&self, category_path: &str) -> Result<Option<String>> {
-        let url = format!("{}/api/docuram/categories", self.base_url);
-
-        let response = self.client
-            .get(&url)
-            .bearer_auth(&self.token)
-            .send()
-            .await
-            .context("Failed to fetch categories")?;
+        let categories = self.categories().await?;
 
-        if !response.status().is_success() {
-            return Ok(None);
-        }
-
-        let body_text = response.text().await?;
-        let api_response: serde_json::Value = serde_json::from_str(&body_text)?;
-
-        // Extract categories array from response
-        let categories = api_response.get("categories")
-            .and_then(|c| c.as_array())
-            .context("No categories in response")?;
-
-        // Recursive function to search for category by path
-        fn find_category_uuid(categories: &[serde_json::Value], path: &str) -> Option<String> {
-            for cat in categories {
-                if let Some(cat_path) = cat.get("path").and_then(|p| p.as_str()) {
-                    if cat_path == path {
-                        return cat.get("uuid").and_then(|id| id.as_str()).map(|s| s.to_string());
-                    }
-                }
-                // Search in subcategories
-                if let Some(subcats) = cat.get("subcategories").and_then(|s| s.as_array()) {
-                    if let Some(uuid) = find_category_uuid(subcats, path) {
-                        return Some(uuid);
-                    }
-                }
-            }
-            None
-        }
-
-        Ok(find_category_uuid(categories, category_path))
+        Ok(find_category(&categories, category_path)
+            .and_then(|cat| cat.get("uuid"))
+            .and_then(|uuid| uuid.as_str())
+            .map(|uuid| uuid.to_string()))
     }
 
     /// Get documents in a category by path
     pub async fn get_category_documents(&self, category_path: &str) -> Result<Vec<DocumentInfo>> {
         let url = format!("{}/api/docuram/documents", self.base_url);
 
-        let response = self.client
-            .get(&url)
-            .bearer_auth(&self.token)
-            .send()
-            .await
-            .context("Failed to fetch documents")?;
-
-        if !response.status().is_success() {
-            return Ok(Vec::new());
-        }
-
-        let body_text = response.text().await?;
-        let api_response: serde_json::Value = serde_json::from_str(&body_text)?;
-
-        // Extract documents array from response
-        let documents = api_response.get("documents")
-            .and_then(|d| d.as_array())
-            .context("No documents in response")?;
-
-        // Filter documents by category path
-        let mut category_docs = Vec::new();
-        for doc in documents {
-            if let Some(doc_cat_path) = doc.get("category_path").and_then(|p| p.as_str()) {
-                if doc_cat_path == category_path {
-                    // Try to deserialize this document
-                    if let Ok(doc_info) = serde_json::from_value::<DocumentInfo>(doc.clone()) {
-                        category_docs.push(doc_info);
-                    }
-                }
-            }
-        }
-
-        Ok(category_docs)
+        self.get_all_pages(&url, "documents", |status, body| parse_category_documents(status, body, category_path)).await
     }
 
     /// Send feedback to document authors or category creators
+    /// The server takes one target type per request, so documents and categories are sent
+    /// separately; later requests reply to the thread the first one started.
     pub async fn send_feedback(
         &self,
         target_uuids: Vec<String>,
         message: String,
+        reply_to: Option<String>,
     ) -> Result<FeedbackResponse> {
-        let url = format!("{}/api/docuram/feedback", self.base_url);
-
-        // Detect target type (document or category)
-        let target_type = self.detect_target_type(&target_uuids[0]).await?;
+        let mut types = Vec::with_capacity(target_uuids.len());
+        for uuid in &target_uuids {
+            types.push(self.detect_target_type(uuid).await?);
+        }
 
-        let request_body = FeedbackRequest {
-            target_type: target_type.to_string(),
-            target_uuids,
-            message,
+        let mut combined = FeedbackResponse {
+            success: true,
+            recipients: Vec::new(),
+            message_count: 0,
+            thread_id: None,
         };
+        let mut thread_id = reply_to;
+        for (target_type, uuids) in group_by_target_type(target_uuids, &types) {
+            let response = self.post_feedback(FeedbackRequest {
+                target_type: target_type.to_string(),
+                target_uuids: uuids,
+                message: message.clone(),
+                reply_to: thread_id.clone(),
+            }).await?;
+
+            if thread_id.is_none() {
+                thread_id = response.thread_id.clone();
+            }
+            if combined.thread_id.is_none() {
+                combined.thread_id = response.thread_id;
+            }
+            combined.success &= response.success;
+            combined.message_count += response.message_count;
+            for recipient in response.recipients {
+                if !combined.recipients.iter().any(|r| r.user_id == recipient.user_id) {
+                    combined.recipients.push(recipient);
+                }
+            }
+        }
+
+        Ok(combined)
+    }
 
-        logger::debug("send_feedback", &format!("Sending feedback to {}", url));
+    /// Record UUIDs known to be documents so feedback does not probe them
+    pub fn mark_document_targets(&self, uuids: &[String]) {
+        let mut target_types = self.target_types.lock().expect("target types lock poisoned");
+        for uuid in uuids {
+            target_types.insert(uuid.clone(), "document");
+        }
+    }
+
+    /// POST one feedback request whose targets all have the same type
+    async fn post_feedback(&self, request_body: FeedbackRequest) -> Result<FeedbackResponse> {
+        let url = format!("{}/api/docuram/feedback", self.base_url);
+
+        logger::debug("send_feedback", &format!("Sending {} feedback to {}", request_body.target_type, url));
         logger::http_request("POST", &url);
 
         let response = self
             .client
             .post(&url)
-            .bearer_auth(&self.token)
+            .bearer_auth(self.bearer_token().await)
             .json(&request_body)
-            .send()
+            .send_with_retry()
             .await
             .context("Failed to send feedback request")?;
 
@@ -949,15 +1341,24 @@ impl ApiClient {
         }
     }
 
-    /// Detect whether UUID is a document or category
+    /// Detect whether UUID is a document or category, probing the server once per UUID
     async fn detect_target_type(&self, uuid: &str) -> Result<&'static str> {
+        if let Some(target_type) = self.target_types.lock().expect("target types lock poisoned").get(uuid) {
+            return Ok(target_type);
+        }
+        let target_type = self.probe_target_type(uuid).await?;
+        self.target_types.lock().expect("target types lock poisoned").insert(uuid.to_string(), target_type);
+        Ok(target_type)
+    }
+
+    async fn probe_target_type(&self, uuid: &str) -> Result<&'static str> {
         // Try to fetch as document first
         let doc_url = format!("{}/api/docuram/documents/{}", self.base_url, uuid);
         let doc_response = self
             .client
             .get(&doc_url)
-            .bearer_auth(&self.token)
-            .send()
+            .bearer_auth(self.bearer_token().await)
+            .send_with_retry()
             .await?;
 
         if doc_response.status().is_success() {
@@ -969,8 +1370,8 @@ impl ApiClient {
         let cat_response = self
             .client
             .get(&cat_url)
-            .bearer_auth(&self.token)
-            .send()
+            .bearer_auth(self.bearer_token().await)
+            .send_with_retry()
             .await?;
 
         if cat_response.status().is_success() {
@@ -981,12 +1382,27 @@ impl ApiClient {
     }
 }
 
+/// Targets grouped by type, in order of first appearance
+fn group_by_target_type(uuids: Vec<String>, types: &[&'static str]) -> Vec<(&'static str, Vec<String>)> {
+    let mut groups: Vec<(&'static str, Vec<String>)> = Vec::new();
+    for (uuid, target_type) in uuids.into_iter().zip(types) {
+        match groups.iter_mut().find(|(t, _)| t == target_type) {
+            Some((_, group)) => group.push(uuid),
+            None => groups.push((target_type, vec![uuid])),
+        }
+    }
+    groups
+}
+
 /// Feedback request structure
 #[derive(Debug, Serialize)]
 pub struct FeedbackRequest {
     pub target_type: String,
     pub target_uuids: Vec<String>,
     pub message: String,
+    /// Existing thread to attach this message to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
 }
 
 /// Feedback response structure
@@ -995,6 +1411,9 @@ pub struct FeedbackResponse {
     pub success: bool,
     pub recipients: Vec<Recipient>,
     pub message_count: usize,
+    /// Thread the message was posted to (for replying with --reply-to)
+    #[serde(default)]
+    pub thread_id: Option<String>,
 }
 
 /// Recipient information
@@ -1090,9 +1509,7 @@ pub struct PublicApiClient {
 impl PublicApiClient {
     /// Create a new public API client
     pub fn new(base_url: String) -> Self {
-        let client = Client::builder()
-            .danger_accept_invalid_certs(true)
-            .build()
+        let client = build_client(ClientOptions { accept_invalid_certs: true, ..Default::default() })
             .expect("Failed to create HTTP client");
 
         Self { base_url, client }
@@ -1112,7 +1529,7 @@ impl PublicApiClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_with_retry()
             .await
             .context("Failed to fetch global dependencies")?;
 
@@ -1151,7 +1568,7 @@ impl PublicApiClient {
         let response = self
             .client
             .get(&url)
-            .send()
+            .send_with_retry()
             .await
             .context("Failed to download global dependency")?;
 
@@ -1184,3 +1601,224 @@ impl PublicApiClient {
         }
     }
 }
+
+/// Parse the category list response
+/// A 404 means there are no categories; other failures (auth, server errors) are returned as errors
+/// so callers do not mistake them for a missing category.
+fn parse_category_list(status: StatusCode, body: &str) -> Result<Vec<serde_json::Value>> {
+    let api_response = match status {
+        StatusCode::NOT_FOUND => return Ok(Vec::new()),
+        StatusCode::UNAUTHORIZED => anyhow::bail!("Token is invalid or expired"),
+        s if s.is_success() => serde_json::from_str::<serde_json::Value>(body)
+            .context("Failed to parse categories response")?,
+        _ => anyhow::bail!("Failed to fetch categories: {}", status),
+    };
+
+    let categories = api_response.get("categories")
+        .and_then(|c| c.as_array())
+        .context("No categories in response")?;
+
+    Ok(categories.clone())
+}
+
+/// Find a category by path, searching subcategories recursively
+fn find_category<'a>(categories: &'a [serde_json::Value], path: &str) -> Option<&'a serde_json::Value> {
+    for cat in categories {
+        if cat.get("path").and_then(|p| p.as_str()) == Some(path) {
+            return Some(cat);
+        }
+        if let Some(subcats) = cat.get("subcategories").and_then(|s| s.as_array()) {
+            if let Some(found) = find_category(subcats, path) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Documents of one page of the document versions response
+fn parse_document_versions(status: StatusCode, body: &str) -> Result<Vec<DocumentInfo>> {
+    if !status.is_success() {
+        anyhow::bail!("Failed to fetch document versions: HTTP {}", status.as_u16());
+    }
+
+    #[derive(Deserialize)]
+    struct ApiResponse {
+        status: i32,
+        #[serde(default)]
+        error_msg: String,
+        #[serde(default)]
+        error_code: i32,
+        documents: Vec<DocumentInfo>,
+    }
+
+    let api_response: ApiResponse = serde_json::from_str(body)
+        .context("Failed to parse document versions response")?;
+
+    if api_response.status != 0 {
+        let error_msg = if api_response.error_msg.is_empty() {
+            "Unknown error".to_string()
+        } else {
+            api_response.error_msg
+        };
+        anyhow::bail!("API error: {}", error_msg);
+    }
+
+    Ok(api_response.documents)
+}
+
+/// URL of the page after `page`, or None on the last page
+/// A `rel="next"` Link entry wins. Otherwise, including Link headers with only other relations,
+/// `next_page`, or `page`/`total_pages`, are read from the body or its `pagination`/`meta` object
+/// and turned into `?page=N` on `url`.
+fn next_page_url(url: &str, page: u32, link: Option<&str>, body: &serde_json::Value) -> Option<String> {
+    let link_next = link.and_then(|link| link.split(',')
+        .find(|part| part.split(';').skip(1).any(|param| param.trim().replace('"', "") == "rel=next")));
+    if let Some(part) = link_next {
+        return part.split(';').next()
+            .map(|target| target.trim().trim_start_matches('<').trim_end_matches('>').to_string());
+    }
+
+    let paging = [Some(body), body.get("pagination"), body.get("meta")]
+        .into_iter()
+        .flatten()
+        .find(|value| value.get("next_page").is_some() || value.get("total_pages").is_some())?;
+
+    let next = match paging.get("next_page") {
+        Some(next) => next.as_u64()?,
+        None => {
+            let current = ["page", "current_page"].iter()
+                .find_map(|key| paging.get(*key).and_then(|v| v.as_u64()))
+                .unwrap_or(page as u64);
+            let total = paging.get("total_pages")?.as_u64()?;
+            if current >= total {
+                return None;
+            }
+            current + 1
+        }
+    };
+
+    Some(format!("{}?page={}", url, next))
+}
+
+/// Parse the document list response, keeping documents in the given category
+/// A 404 means there are no documents; other failures are returned as errors.
+fn parse_category_documents(status: StatusCode, body: &str, category_path: &str) -> Result<Vec<DocumentInfo>> {
+    let api_response = match status {
+        StatusCode::NOT_FOUND => return Ok(Vec::new()),
+        StatusCode::UNAUTHORIZED => anyhow::bail!("Token is invalid or expired"),
+        s if s.is_success() => serde_json::from_str::<serde_json::Value>(body)
+            .context("Failed to parse documents response")?,
+        _ => anyhow::bail!("Failed to fetch documents: {}", status),
+    };
+
+    // Extract documents array from response
+    let documents = api_response.get("documents")
+        .and_then(|d| d.as_array())
+        .context("No documents in response")?;
+
+    // Filter documents by category path
+    let category_docs = documents.iter()
+        .filter(|doc| doc.get("category_path").and_then(|p| p.as_str()) == Some(category_path))
+        .filter_map(|doc| serde_json::from_value::<DocumentInfo>(doc.clone()).ok())
+        .collect();
+
+    Ok(category_docs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_list_server_error_is_not_treated_as_missing() {
+        let err = parse_category_list(StatusCode::INTERNAL_SERVER_ERROR, "oops").unwrap_err();
+        assert!(err.to_string().contains("500"));
+        assert!(parse_category_documents(StatusCode::INTERNAL_SERVER_ERROR, "oops", "Cat").is_err());
+        assert!(parse_category_list(StatusCode::UNAUTHORIZED, "").is_err());
+    }
+
+    #[test]
+    fn test_next_page_url() {
+        let url = "http://x/api/docuram/documents";
+        let body = serde_json::json!({"documents": []});
+        assert_eq!(next_page_url(url, 1, None, &body), None);
+
+        let body = serde_json::json!({"documents": [], "next_page": 2});
+        assert_eq!(next_page_url(url, 1, None, &body).as_deref(), Some("http://x/api/docuram/documents?page=2"));
+        let body = serde_json::json!({"documents": [], "next_page": null});
+        assert_eq!(next_page_url(url, 2, None, &body), None);
+
+        let body = serde_json::json!({"documents": [], "pagination": {"page": 2, "total_pages": 3}});
+        assert_eq!(next_page_url(url, 2, None, &body).as_deref(), Some("http://x/api/docuram/documents?page=3"));
+        let body = serde_json::json!({"documents": [], "meta": {"total_pages": 3}});
+        assert_eq!(next_page_url(url, 3, None, &body), None);
+
+        let link = r#"<http://x/a?page=1>; rel="prev", <http://x/a?page=3>; rel="next""#;
+        assert_eq!(next_page_url(url, 2, Some(link), &body).as_deref(), Some("http://x/a?page=3"));
+        assert_eq!(next_page_url(url, 3, Some(r#"<http://x/a?page=2>; rel="prev""#), &body), None);
+
+        // A Link header without rel="next" does not hide the body's pagination
+        let body = serde_json::json!({"documents": [], "next_page": 4});
+        assert_eq!(next_page_url(url, 3, Some(r#"<http://x/a>; rel="canonical""#), &body).as_deref(), Some("http://x/api/docuram/documents?page=4"));
+    }
+
+    #[test]
+    fn test_category_list_empty_response() {
+        let categories = parse_category_list(StatusCode::OK, r#"{"categories": []}"#).unwrap();
+        assert!(find_category(&categories, "Cat").is_none());
+        assert!(parse_category_documents(StatusCode::OK, r#"{"documents": []}"#, "Cat").unwrap().is_empty());
+        assert!(parse_category_list(StatusCode::NOT_FOUND, "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_download_error_keeps_status_and_body_preview() {
+        let body = preview(&"x".repeat(300), ERROR_BODY_PREVIEW_CHARS);
+        let err: anyhow::Error = DownloadError::Http { status: StatusCode::BAD_GATEWAY, body }.into();
+        let download_error = err.downcast_ref::<DownloadError>().unwrap();
+        assert_eq!(download_error.status(), 502);
+        assert_eq!(download_error.body().unwrap().chars().count(), ERROR_BODY_PREVIEW_CHARS + 3);
+    }
+
+    #[test]
+    fn test_retry_backoff_and_transient_statuses() {
+        let delays: Vec<u128> = (1..=MAX_RETRIES).map(|attempt| retry_delay(attempt).as_millis()).collect();
+        assert_eq!(delays, vec![500, 1000, 2000]);
+
+        assert!(is_transient_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_transient_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_transient_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_only_idempotent_requests_are_retried() {
+        let mut headers = HeaderMap::new();
+        assert!(is_idempotent(&Method::GET, &headers));
+        assert!(is_idempotent(&Method::PUT, &headers));
+        assert!(is_idempotent(&Method::DELETE, &headers));
+        assert!(!is_idempotent(&Method::POST, &headers));
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "k".parse().unwrap());
+        assert!(is_idempotent(&Method::POST, &headers));
+    }
+
+    #[test]
+    fn test_find_category_in_subcategories() {
+        let body = r#"{"categories": [{"id": 1, "path": "Cat", "subcategories": [{"id": 2, "path": "Cat/impl"}]}]}"#;
+        let categories = parse_category_list(StatusCode::OK, body).unwrap();
+        let found = find_category(&categories, "Cat/impl").unwrap();
+        assert_eq!(found.get("id").and_then(|id| id.as_i64()), Some(2));
+    }
+
+    #[test]
+    fn test_group_by_target_type() {
+        let uuids = vec!["d1".to_string(), "c1".to_string(), "d2".to_string()];
+        assert_eq!(
+            group_by_target_type(uuids, &["document", "category", "document"]),
+            vec![
+                ("document", vec!["d1".to_string(), "d2".to_string()]),
+                ("category", vec!["c1".to_string()]),
+            ]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::{Result, bail};
 use console::style;
 use std::time::Duration;
-use reqwest::Client;
+use crate::utils::http::{build_client, request_error, ClientOptions};
 use crate::auth::{generate_login_id, AuthConfig, PollResponse};
 use crate::utils::logger;
 
@@ -42,7 +42,7 @@ pub async fn authorize(base_url: &str) -> Result<AuthConfig> {
     };
 
     // Initialize login session on server
-    let client = Client::new();
+    let client = build_client(ClientOptions { same_origin_redirects: true, ..Default::default() })?;
     let init_url = format!("{}/api/cli/auth/init", backend_url);
 
     println!("{}", style("Initializing login session...").cyan());
@@ -51,7 +51,8 @@ pub async fn authorize(base_url: &str) -> Result<AuthConfig> {
         .post(&init_url)
         .json(&serde_json::json!({ "login_id": login_id }))
         .send()
-        .await?;
+        .await
+        .map_err(request_error)?;
 
     if !init_response.status().is_success() {
         bail!("Failed to initialize login session: {}", init_response.status());
@@ -76,99 +77,165 @@ pub async fn authorize(base_url: &str) -> Result<AuthConfig> {
     loop {
         tokio::time::sleep(Duration::from_secs(2)).await;
 
-        if logger::is_verbose() {
-            println!("\n[DEBUG] Polling URL: {}", poll_url);
-            println!("[DEBUG] Login ID: {}", login_id);
-        }
+        logger::debug("auth", &format!("Polling URL: {}", poll_url));
+        logger::debug("auth", &format!("Login ID: {}", login_id));
 
         let response = client
             .get(&poll_url)
             .query(&[("login_id", &login_id)])
             .send()
-            .await?;
+            .await
+            .map_err(request_error)?;
 
-        if logger::is_verbose() {
-            println!("[DEBUG] Response status: {}", response.status());
-        }
+        let http_status = response.status().as_u16();
+        logger::debug("auth", &format!("Response status: {}", http_status));
 
         // Get response body as text first for debugging
         let body_text = response.text().await?;
 
-        if logger::is_verbose() {
-            println!("[DEBUG] Response body: {}", body_text);
+        if logger::is_verbose_bodies() {
+            logger::debug("auth", &format!("Response body: {}", body_text));
         }
 
-        // Try to parse the response
-        if body_text.is_empty() {
-            if logger::is_verbose() {
-                println!("[DEBUG] Empty response body, continuing...");
+        match parse_poll_response(http_status, &body_text)? {
+            PollState::Initialized | PollState::Pending => {
+                logger::debug("auth", "Not authorized yet, continuing to poll...");
+            }
+            PollState::Authorized(auth) => {
+                println!("{}", style("✓ Authorization successful!").green().bold());
+                println!("  {} {} ({})",
+                    style("Logged in as:").dim(),
+                    style(&auth.user_name).cyan().bold(),
+                    style(&auth.user_email).dim()
+                );
+                return Ok(auth);
+            }
+            PollState::Denied => {
+                bail!("Authorization was denied by user");
+            }
+            PollState::Expired => {
+                bail!("Login session expired before it was authorized. Run 'teamturbo login' again.");
             }
-            continue;
         }
+    }
+}
 
-        let data: PollResponse = match serde_json::from_str(&body_text) {
-            Ok(d) => d,
-            Err(e) => {
-                eprintln!("[ERROR] Failed to parse JSON: {}", e);
-                eprintln!("[ERROR] Raw body: {}", body_text);
-                bail!("Failed to parse server response");
-            }
-        };
-
-        // Handle the response based on status code and data
-        if data.status == 0 {
-            if let Some(auth) = data.auth {
-                match auth.status.as_str() {
-                    "authorized" => {
-                        if let (Some(token), Some(user), Some(expires_at)) =
-                            (auth.access_token, auth.user, auth.expires_at) {
-
-                            println!("{}", style("✓ Authorization successful!").green().bold());
-                            println!("  {} {} ({})",
-                                style("Logged in as:").dim(),
-                                style(user.display_name_or_account()).cyan().bold(),
-                                style(&user.email).dim()
-                            );
-
-                            return Ok(AuthConfig {
-                                access_token: token,
-                                token_type: auth.token_type.unwrap_or_else(|| "Bearer".to_string()),
-                                expires_at,
-                                user_id: user.id,
-                                user_name: user.display_name_or_account().to_string(),
-                                user_email: user.email,
-                            });
-                        }
-                    }
-                    "denied" => {
-                        bail!("Authorization was denied by user");
-                    }
-                    "pending" => {
-                        // Continue waiting
-                        if logger::is_verbose() {
-                            println!("[DEBUG] Status: pending, continuing to poll...");
-                        }
-                        continue;
-                    }
-                    _ => {
-                        bail!("Unknown authorization status: {}", auth.status);
-                    }
-                }
-            } else {
-                // status == 0 but no auth data (e.g., pending with status 202)
-                if logger::is_verbose() {
-                    println!("[DEBUG] No auth data yet, continuing to poll...");
-                }
-                continue;
+/// State of a browser login session as reported by the poll endpoint
+#[derive(Debug)]
+pub enum PollState {
+    /// Session exists but the authorization page has not been opened yet
+    Initialized,
+    /// Waiting for the user to approve (the server may answer 202 with an empty body)
+    Pending,
+    /// Approved, with the credentials to store
+    Authorized(AuthConfig),
+    /// The user rejected the request
+    Denied,
+    /// The server no longer knows this login_id (timed out or never created)
+    Expired,
+}
+
+/// Interpret a poll response from its HTTP status and body
+/// Unknown states and malformed bodies are errors so polling never loops on something it cannot handle.
+pub fn parse_poll_response(http_status: u16, body: &str) -> Result<PollState> {
+    if matches!(http_status, 404 | 410) {
+        return Ok(PollState::Expired);
+    }
+
+    if body.trim().is_empty() {
+        return Ok(PollState::Pending);
+    }
+
+    let data: PollResponse = match serde_json::from_str(body) {
+        Ok(d) => d,
+        Err(e) => bail!("Failed to parse server response: {} (body: {})", e, body),
+    };
+
+    if data.status != 0 {
+        let message = data.error.or(data.error_msg)
+            .unwrap_or_else(|| format!("status code {}", data.status));
+        if message.to_lowercase().contains("expired") {
+            return Ok(PollState::Expired);
+        }
+        bail!("Authorization failed: {}", message);
+    }
+
+    // The session state lives in `auth` once the user acted, in `data` before that
+    let state = data.auth.as_ref()
+        .map(|auth| auth.status.clone())
+        .or_else(|| data.data.as_ref().map(|d| d.status.clone()))
+        .unwrap_or_else(|| "pending".to_string());
+
+    match state.as_str() {
+        "initialized" => Ok(PollState::Initialized),
+        "pending" => Ok(PollState::Pending),
+        "denied" => Ok(PollState::Denied),
+        "expired" => Ok(PollState::Expired),
+        "authorized" => {
+            let Some(auth) = data.auth else {
+                bail!("Server reported authorized without auth data");
+            };
+            match (auth.access_token, auth.user, auth.expires_at) {
+                (Some(token), Some(user), Some(expires_at)) => Ok(PollState::Authorized(AuthConfig {
+                    access_token: token,
+                    token_type: auth.token_type.unwrap_or_else(|| "Bearer".to_string()),
+                    expires_at,
+                    user_id: user.id,
+                    user_name: user.display_name_or_account().to_string(),
+                    user_email: user.email,
+                    capabilities: None,
+                })),
+                _ => bail!("Server reported authorization without credentials"),
             }
-        } else {
-            // status != 0, error case
-            eprintln!("[ERROR] Server returned error status: {}", data.status);
-            if let Some(err) = data.error {
-                bail!("Authorization failed: {}", err);
-            } else {
-                bail!("Authorization failed with status code: {}", data.status);
+        }
+        other => bail!("Unknown authorization status: {}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_poll_response_states() {
+        assert!(matches!(parse_poll_response(202, "").unwrap(), PollState::Pending));
+        assert!(matches!(
+            parse_poll_response(200, r#"{"status":0,"data":{"status":"initialized"}}"#).unwrap(),
+            PollState::Initialized
+        ));
+        assert!(matches!(
+            parse_poll_response(200, r#"{"status":0,"auth":{"status":"pending"}}"#).unwrap(),
+            PollState::Pending
+        ));
+        assert!(matches!(
+            parse_poll_response(200, r#"{"status":0,"auth":{"status":"denied"}}"#).unwrap(),
+            PollState::Denied
+        ));
+        assert!(matches!(parse_poll_response(404, "").unwrap(), PollState::Expired));
+        assert!(matches!(
+            parse_poll_response(200, r#"{"status":1,"error":"login_id expired"}"#).unwrap(),
+            PollState::Expired
+        ));
+
+        let authorized = r#"{"status":0,"auth":{"status":"authorized","access_token":"t","expires_at":"2030-01-01T00:00:00Z",
+            "user":{"id":7,"account":"alice","display_name":null,"email":"a@example.com"}}}"#;
+        match parse_poll_response(200, authorized).unwrap() {
+            PollState::Authorized(auth) => {
+                assert_eq!(auth.access_token, "t");
+                assert_eq!(auth.token_type, "Bearer");
+                assert_eq!(auth.user_name, "alice");
             }
+            other => panic!("unexpected state: {:?}", other),
         }
     }
+
+    #[test]
+    fn test_parse_poll_response_errors() {
+        assert!(parse_poll_response(200, r#"{"status":0,"auth":{"status":"authorized"}}"#).is_err());
+        assert!(parse_poll_response(200, r#"{"status":0,"data":{"status":"authorized"}}"#).is_err());
+        assert!(parse_poll_response(200, r#"{"status":0,"auth":{"status":"weird"}}"#).is_err());
+        assert!(parse_poll_response(200, r#"{"status":1,"error":"boom"}"#).is_err());
+        assert!(parse_poll_response(200, "<html>").is_err());
+    }
 }
+
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use console::style;
 use dialoguer::Input;
-use reqwest::Client;
+use crate::utils::http::{build_client, request_error, ClientOptions};
 use crate::auth::{AuthConfig, VerifyResponse};
 
 /// Authorize via manual token input (mode 2)
@@ -20,6 +20,11 @@ pub async fn authorize(base_url: &str) -> Result<AuthConfig> {
         .with_prompt("Paste the token here")
         .interact_text()?;
 
+    verify_token(base_url, &token).await
+}
+
+/// Verify a CLI token through /api/cli/auth/verify and build the login to save
+pub async fn verify_token(base_url: &str, token: &str) -> Result<AuthConfig> {
     let token = token.trim().to_string();
 
     if token.is_empty() {
@@ -29,14 +34,15 @@ pub async fn authorize(base_url: &str) -> Result<AuthConfig> {
     println!("{}", style("Verifying token...").cyan());
 
     // Verify token
-    let client = Client::new();
+    let client = build_client(ClientOptions { same_origin_redirects: true, ..Default::default() })?;
     let verify_url = format!("{}/api/cli/auth/verify", base_url);
 
     let response = client
         .get(&verify_url)
         .header("Authorization", format!("Bearer {}", token))
         .send()
-        .await?;
+        .await
+        .map_err(request_error)?;
 
     if !response.status().is_success() {
         anyhow::bail!("Token verification failed: Invalid or expired token");
@@ -63,6 +69,7 @@ pub async fn authorize(base_url: &str) -> Result<AuthConfig> {
             user_id: user.id,
             user_name: user.display_name_or_account().to_string(),
             user_email: user.email,
+            capabilities: None,
         })
     } else {
         anyhow::bail!("Failed to get user information from verification response");
@@ -11,6 +11,42 @@ pub struct AuthConfig {
     pub user_id: i64,
     pub user_name: String,
     pub user_email: String,
+    /// Server capabilities recorded at login (absent for configs saved by older CLIs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<ServerCapabilities>,
+}
+
+/// Features and version advertised by the server via /api/cli/capabilities
+/// Servers without the endpoint are recorded with no version and no features.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ServerCapabilities {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+/// Server feature: moving a document to another category (PUT /documents/{uuid}/move)
+pub const FEATURE_DOCUMENT_MOVE: &str = "document_move";
+
+/// Server feature: several document downloads may be served in parallel
+pub const FEATURE_CONCURRENT_DOWNLOADS: &str = "concurrent_downloads";
+
+impl ServerCapabilities {
+    /// Whether the server advertised `feature`
+    /// Servers that advertise nothing predate the endpoint and are assumed to support everything.
+    pub fn supports(&self, feature: &str) -> bool {
+        (self.version.is_none() && self.features.is_empty())
+            || self.features.iter().any(|f| f == feature)
+    }
+}
+
+impl AuthConfig {
+    /// Whether the server behind this login supports `feature`
+    /// Logins without recorded capabilities (older configs, $TEAMTURBO_TOKEN) assume it does.
+    pub fn supports(&self, feature: &str) -> bool {
+        self.capabilities.as_ref().is_none_or(|c| c.supports(feature))
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,6 +92,7 @@ impl User {
 pub struct TokenResponse {
     pub access_token: String,
     pub token_type: String,
+    #[serde(default)]
     pub expires_in: i64,
     pub expires_at: String,
     pub message: Option<String>,
@@ -86,3 +123,21 @@ pub fn generate_login_id() -> String {
 pub fn can_open_browser() -> bool {
     std::env::var("DISPLAY").is_ok() || cfg!(target_os = "windows") || cfg!(target_os = "macos")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_supports() {
+        let legacy = ServerCapabilities::default();
+        assert!(legacy.supports(FEATURE_DOCUMENT_MOVE));
+
+        let current = ServerCapabilities {
+            version: Some("2.3.0".to_string()),
+            features: vec![FEATURE_CONCURRENT_DOWNLOADS.to_string()],
+        };
+        assert!(current.supports(FEATURE_CONCURRENT_DOWNLOADS));
+        assert!(!current.supports(FEATURE_DOCUMENT_MOVE));
+    }
+}
@@ -1,23 +1,37 @@
 use anyhow::{Result, Context};
 use console::style;
 use std::fs;
+use std::io::{IsTerminal, Read};
+use std::process::Command;
 use std::path::{Path, PathBuf};
 
-use crate::config::DocuramConfig;
-use crate::utils::write_file;
+use crate::config::{DocuramConfig, DocumentDefaults, DocumentInfo, LocalOnlyDocument};
+use crate::utils::{calculate_checksum, calculate_content_checksum, default_front_matter, ensure_category_dir, normalize_category_path, write_file, TEMPLATES_DIR};
 
 /// Type of organic document to add
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
 pub enum DocType {
-    Req,  // Requirement document
-    Bug,  // Bug report document
+    /// Requirement
+    Req,
+    /// Bug report
+    Bug,
+    /// Design document
+    Design,
+    /// Test plan or test case
+    Test,
+    /// Reference knowledge
+    Knowledge,
 }
 
 impl DocType {
+    /// Filename prefix, numbered per prefix (req001.md, kb002.md)
     fn prefix(&self) -> &str {
         match self {
             DocType::Req => "req",
             DocType::Bug => "bug",
+            DocType::Design => "design",
+            DocType::Test => "test",
+            DocType::Knowledge => "kb",
         }
     }
 
@@ -25,27 +39,78 @@ impl DocType {
         match self {
             DocType::Req => "**实现以下需求，并按Docuram规范生成并放置文档**",
             DocType::Bug => "**修正以下错误，并按Docuram规范生成并放置文档**",
+            DocType::Design => "**按以下设计实现，并按Docuram规范生成并放置文档**",
+            DocType::Test => "**编写并执行以下测试，并按Docuram规范生成并放置文档**",
+            DocType::Knowledge => "**参考以下知识，并按Docuram规范维护本文档**",
         }
     }
+
+    /// Project template replacing the built-in header: docuram/templates/<type>.md
+    fn template_path(&self) -> PathBuf {
+        let name = match self {
+            DocType::Knowledge => "knowledge",
+            other => other.prefix(),
+        };
+        Path::new(TEMPLATES_DIR).join(format!("{}.md", name))
+    }
+
+    /// Header from the project template when there is one, else the built-in default
+    fn header(&self) -> Result<String> {
+        let path = self.template_path();
+        match fs::read_to_string(&path) {
+            Ok(template) => Ok(template),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(self.default_header().to_string()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read template {}", path.display())),
+        }
+    }
+}
+
+/// Replaced with the document title in templates
+const TITLE_PLACEHOLDER: &str = "{{title}}";
+
+/// Options for `add`
+pub struct AddOptions {
+    /// Title appended to the filename and used as the first heading
+    pub title: Option<String>,
+    /// Server doc_type/priority used on push (project defaults apply otherwise)
+    pub overrides: DocumentDefaults,
+    /// Record a provisional entry in docuram.json right away (see `DocumentInfo::provisional`)
+    pub register: bool,
+    /// Seed the body with the contents of this file (e.g. a stack trace)
+    pub from_file: Option<String>,
+    /// Seed the body with content read from stdin
+    pub from_stdin: bool,
+    /// Category directory under docuram/ (default: organic)
+    pub category: Option<String>,
+    /// Create the category directory when it is missing
+    pub parents: bool,
 }
 
-/// Add a new organic document (req or bug)
-pub async fn execute(doc_type: DocType, title: Option<String>) -> Result<()> {
-    println!("{}", style("Add Organic Document").cyan().bold());
-    println!();
+/// Add a new organic document (req, bug, design, test or knowledge)
+/// Returns the path of the created file.
+pub async fn execute(doc_type: DocType, options: AddOptions) -> Result<PathBuf> {
+    let AddOptions { title, overrides, register, from_file, from_stdin, category, parents } = options;
+
+    crate::outln!("{}", style("Add Organic Document").cyan().bold());
+    crate::outln!();
+
+    // Read seed content first so a bad path fails before anything is created
+    let body = read_seed_content(from_file.as_deref(), from_stdin)?;
 
     // Load docuram config to validate we're in a docuram project
-    let _docuram_config = DocuramConfig::load()
+    let mut docuram_config = DocuramConfig::load()
         .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
 
-    // Use the organic directory directly under docuram/
-    let organic_path = PathBuf::from("docuram/organic");
+    // Documents go to docuram/organic unless a category is given
+    let category = match category {
+        Some(category) => relative_category(&category, &docuram_config.docuram.category_path)?,
+        None => "organic".to_string(),
+    };
+    let organic_path = PathBuf::from("docuram").join(&category);
 
-    // Create organic directory if it doesn't exist
-    if !organic_path.exists() {
-        fs::create_dir_all(&organic_path)
-            .context("Failed to create docuram/organic directory")?;
-        println!("{} Created organic directory: {}",
+    // The organic directory is standard and always created; other categories need --parents
+    if ensure_category_dir(&organic_path, parents || category == "organic")? {
+        crate::outln!("{} Created directory: {}",
             style("ℹ").blue().bold(),
             style(organic_path.display()).dim()
         );
@@ -66,22 +131,144 @@ pub async fn execute(doc_type: DocType, title: Option<String>) -> Result<()> {
     }
 
     // Generate pure markdown content (no frontmatter)
-    let content = generate_document_content(doc_type, title.as_deref());
+    let content = generate_document_content(&doc_type.header()?, title.as_deref(), body.as_deref());
 
     // Write file as pure markdown
     write_file(&file_path, &content)
         .context(format!("Failed to create file: {}", file_path.display()))?;
 
-    println!("{} {}", 
+    // Record metadata overrides so push creates the document with them
+    if !overrides.is_empty() {
+        let path = file_path.to_string_lossy().to_string();
+        docuram_config.add_local_document(LocalOnlyDocument::new(path, &content, &overrides));
+        docuram_config.save()?;
+    }
+
+    // Track the document immediately; push swaps in the server uuid when it is created
+    if register {
+        let doc_info = provisional_document(&docuram_config, &category, &file_path, &content, &overrides)?;
+        crate::outln!("{} Registered in docuram.json with provisional UUID {}",
+            style("ℹ").blue().bold(),
+            style(&doc_info.uuid).dim()
+        );
+        docuram_config.documents.push(doc_info);
+        docuram_config.save()?;
+    }
+
+    crate::outln!("{} {}", 
         style("✓").green().bold(),
         style(format!("Created: {}", file_path.display())).green()
     );
-    println!();
-    println!("{}", style("Document ready for editing!").dim());
+    crate::outln!();
+    crate::outln!("{}", style("Document ready for editing!").dim());
+
+    Ok(file_path)
+}
+
+/// Editor command for --open: $VISUAL, then $EDITOR (like git), then the platform default
+fn editor_command() -> String {
+    ["VISUAL", "EDITOR"].iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|editor| editor.trim().to_string())
+        .find(|editor| !editor.is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string())
+}
+
+/// Run the editor on `path` and wait for it to exit (add --open)
+/// Without a terminal, or when the editor cannot be started, the path is printed instead.
+pub fn open_in_editor(path: &Path) {
+    let skip = |reason: String| {
+        crate::outln!("{} {}; edit {} yourself", style("ℹ").blue().bold(), reason, path.display());
+    };
+
+    if !std::io::stdin().is_terminal() {
+        return skip("Not opening an editor without a terminal".to_string());
+    }
+
+    let editor = editor_command();
+    // The editor value may carry arguments (`code --wait`), so let the shell split it like git does
+    let status = if cfg!(windows) {
+        let mut parts = editor.split_whitespace();
+        let program = parts.next().unwrap_or("notepad");
+        Command::new(program).args(parts).arg(path).status()
+    } else {
+        Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} \"$@\"", editor))
+            .arg(&editor)
+            .arg(path)
+            .status()
+    };
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => skip(format!("Editor '{}' exited with {}", editor, status)),
+        Err(e) => skip(format!("Could not start editor '{}': {}", editor, e)),
+    }
+}
 
-    Ok(())
+
+/// Category directory under docuram/ for `--category`
+/// Accepts a path relative to the working category (`impl/feature`, `docuram/impl/feature`) or a
+/// full category path under it (`<working>/impl/feature`); anything escaping it is rejected.
+fn relative_category(category: &str, working_category_path: &str) -> Result<String> {
+    let normalized = normalize_category_path(category);
+    let relative = normalized.strip_prefix(&format!("{}/", working_category_path))
+        .unwrap_or(&normalized);
+
+    let valid = !relative.is_empty()
+        && relative != working_category_path
+        && relative.split('/').all(|part| !part.is_empty() && part != "." && part != "..");
+    if !valid {
+        anyhow::bail!(
+            "Invalid category '{}': give a subcategory of {} (e.g. 'impl/feature' or '{}/impl/feature')",
+            category, working_category_path, working_category_path
+        );
+    }
+
+    Ok(relative.to_string())
 }
 
+/// Build a provisional docuram.json entry for a newly added file
+fn provisional_document(
+    docuram_config: &DocuramConfig,
+    category: &str,
+    file_path: &Path,
+    content: &str,
+    overrides: &DocumentDefaults,
+) -> Result<DocumentInfo> {
+    let category_path = format!("{}/{}", docuram_config.docuram.category_path, category);
+    let category_name = category.rsplit('/').next().unwrap_or(category).to_string();
+    let title = file_path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("Untitled.md")
+        .to_string();
+    let front_matter = default_front_matter(&category_path, &title, overrides)?;
+    let checksum = calculate_checksum(content);
+
+    Ok(DocumentInfo {
+        id: 0,
+        uuid: uuid::Uuid::new_v4().to_string(),
+        title,
+        category_id: 0,
+        category_name,
+        category_path,
+        category_uuid: String::new(),
+        doc_type: front_matter.doc_type,
+        version: 0,
+        path: file_path.to_string_lossy().to_string(),
+        checksum: checksum.clone(),
+        is_required: false,
+        local_checksum: Some(checksum),
+        local_content_checksum: Some(calculate_content_checksum(content)),
+        last_sync: None,
+        pending_deletion: false,
+        provisional: true,
+        keep_local: false,
+        local_name: None,
+        extra: Default::default(),
+    })
+}
 
 /// Get the next available document number for the given type
 fn get_next_document_number(organic_path: &Path, doc_type: DocType) -> Result<usize> {
@@ -124,14 +311,42 @@ fn generate_filename(doc_type: DocType, num: usize, title: Option<&str>) -> Stri
     }
 }
 
+/// Read seed content for the document body from a file or stdin
+fn read_seed_content(from_file: Option<&str>, from_stdin: bool) -> Result<Option<String>> {
+    if let Some(path) = from_file {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path))?;
+        return Ok(Some(content));
+    }
+
+    if from_stdin {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)
+            .context("Failed to read from stdin")?;
+        return Ok(Some(content));
+    }
+
+    Ok(None)
+}
+
 /// Generate document content
-fn generate_document_content(doc_type: DocType, title: Option<&str>) -> String {
-    let header = doc_type.default_header();
+/// `body` is appended after the header (and title) when provided. A header containing
+/// `{{title}}` places the title itself instead of getting a `# title` heading.
+fn generate_document_content(header: &str, title: Option<&str>, body: Option<&str>) -> String {
+    let header = header.trim_end();
 
-    match title {
+    let mut content = match title {
+        Some(t) if header.contains(TITLE_PLACEHOLDER) => format!("{}\n\n", header.replace(TITLE_PLACEHOLDER, t)),
         Some(t) => format!("{}\n\n# {}\n\n", header, t),
-        None => format!("{}\n\n", header),
+        None => format!("{}\n\n", header.replace(TITLE_PLACEHOLDER, "")),
+    };
+
+    if let Some(body) = body.filter(|b| !b.trim().is_empty()) {
+        content.push_str(body.trim_end());
+        content.push('\n');
     }
+
+    content
 }
 
 #[cfg(test)]
@@ -158,17 +373,42 @@ mod tests {
 
     #[test]
     fn test_generate_document_content_without_title() {
-        let content = generate_document_content(DocType::Req, None);
+        let content = generate_document_content(DocType::Req.default_header(), None, None);
         assert!(content.contains("**实现以下需求，并按Docuram规范生成并放置文档**"));
         assert!(!content.contains("# "));
     }
 
     #[test]
     fn test_generate_document_content_with_title() {
-        let content = generate_document_content(DocType::Req, Some("测试标题"));
+        let content = generate_document_content(DocType::Req.default_header(), Some("测试标题"), None);
         assert!(content.contains("**实现以下需求，并按Docuram规范生成并放置文档**"));
         assert!(content.contains("# 测试标题"));
     }
-}
 
+    #[test]
+    fn test_generate_document_content_with_body() {
+        let content = generate_document_content(DocType::Bug.default_header(), Some("Crash"), Some("panicked at main.rs:1\n\n"));
+        assert!(content.ends_with("# Crash\n\npanicked at main.rs:1\n"));
+    }
+
+    #[test]
+    fn test_relative_category() {
+        assert_eq!(relative_category("impl/feature", "Cat").unwrap(), "impl/feature");
+        assert_eq!(relative_category("docuram/impl/", "Cat").unwrap(), "impl");
+        assert_eq!(relative_category("Cat/impl/feature", "Cat").unwrap(), "impl/feature");
+        assert!(relative_category("Cat", "Cat").is_err());
+        assert!(relative_category("../outside", "Cat").is_err());
+        assert!(relative_category("impl//x", "Cat").is_err());
+        assert!(relative_category("", "Cat").is_err());
+    }
 
+    #[test]
+    fn test_generate_document_content_from_template() {
+        let template = "# Design: {{title}}\n\n## Goals\n";
+        assert_eq!(
+            generate_document_content(template, Some("Cache"), None),
+            "# Design: Cache\n\n## Goals\n\n"
+        );
+        assert_eq!(generate_filename(DocType::Knowledge, 3, None), "kb003.md");
+    }
+}
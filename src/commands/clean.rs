@@ -0,0 +1,167 @@
+use anyhow::{Context, Result};
+use console::style;
+use dialoguer::Confirm;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::commands::delete::remove_empty_directories;
+use crate::commands::list::{find_new_local_docs, scan_markdown_files};
+use crate::config::DocuramConfig;
+
+/// Directory holding read-only dependency documents (at project root)
+const DEPENDENCIES_DIR: &str = "dependencies";
+
+/// Directory holding the working category's documents
+const DOCURAM_DIR: &str = "docuram";
+
+/// Remove local files that docuram.json no longer references
+/// Every untracked file in dependencies/ is removed. In docuram/ only files left behind by
+/// documents deleted on the server are: other untracked files are new documents waiting for push.
+pub fn execute(force: bool) -> Result<()> {
+    crate::outln!("{}", style("Clean Orphaned Files").cyan().bold());
+    crate::outln!();
+
+    let mut docuram_config = DocuramConfig::load_with_migration()
+        .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+    drop_stale_removals(&mut docuram_config)?;
+
+    let orphans = find_orphans(&docuram_config)?;
+
+    let new_docs = find_new_local_docs(&docuram_config);
+    if !new_docs.is_empty() {
+        crate::outln!("{}", style(format!("ⓘ {} new document(s) in docuram/ kept (they are created on the next push)", new_docs.len())).dim());
+        crate::outln!();
+    }
+
+    if orphans.is_empty() {
+        crate::outln!("{}", style("No orphaned files found.").green());
+        return Ok(());
+    }
+
+    crate::outln!("{}", style(format!("Orphaned files ({}):", orphans.len())).bold());
+    for path in &orphans {
+        crate::item!("  - {}", path);
+    }
+    crate::outln!();
+
+    if !force {
+        let confirmed = Confirm::new()
+            .with_prompt("Delete these files?")
+            .default(false)
+            .interact()?;
+
+        if !confirmed {
+            crate::outln!();
+            crate::outln!("{}", style("Clean cancelled.").yellow());
+            return Ok(());
+        }
+        crate::outln!();
+    }
+
+    let deleted_count = remove_orphans(&mut docuram_config, &orphans)?;
+
+    crate::outln!();
+    crate::outln!("{} Removed {} orphaned file(s)", style("✓").green(), deleted_count);
+
+    Ok(())
+}
+
+/// Untracked dependency files and leftovers of documents deleted on the server, sorted
+pub fn find_orphans(docuram_config: &DocuramConfig) -> Result<Vec<String>> {
+    let layout = docuram_config.layout();
+
+    // Public dependencies are stored under their own path, the rest under local_path()
+    let referenced: HashSet<String> = docuram_config.all_documents()
+        .map(|d| d.local_path(&layout))
+        .chain(docuram_config.public_dependencies.iter()
+            .flat_map(|dep| dep.documents.iter().map(|d| d.path.clone())))
+        .collect();
+
+    let files: Vec<String> = if Path::new(DEPENDENCIES_DIR).is_dir() {
+        scan_markdown_files(DEPENDENCIES_DIR)?
+            .into_iter()
+            .map(|d| d.file_path)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let mut orphans = orphaned_files(files, &referenced);
+
+    if Path::new(DOCURAM_DIR).is_dir() {
+        let new_document_filter = docuram_config.new_document_filter();
+        orphans.extend(scan_markdown_files(DOCURAM_DIR)?
+            .into_iter()
+            .map(|d| d.file_path)
+            .filter(|path| {
+                new_document_filter.is_left_by_server_deletion(path)
+                    && docuram_config.get_local_document_by_path(path).is_none()
+            }));
+        orphans.sort();
+    }
+
+    Ok(orphans)
+}
+
+/// Delete orphaned files and the directories they leave empty, returning how many were deleted
+pub fn remove_orphans(docuram_config: &mut DocuramConfig, orphans: &[String]) -> Result<usize> {
+    let mut deleted_count = 0;
+    for path in orphans {
+        match fs::remove_file(path) {
+            Ok(_) => {
+                crate::item!("  {} Deleted file: {}", style("✓").green(), path);
+                deleted_count += 1;
+            }
+            Err(e) => {
+                crate::outln!("  {} Failed to delete file: {} - {}", style("✗").red(), path, e);
+            }
+        }
+    }
+
+    remove_empty_directories(Path::new(DEPENDENCIES_DIR))?;
+    remove_empty_directories(Path::new(DOCURAM_DIR))?;
+    drop_stale_removals(docuram_config)?;
+
+    Ok(deleted_count)
+}
+
+/// Forget server deletions whose leftover file is gone or tracked again
+fn drop_stale_removals(docuram_config: &mut DocuramConfig) -> Result<()> {
+    let layout = docuram_config.layout();
+    let tracked: HashSet<String> = docuram_config.all_documents()
+        .map(|d| d.local_path(&layout))
+        .collect();
+
+    let before = docuram_config.removed_documents.len();
+    docuram_config.removed_documents
+        .retain(|d| Path::new(&d.path).exists() && !tracked.contains(&d.path));
+    if docuram_config.removed_documents.len() != before {
+        docuram_config.save()?;
+    }
+    Ok(())
+}
+
+/// Files that no document in docuram.json points at, sorted
+fn orphaned_files(files: Vec<String>, referenced: &HashSet<String>) -> Vec<String> {
+    let mut orphans: Vec<String> = files.into_iter()
+        .filter(|path| !referenced.contains(path))
+        .collect();
+    orphans.sort();
+    orphans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orphaned_files() {
+        let referenced: HashSet<String> = ["dependencies/Cat/a.md".to_string()].into_iter().collect();
+        let files = vec![
+            "dependencies/Cat/z.md".to_string(),
+            "dependencies/Cat/a.md".to_string(),
+            "dependencies/Other/b.md".to_string(),
+        ];
+        assert_eq!(orphaned_files(files, &referenced), vec!["dependencies/Cat/z.md", "dependencies/Other/b.md"]);
+    }
+}
@@ -0,0 +1,226 @@
+use anyhow::Result;
+use std::borrow::Cow;
+use console::style;
+
+use crate::api::client;
+use crate::config::{CliConfig, DocuramConfig, ProjectConfig};
+use crate::utils::{self, logger};
+
+/// Where an effective setting came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingSource {
+    /// Built-in default
+    Default,
+    /// ~/.teamturbo-cli/config.toml
+    Global,
+    /// docuram.json or .docuram/config.toml
+    Project,
+    /// Environment variable
+    Env,
+    /// Command-line flag
+    Flag,
+}
+
+impl SettingSource {
+    fn label(&self) -> &'static str {
+        match self {
+            SettingSource::Default => "default",
+            SettingSource::Global => "global",
+            SettingSource::Project => "project",
+            SettingSource::Env => "env",
+            SettingSource::Flag => "flag",
+        }
+    }
+}
+
+/// One resolved setting
+#[derive(Debug)]
+pub struct Setting {
+    pub key: String,
+    pub value: String,
+    pub source: SettingSource,
+}
+
+impl Setting {
+    fn new(key: &str, value: impl ToString, source: SettingSource) -> Self {
+        Setting { key: key.to_string(), value: value.to_string(), source }
+    }
+
+    fn flag(key: &str, value: bool) -> Self {
+        let source = if value { SettingSource::Flag } else { SettingSource::Default };
+        Setting::new(key, value, source)
+    }
+}
+
+/// Execute config dump command
+pub async fn dump() -> Result<()> {
+    crate::outln!("{}", style("Effective Configuration").cyan().bold());
+    crate::outln!();
+
+    let global_path = CliConfig::config_path()?;
+    let global = CliConfig::load()?;
+    let project = ProjectConfig::load()?;
+    let docuram_path = DocuramConfig::config_path();
+    let docuram = if docuram_path.exists() {
+        Some(DocuramConfig::load()?)
+    } else {
+        None
+    };
+
+    crate::outln!("{}", style("Files:").bold());
+    for (label, path) in [
+        ("global", global_path),
+        ("project", ProjectConfig::config_path()),
+        ("docuram", docuram_path),
+    ] {
+        let state = if path.exists() { style("found").green() } else { style("not found").dim() };
+        crate::outln!("  {:<8} {} ({})", label, path.display(), state);
+    }
+    crate::outln!();
+
+    let settings = resolve_settings(&global, &project, docuram.as_ref());
+    let key_width = settings.iter().map(|s| s.key.len()).max().unwrap_or(0);
+    let value_width = settings.iter().map(|s| s.value.chars().count()).max().unwrap_or(0);
+
+    crate::outln!("{}", style("Settings:").bold());
+    for setting in &settings {
+        let source = match setting.source {
+            SettingSource::Default => style(setting.source.label()).dim(),
+            _ => style(setting.source.label()).cyan(),
+        };
+        crate::outln!("  {:<key_width$}  {:<value_width$}  ({})", setting.key, setting.value, source);
+    }
+
+    Ok(())
+}
+
+/// Keys `config set` and `config unset` accept
+const GLOBAL_KEYS: [&str; 2] = ["default_server", "user_agent"];
+
+/// Set (or, with None, remove) a value in the global config file
+pub fn set(key: &str, value: Option<String>) -> Result<()> {
+    let mut global = CliConfig::load()?;
+    let slot = match key {
+        "default_server" => &mut global.default_server,
+        "user_agent" => &mut global.user_agent,
+        _ => anyhow::bail!("Unknown setting '{}'. Settable keys: {}", key, GLOBAL_KEYS.join(", ")),
+    };
+
+    // Stored as the full URL login would connect to, so a subdomain works too
+    let value = match value {
+        Some(value) if key == "default_server" => Some(crate::commands::login::parse_domain(&value)),
+        value => value,
+    };
+
+    match &value {
+        Some(value) => crate::outln!("{} {} = {}", style("✓").green(), key, value),
+        None => crate::outln!("{} {} unset", style("✓").green(), key),
+    }
+    *slot = value;
+    global.save()
+}
+
+/// Resolve every setting the CLI uses, with the layer it came from
+/// Global flags are read from the state main() configured from the command line.
+pub fn resolve_settings(global: &CliConfig, project: &ProjectConfig, docuram: Option<&DocuramConfig>) -> Vec<Setting> {
+    let mut settings = vec![
+        Setting::flag("verbose", logger::is_verbose()),
+        Setting::flag("verbose_bodies", logger::is_verbose_bodies()),
+        Setting::flag("summary_only", logger::is_summary_only()),
+        Setting::flag("plain", logger::is_plain_forced()),
+        Setting::flag("offline", client::is_offline()),
+        Setting::flag("no_retry", !client::retry_enabled()),
+    ];
+
+    let max_redirects = client::max_redirects();
+    settings.push(Setting::new(
+        "max_redirects",
+        max_redirects,
+        if max_redirects == client::DEFAULT_MAX_REDIRECTS { SettingSource::Default } else { SettingSource::Flag },
+    ));
+
+    let timeout = client::request_timeout().map(|timeout| timeout.as_secs()).unwrap_or(0);
+    let timeout_source = if client::timeout_from_env().ok().flatten() == Some(timeout) {
+        SettingSource::Env
+    } else if timeout == client::DEFAULT_REQUEST_TIMEOUT_SECS {
+        SettingSource::Default
+    } else {
+        SettingSource::Flag
+    };
+    let timeout_value = if timeout == 0 { "none".to_string() } else { format!("{}s", timeout) };
+    settings.push(Setting::new("timeout", timeout_value, timeout_source));
+
+    let proxy_source = match client::proxy() {
+        None => SettingSource::Default,
+        Some(proxy) if client::proxy_from_env().as_deref() == Some(proxy) => SettingSource::Env,
+        Some(_) => SettingSource::Flag,
+    };
+    settings.push(Setting::new("proxy", client::proxy().unwrap_or("none"), proxy_source));
+
+    settings.push(match logger::log_file() {
+        Some(path) => Setting::new("log_file", path, SettingSource::Flag),
+        None => Setting::new("log_file", "none", SettingSource::Default),
+    });
+
+    settings.push(match global.user_agent.as_deref().filter(|ua| !ua.trim().is_empty()) {
+        Some(user_agent) => Setting::new("user_agent", user_agent, SettingSource::Global),
+        None => Setting::new("user_agent", client::default_user_agent(), SettingSource::Default),
+    });
+
+    settings.push(match &global.default_server {
+        Some(server) => Setting::new("default_server", server, SettingSource::Global),
+        None => Setting::new("default_server", "none", SettingSource::Default),
+    });
+
+    settings.push(match &project.defaults.doc_type {
+        Some(doc_type) => Setting::new("defaults.doc_type", doc_type, SettingSource::Project),
+        None => Setting::new("defaults.doc_type", utils::DEFAULT_DOC_TYPE, SettingSource::Default),
+    });
+
+    settings.push(match project.defaults.priority {
+        Some(priority) => Setting::new("defaults.priority", priority, SettingSource::Project),
+        None => Setting::new("defaults.priority", utils::DEFAULT_PRIORITY, SettingSource::Default),
+    });
+
+    if let Some(docuram) = docuram {
+        settings.push(Setting::new("server_url", docuram.server_url(), SettingSource::Project));
+        settings.push(Setting::new("category_path", &docuram.docuram.category_path, SettingSource::Project));
+
+        // Only who is logged in, never the token itself
+        let (login, source) = match global.resolve_auth(docuram.server_url()) {
+            // Only the environment token is owned
+            Some(Cow::Owned(auth)) => (format!("token from {}", auth.user_email), SettingSource::Env),
+            Some(auth) => (format!("{} (expires {})", auth.user_email, auth.expires_at), SettingSource::Global),
+            None => ("not logged in".to_string(), SettingSource::Global),
+        };
+        settings.push(Setting::new("login", login, source));
+    }
+
+    settings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(settings: &'a [Setting], key: &str) -> &'a Setting {
+        settings.iter().find(|s| s.key == key).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_settings_sources() {
+        let settings = resolve_settings(&CliConfig::default(), &ProjectConfig::default(), None);
+        assert_eq!(find(&settings, "user_agent").source, SettingSource::Default);
+        assert_eq!(find(&settings, "defaults.doc_type").value, utils::DEFAULT_DOC_TYPE);
+        assert!(settings.iter().all(|s| s.key != "server_url"));
+
+        let global = CliConfig { user_agent: Some("corp-proxy/1.0".to_string()), ..Default::default() };
+        let mut project = ProjectConfig::default();
+        project.defaults.priority = Some(3);
+        let settings = resolve_settings(&global, &project, None);
+        assert_eq!(find(&settings, "user_agent").source, SettingSource::Global);
+        assert_eq!(find(&settings, "defaults.priority").value, "3");
+        assert_eq!(find(&settings, "defaults.priority").source, SettingSource::Project);
+        assert_eq!(find(&settings, "defaults.doc_type").source, SettingSource::Default);
+    }
+}
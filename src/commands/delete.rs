@@ -5,12 +5,17 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::DocuramConfig;
-
-pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<()> {
-    println!();
-    println!("{}", style("Delete Documents").bold());
-    println!();
+use crate::config::{DocuramConfig, LocalLayout};
+use crate::utils::STANDARD_DIRS;
+
+/// Delete documents locally and (on next push) from the server
+/// `server_only` unpublishes uploaded documents but keeps the local files and entries
+/// `local_only` removes local files and entries without ever deleting from the server
+/// `dry_run` lists what would be deleted and stops before touching files or docuram.json
+pub async fn execute(paths: Vec<String>, force: bool, server_only: bool, local_only: bool, dry_run: bool, _verbose: bool) -> Result<()> {
+    crate::outln!();
+    crate::outln!("{}", style("Delete Documents").bold());
+    crate::outln!();
 
     if paths.is_empty() {
         anyhow::bail!("No paths specified. Please provide at least one document or directory path.");
@@ -35,7 +40,7 @@ pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<
         // Accept both existing and non-existing paths
         // Non-existing paths may still have documents in docuram.json to clean up
         if !absolute_path.exists() {
-            println!("{} Path does not exist locally: {}", style("ⓘ").dim(), path_str);
+            crate::outln!("{} Path does not exist locally: {}", style("ⓘ").dim(), path_str);
         }
 
         target_paths.push(absolute_path);
@@ -45,8 +50,8 @@ pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<
         anyhow::bail!("No paths specified.");
     }
 
-    // Get working category path
-    let working_category_path = &docuram_config.docuram.category_path;
+    // Layout for local_path() conversion
+    let layout = docuram_config.layout();
 
     // Collect all documents to delete (including documents in directories)
     let mut docs_to_delete = Vec::new();
@@ -55,11 +60,11 @@ pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<
     for target_path in &target_paths {
         if target_path.is_file() {
             // Single file - find matching document
-            if let Some(doc) = find_document_by_path(&docuram_config, target_path, working_category_path) {
+            if let Some(doc) = find_document_by_path(&docuram_config, target_path, &layout) {
                 docs_to_delete.push(doc);
                 files_to_delete.push(target_path.clone());
             } else {
-                println!("{} No document found for: {}",
+                crate::outln!("{} No document found for: {}",
                     style("⚠").yellow(),
                     target_path.display()
                 );
@@ -70,18 +75,18 @@ pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<
             let (dir_docs, dir_files) = find_documents_in_directory(
                 &docuram_config,
                 target_path,
-                working_category_path
+                &layout
             );
 
             if dir_docs.is_empty() {
                 // If still no documents found, try as a single file
-                if let Some(doc) = find_document_by_path(&docuram_config, target_path, working_category_path) {
+                if let Some(doc) = find_document_by_path(&docuram_config, target_path, &layout) {
                     docs_to_delete.push(doc);
                     if target_path.exists() {
                         files_to_delete.push(target_path.clone());
                     }
                 } else {
-                    println!("{} No document found for: {}",
+                    crate::outln!("{} No document found for: {}",
                         style("⚠").yellow(),
                         target_path.display()
                     );
@@ -94,7 +99,7 @@ pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<
     }
 
     if docs_to_delete.is_empty() {
-        println!("{}", style("No documents to delete.").yellow());
+        crate::outln!("{}", style("No documents to delete.").yellow());
         return Ok(());
     }
 
@@ -123,37 +128,76 @@ pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<
         }
     }
 
+    // Nothing to unpublish for documents that never reached the server
+    if server_only {
+        for doc in local_only_docs.iter().chain(config_only_docs.iter()) {
+            crate::outln!("{} Not on server, skipped: {}", style("ⓘ").dim(), doc.path);
+        }
+        local_only_docs.clear();
+        config_only_docs.clear();
+        files_to_delete.clear();
+
+        if uploaded_docs.is_empty() {
+            crate::outln!("{}", style("No uploaded documents to delete from server.").yellow());
+            return Ok(());
+        }
+        crate::outln!();
+    }
+
     // Display summary
-    println!("{}", style(format!("Found {} document(s) to delete:", docs_to_delete.len())).bold());
-    println!();
+    crate::outln!("{}", style(format!("Found {} document(s) to delete:", uploaded_docs.len() + local_only_docs.len() + config_only_docs.len())).bold());
+    crate::outln!();
 
     if !local_only_docs.is_empty() {
-        println!("{}", style("Local-only documents (file exists, will be deleted):").cyan());
+        crate::outln!("{}", style("Local-only documents (file exists, will be deleted):").cyan());
         for doc in &local_only_docs {
-            println!("  - {} ({})", doc.title, doc.path);
+            crate::outln!("  - {} ({})", doc.title, doc.path);
         }
-        println!();
+        crate::outln!();
     }
 
     if !uploaded_docs.is_empty() {
-        println!("{}", style("Uploaded documents (will be deleted from both local and server):").yellow());
+        let label = if server_only {
+            "Uploaded documents (will be deleted from server, local files kept):"
+        } else if local_only {
+            "Uploaded documents (local files deleted, server copies kept):"
+        } else {
+            "Uploaded documents (will be deleted from both local and server):"
+        };
+        crate::outln!("{}", style(label).yellow());
         for doc in &uploaded_docs {
-            println!("  - {} ({})", doc.title, doc.path);
+            crate::outln!("  - {} ({})", doc.title, doc.path);
         }
-        println!();
+        crate::outln!();
     }
 
     if !config_only_docs.is_empty() {
-        println!("{}", style("Config-only documents (file doesn't exist, will be removed from docuram.json):").dim());
+        crate::outln!("{}", style("Config-only documents (file doesn't exist, will be removed from docuram.json):").dim());
         for doc in &config_only_docs {
-            println!("  - {} ({})", doc.title, doc.path);
+            crate::outln!("  - {} ({})", doc.title, doc.path);
         }
-        println!();
+        crate::outln!();
+    }
+
+    if dry_run {
+        if !files_to_delete.is_empty() {
+            crate::outln!("{}", style(format!("Would delete {} local file(s):", files_to_delete.len())).bold());
+            for file_path in &files_to_delete {
+                crate::outln!("  - {}", file_path.display());
+            }
+            crate::outln!();
+        }
+        crate::outln!("{}", style("Dry run: nothing was deleted and docuram.json was not changed.").yellow());
+        return Ok(());
     }
 
     // Confirm deletion
     if !force {
-        let message = if !uploaded_docs.is_empty() {
+        let message = if server_only {
+            "This will mark documents for deletion from the server on next push (local files are kept). Continue?"
+        } else if local_only {
+            "This will delete local files only (nothing is deleted from the server). Continue?"
+        } else if !uploaded_docs.is_empty() {
             "This will delete files locally and mark uploaded documents for deletion (will be deleted from server on next push). Continue?"
         } else if !local_only_docs.is_empty() {
             "This will delete local files. Continue?"
@@ -167,54 +211,74 @@ pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<
             .interact()?;
 
         if !confirmed {
-            println!();
-            println!("{}", style("Deletion cancelled.").yellow());
+            crate::outln!();
+            crate::outln!("{}", style("Deletion cancelled.").yellow());
             return Ok(());
         }
     }
 
-    println!();
-    println!("{}", style("Deleting documents locally...").bold());
-    println!();
+    crate::outln!();
+    if !server_only {
+        crate::outln!("{}", style("Deleting documents locally...").bold());
+        crate::outln!();
+    }
 
     // Mark uploaded documents as pending deletion in docuram.json
-    let server_docs: Vec<_> = uploaded_docs.iter().collect();
+    // --local-only never touches the server copies
+    let server_docs: Vec<_> = if local_only { Vec::new() } else { uploaded_docs.iter().collect() };
     if !server_docs.is_empty() {
-        println!("{}", style("Marking documents for deletion from server...").dim());
+        crate::outln!("{}", style("Marking documents for deletion from server...").dim());
 
         for doc in &server_docs {
             // Mark for deletion in docuram.json
             if docuram_config.mark_for_deletion(&doc.uuid) {
-                println!("  {} Marked for deletion: {}", style("⏳").yellow(), doc.title);
+                if server_only {
+                    if let Some(info) = docuram_config.get_document_by_uuid_mut(&doc.uuid) {
+                        info.keep_local = true;
+                    }
+                }
+                crate::outln!("  {} Marked for deletion: {}", style("⏳").yellow(), doc.title);
             } else {
-                println!("  {} Document not found in config: {}", style("○").dim(), doc.title);
+                crate::outln!("  {} Document not found in config: {}", style("○").dim(), doc.title);
             }
         }
-        println!();
+        crate::outln!();
+    }
+
+    if server_only {
+        docuram_config.save()
+            .context("Failed to save docuram.json")?;
+
+        crate::outln!("{}", style("Summary:").bold());
+        crate::outln!("  {} document(s) marked for deletion from server (local files kept)", server_docs.len());
+        crate::outln!();
+        crate::outln!("{}", style("Note: Run 'teamturbo push' to delete marked documents from the server.").cyan());
+        crate::outln!("{}", style("The kept files stay unpublished: later pushes do not create them again.").dim());
+        return Ok(());
     }
 
     // Config-only documents will just be removed from docuram.json
     if !config_only_docs.is_empty() {
-        println!("{}", style("Will remove from config:").dim());
+        crate::outln!("{}", style("Will remove from config:").dim());
         for doc in &config_only_docs {
-            println!("  {} {}", style("○").dim(), doc.title);
+            crate::outln!("  {} {}", style("○").dim(), doc.title);
         }
-        println!();
+        crate::outln!();
     }
 
     // Delete local files
-    println!("{}", style("Deleting local files...").dim());
+    crate::outln!("{}", style("Deleting local files...").dim());
 
     for file_path in &files_to_delete {
         match fs::remove_file(file_path) {
             Ok(_) => {
-                println!("  {} Deleted file: {}",
+                crate::outln!("  {} Deleted file: {}",
                     style("✓").green(),
                     file_path.display()
                 );
             }
             Err(e) => {
-                println!("  {} Failed to delete file: {} - {}",
+                crate::outln!("  {} Failed to delete file: {} - {}",
                     style("✗").red(),
                     file_path.display(),
                     e
@@ -230,10 +294,13 @@ pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<
         }
     }
 
-    println!();
+    crate::outln!();
 
     // Update docuram.json - remove deleted documents
+    // Documents marked for server deletion stay until push has deleted them
+    let marked_uuids: HashSet<&str> = server_docs.iter().map(|d| d.uuid.as_str()).collect();
     let deleted_uuids: HashSet<String> = docs_to_delete.iter()
+        .filter(|d| !marked_uuids.contains(d.uuid.as_str()))
         .map(|d| d.uuid.clone())
         .collect();
 
@@ -260,21 +327,21 @@ pub async fn execute(paths: Vec<String>, force: bool, _verbose: bool) -> Result<
     docuram_config.save()
         .context("Failed to save docuram.json")?;
 
-    println!("{}", style("Summary:").bold());
-    println!("  {} file(s) deleted locally", files_to_delete.len());
-    println!("  {} document(s) removed from docuram.json", removed_from_config);
+    crate::outln!("{}", style("Summary:").bold());
+    crate::outln!("  {} file(s) deleted locally", files_to_delete.len());
+    crate::outln!("  {} document(s) removed from docuram.json", removed_from_config);
 
-    let marked_for_deletion = uploaded_docs.len();
+    let marked_for_deletion = server_docs.len();
     if marked_for_deletion > 0 {
-        println!("  {} document(s) marked for deletion from server", marked_for_deletion);
+        crate::outln!("  {} document(s) marked for deletion from server", marked_for_deletion);
     }
 
-    println!();
-    println!("{}", style("✓ Delete completed locally").green().bold());
+    crate::outln!();
+    crate::outln!("{}", style("✓ Delete completed locally").green().bold());
 
     if marked_for_deletion > 0 {
-        println!();
-        println!("{}", style("Note: Run 'teamturbo push' to delete marked documents from the server.").cyan());
+        crate::outln!();
+        crate::outln!("{}", style("Note: Run 'teamturbo push' to delete marked documents from the server.").cyan());
     }
 
     Ok(())
@@ -293,12 +360,12 @@ struct DocumentToDelete {
 fn find_document_by_path(
     docuram_config: &DocuramConfig,
     file_path: &Path,
-    working_category_path: &str,
+    layout: &LocalLayout,
 ) -> Option<DocumentToDelete> {
     // Try to match by path in docuram.json (documents and requires)
     for doc in docuram_config.all_documents() {
         // Use local_path() to get correct path (dependencies go in dependencies/ at project root)
-        let local_file_path = doc.local_path(working_category_path);
+        let local_file_path = doc.local_path(layout);
         let doc_path = PathBuf::from(&local_file_path);
         if doc_path == file_path || doc_path.canonicalize().ok() == file_path.canonicalize().ok() {
             return Some(DocumentToDelete {
@@ -342,7 +409,7 @@ fn find_document_by_path(
 fn find_documents_in_directory(
     docuram_config: &DocuramConfig,
     dir_path: &Path,
-    working_category_path: &str,
+    layout: &LocalLayout,
 ) -> (Vec<DocumentToDelete>, Vec<PathBuf>) {
     let mut docs = Vec::new();
     let mut files = Vec::new();
@@ -352,7 +419,7 @@ fn find_documents_in_directory(
     // Search in docuram.json (documents and requires)
     for doc in docuram_config.all_documents() {
         // Use local_path() to get correct path (dependencies go in dependencies/ at project root)
-        let local_file_path = doc.local_path(working_category_path);
+        let local_file_path = doc.local_path(layout);
         let doc_path = PathBuf::from(&local_file_path);
 
         // Try canonical path if file exists, otherwise use the path directly
@@ -410,7 +477,7 @@ fn find_documents_in_directory(
                     let (sub_docs, sub_files) = find_documents_in_directory(
                         docuram_config,
                         &file_path,
-                        working_category_path,
+                        layout,
                     );
                     for doc in sub_docs {
                         if seen_uuids.insert(doc.uuid.clone()) {
@@ -463,7 +530,7 @@ fn find_documents_in_directory(
 }
 
 /// Remove empty directories recursively
-fn remove_empty_directories(dir_path: &Path) -> Result<()> {
+pub fn remove_empty_directories(dir_path: &Path) -> Result<()> {
     if !dir_path.is_dir() {
         return Ok(());
     }
@@ -479,10 +546,14 @@ fn remove_empty_directories(dir_path: &Path) -> Result<()> {
     }
 
     // Then try to remove this directory if it's empty
-    // Don't remove the docuram/ or dependencies/ directories themselves
+    // Don't remove the docuram/ or dependencies/ directories themselves, or the standard
+    // subdirectories of docuram/
     let dir_name = dir_path.file_name();
+    let is_standard_dir = dir_path.parent().and_then(|p| p.file_name()) == Some(std::ffi::OsStr::new("docuram"))
+        && dir_name.and_then(|n| n.to_str()).is_some_and(|n| STANDARD_DIRS.contains(&n));
     if dir_name != Some(std::ffi::OsStr::new("docuram")) &&
-       dir_name != Some(std::ffi::OsStr::new("dependencies")) {
+       dir_name != Some(std::ffi::OsStr::new("dependencies")) &&
+       !is_standard_dir {
         if let Ok(mut entries) = fs::read_dir(dir_path) {
             if entries.next().is_none() {
                 let _ = fs::remove_dir(dir_path);
@@ -1,31 +1,72 @@
 use anyhow::{Context, Result};
 use console::style;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::api::ApiClient;
+use crate::api::client::DocumentContent;
+use crate::api::client::is_offline;
 use crate::config::{CliConfig, DocuramConfig};
-use crate::utils::{read_file, calculate_checksum};
+use crate::utils::{read_file, content_matches_checksum, extract_front_matter};
 
-pub async fn execute(document: Option<String>) -> Result<()> {
-    println!("{}", style("Document Diff").cyan().bold());
-    println!();
+/// Lines of unchanged context shown around each change in a unified diff
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// `content_only` ignores front matter changes when comparing with the last sync
+/// `from` compares `document` (a file path) against an earlier local copy instead
+/// `name_only` prints just the paths of modified documents, suitable for `push --document-list -`
+/// `stat` prints insertion/deletion counts against the remote instead of the full diff
+/// `version` compares `document` against that server version instead of the latest
+pub async fn execute(
+    document: Option<String>,
+    content_only: bool,
+    from: Option<String>,
+    name_only: bool,
+    stat: bool,
+    version: Option<i64>,
+) -> Result<()> {
+    if name_only {
+        let docuram_config = DocuramConfig::load()
+            .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+        for path in modified_document_paths(&docuram_config, Path::new("."), document.as_deref(), content_only) {
+            crate::outln!("{}", path);
+        }
+        return Ok(());
+    }
+
+    crate::outln!("{}", style("Document Diff").cyan().bold());
+    crate::outln!();
+
+    if let Some(from) = from {
+        let path = document.context("A file path is required with --from")?;
+        return diff_against_copy(&from, &path);
+    }
 
     // Load docuram config
     let docuram_config = DocuramConfig::load()
         .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
 
-    // Load CLI config
-    let cli_config = CliConfig::load()?;
+    // Offline mode compares against local state only, so no login is needed
+    let client = if is_offline() {
+        None
+    } else {
+        // Load CLI config
+        let cli_config = CliConfig::load()?;
 
-    let server_url = docuram_config.server_url();
+        let server_url = docuram_config.server_url();
 
-    // Get auth for this server
-    let auth = cli_config
-        .get_auth(server_url)
-        .context(format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
+        // Get auth for this server
+        let auth = cli_config
+            .resolve_auth(server_url)
+            .context(format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
 
-    // Create API client (unused for now, but needed for future remote diff)
-    let _client = ApiClient::new(server_url.to_string(), auth.access_token.clone());
+        Some(ApiClient::from_auth(server_url.to_string(), &auth))
+    };
+
+    if let Some(version) = version {
+        let selector = document.context("A document is required with --version")?;
+        let client = client.context("--version needs the server and cannot be used with --offline")?;
+        return diff_against_version(&client, &docuram_config, selector, version, stat).await;
+    }
 
     // Determine which documents to check
     let docs_to_check: Vec<_> = if let Some(uuid) = document {
@@ -40,12 +81,12 @@ pub async fn execute(document: Option<String>) -> Result<()> {
     };
 
     if docs_to_check.is_empty() {
-        println!("{}", style("No documents found").yellow());
+        crate::outln!("{}", style("No documents found").yellow());
         return Ok(());
     }
 
-    println!("Checking {} document(s)...", docs_to_check.len());
-    println!();
+    crate::outln!("Checking {} document(s)...", docs_to_check.len());
+    crate::outln!();
 
     // Check each document
     let mut modified_count = 0;
@@ -55,12 +96,12 @@ pub async fn execute(document: Option<String>) -> Result<()> {
 
     for doc_info in &docs_to_check {
         // Use local_path() to get correct path (dependencies go in working_category/dependencies/ subdirectory)
-        let working_category_path = &docuram_config.docuram.category_path;
-        let local_file_path = doc_info.local_path(working_category_path);
+        let layout = docuram_config.layout();
+        let local_file_path = doc_info.local_path(&layout);
         let file_path = PathBuf::from(&local_file_path);
 
         if !file_path.exists() {
-            println!("{} {} {}",
+            crate::outln!("{} {} {}",
                 style("missing:").red().bold(),
                 style(&doc_info.uuid).red(),
                 style(format!("({})", doc_info.title)).dim()
@@ -73,7 +114,7 @@ pub async fn execute(document: Option<String>) -> Result<()> {
         let current_content = match read_file(&file_path) {
             Ok(content) => content,
             Err(e) => {
-                println!("{} {} {}",
+                crate::outln!("{} {} {}",
                     style("error:").red().bold(),
                     style(&doc_info.uuid).red(),
                     style(format!("({})", e)).dim()
@@ -82,33 +123,43 @@ pub async fn execute(document: Option<String>) -> Result<()> {
             }
         };
 
-        let current_checksum = calculate_checksum(&current_content);
-
         // Check status based on local_checksum field in docuram.json
-        if let Some(ref local_checksum) = doc_info.local_checksum {
-            if current_checksum != *local_checksum {
+        if let Some(is_modified) = doc_info.differs_from_synced(&current_content, content_only) {
+            if is_modified {
                 // Modified since last sync
-                println!("{} {} {}",
+                crate::outln!("{} {} {}",
                     style("modified:").yellow().bold(),
                     style(&doc_info.uuid).yellow(),
                     style(format!("({})", doc_info.title)).dim()
                 );
                 modified_count += 1;
 
-                // Show line count diff
-                let new_lines = current_content.lines().count();
-                println!("  {} {} lines",
-                    style("→").dim(),
-                    style(format!("{}", new_lines)).cyan()
-                );
-            } else if current_checksum != doc_info.checksum {
+                // Distinguish front-matter-only edits from body changes
+                if doc_info.differs_from_synced(&current_content, true) == Some(false) {
+                    crate::outln!("  {} {}", style("→").dim(), style("front matter only").dim());
+                }
+
+                match &client {
+                    Some(client) => {
+                        show_remote_diff(client, &doc_info.uuid, &current_content, &local_file_path, stat).await;
+                    }
+                    None => {
+                        // Offline: no remote copy to compare with, show the line count only
+                        let new_lines = current_content.lines().count();
+                        crate::outln!("  {} {} lines",
+                            style("→").dim(),
+                            style(format!("{}", new_lines)).cyan()
+                        );
+                    }
+                }
+            } else if !content_matches_checksum(&current_content, &doc_info.checksum) {
                 // Local matches saved state but remote checksum is different
-                println!("{} {} {}",
+                crate::outln!("{} {} {}",
                     style("outdated:").cyan().bold(),
                     style(&doc_info.uuid).cyan(),
                     style(format!("({})", doc_info.title)).dim()
                 );
-                println!("  {} Remote has updates available",
+                crate::outln!("  {} Remote has updates available",
                     style("→").dim()
                 );
                 up_to_date_count += 1;
@@ -116,7 +167,7 @@ pub async fn execute(document: Option<String>) -> Result<()> {
                 // Up to date
                 if docs_to_check.len() == 1 {
                     // Only show if checking single document
-                    println!("{} {} {}",
+                    crate::outln!("{} {} {}",
                         style("clean:").green().bold(),
                         style(&doc_info.uuid).green(),
                         style(format!("({})", doc_info.title)).dim()
@@ -126,7 +177,7 @@ pub async fn execute(document: Option<String>) -> Result<()> {
             }
         } else {
             // No local_checksum, document hasn't been synced yet
-            println!("{} {} {}",
+            crate::outln!("{} {} {}",
                 style("untracked:").magenta().bold(),
                 style(&doc_info.uuid).magenta(),
                 style(format!("({})", doc_info.title)).dim()
@@ -135,40 +186,555 @@ pub async fn execute(document: Option<String>) -> Result<()> {
         }
     }
 
-    println!();
-    println!("{}", style("Summary:").bold());
+    crate::outln!();
+    crate::outln!("{}", style("Summary:").bold());
     if modified_count > 0 {
-        println!("  {} {} document(s) modified",
+        crate::outln!("  {} {} document(s) modified",
             style("●").yellow(),
             style(modified_count).yellow()
         );
     }
     if untracked_count > 0 {
-        println!("  {} {} document(s) untracked",
+        crate::outln!("  {} {} document(s) untracked",
             style("●").magenta(),
             style(untracked_count).magenta()
         );
     }
     if missing_count > 0 {
-        println!("  {} {} document(s) missing",
+        crate::outln!("  {} {} document(s) missing",
             style("●").red(),
             style(missing_count).red()
         );
     }
     if up_to_date_count > 0 {
-        println!("  {} {} document(s) up to date",
+        crate::outln!("  {} {} document(s) up to date",
             style("●").green(),
             style(up_to_date_count).green()
         );
     }
 
-    println!();
+    crate::outln!();
     if modified_count > 0 || untracked_count > 0 {
-        println!("{}", style("Use 'teamturbo push' to upload changes").dim());
+        crate::outln!("{}", style("Use 'teamturbo push' to upload changes").dim());
     }
     if missing_count > 0 {
-        println!("{}", style("Use 'teamturbo pull' to download missing documents").dim());
+        crate::outln!("{}", style("Use 'teamturbo pull' to download missing documents").dim());
+    }
+
+    Ok(())
+}
+
+/// Local paths (relative to the project root `root`) of documents modified since their last sync
+/// `document` restricts the check to one UUID.
+fn modified_document_paths(config: &DocuramConfig, root: &Path, document: Option<&str>, content_only: bool) -> Vec<String> {
+    let layout = config.layout();
+    config
+        .all_documents()
+        .filter(|doc| document.is_none_or(|uuid| doc.uuid == uuid))
+        .filter_map(|doc| {
+            let path = doc.local_path(&layout);
+            let content = read_file(root.join(&path)).ok()?;
+            (doc.differs_from_synced(&content, content_only) == Some(true)).then_some(path)
+        })
+        .collect()
+}
+
+/// Show a unified diff between an earlier copy of a file and the current file
+/// `from` is either the copy itself or a directory mirroring the project layout
+/// (e.g. .docuram/quarantine), in which case `path` is looked up inside it.
+fn diff_against_copy(from: &str, path: &str) -> Result<()> {
+    let current_path = Path::new(path);
+    let from_dir = Path::new(from);
+    let copy_path = if from_dir.is_dir() {
+        from_dir.join(current_path)
+    } else {
+        from_dir.to_path_buf()
+    };
+
+    if !copy_path.is_file() {
+        anyhow::bail!("No earlier copy found at {}", copy_path.display());
     }
 
+    let old = read_file(&copy_path)
+        .with_context(|| format!("Failed to read {}", copy_path.display()))?;
+    // A deleted file diffs against nothing, showing everything the copy had
+    let new = if current_path.exists() {
+        read_file(current_path)
+            .with_context(|| format!("Failed to read {}", current_path.display()))?
+    } else {
+        String::new()
+    };
+
+    let diff = unified_diff(&old, &new, &copy_path.to_string_lossy(), path);
+    if diff.is_empty() {
+        crate::outln!("{}", style("No differences").green());
+        return Ok(());
+    }
+
+    print_unified_diff(&diff, "");
     Ok(())
 }
+
+/// Compare one document's working copy with a historical server version
+async fn diff_against_version(client: &ApiClient, config: &DocuramConfig, selector: String, version: i64, stat: bool) -> Result<()> {
+    let uuid = config.resolve_document_selectors(vec![selector]).remove(0);
+    let doc_info = config.all_documents()
+        .find(|doc| doc.uuid == uuid)
+        .with_context(|| format!("Document not found in docuram.json: {}", uuid))?;
+    let local_path = doc_info.local_path(&config.layout());
+    let local_content = read_file(&local_path)
+        .with_context(|| format!("Failed to read {}", local_path))?;
+
+    let remote = client.download_document_version(&uuid, version).await?;
+
+    crate::outln!("{} {} {}",
+        style(format!("v{}:", version)).cyan().bold(),
+        style(&uuid).cyan(),
+        style(format!("({})", doc_info.title)).dim()
+    );
+    print_content_diff(remote, &local_content, &local_path, stat);
+    Ok(())
+}
+
+/// Download the remote copy of a modified document and show how the local file differs
+async fn show_remote_diff(client: &ApiClient, uuid: &str, local_content: &str, local_path: &str, stat: bool) {
+    match client.download_document(uuid).await {
+        Ok(remote) => print_content_diff(remote, local_content, local_path, stat),
+        Err(e) => {
+            crate::outln!("  {} {}", style("→").dim(), style(format!("Could not fetch remote copy: {}", e)).yellow());
+        }
+    }
+}
+
+/// Show how the local content differs from a server copy
+/// Front matter is stripped from both sides so metadata-only changes do not show up.
+fn print_content_diff(remote: DocumentContent, local_content: &str, local_path: &str, stat: bool) {
+    let remote_content = remote.content.unwrap_or_default();
+    let old = extract_front_matter(&remote_content).1;
+    let new = extract_front_matter(local_content).1;
+
+    if stat {
+        let (insertions, deletions) = diff_stat(old, new);
+        crate::outln!("  {} {} insertion(s)(+), {} deletion(s)(-)",
+            style("→").dim(),
+            style(insertions).green(),
+            style(deletions).red()
+        );
+        return;
+    }
+
+    let diff = unified_diff(old, new, &format!("remote/{} (v{})", local_path, remote.version), local_path);
+    if diff.is_empty() {
+        crate::outln!("  {} {}", style("→").dim(), style("no content differences from remote").dim());
+    } else {
+        print_unified_diff(&diff, "  ");
+    }
+}
+
+/// Print a unified diff with added lines in green and removed lines in red
+fn print_unified_diff(diff: &str, indent: &str) {
+    for line in diff.lines() {
+        if line.starts_with("---") || line.starts_with("+++") {
+            crate::outln!("{}{}", indent, style(line).bold());
+        } else if line.starts_with("@@") {
+            crate::outln!("{}{}", indent, style(line).cyan());
+        } else if line.starts_with('-') {
+            crate::outln!("{}{}", indent, style(line).red());
+        } else if line.starts_with('+') {
+            crate::outln!("{}{}", indent, style(line).green());
+        } else {
+            crate::outln!("{}{}", indent, line);
+        }
+    }
+}
+
+/// Count inserted and deleted lines between `old` and `new`
+fn diff_stat(old: &str, new: &str) -> (usize, usize) {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    diff_lines(&old_lines, &new_lines)
+        .iter()
+        .fold((0, 0), |(insertions, deletions), (kind, _)| match kind {
+            LineChange::Added => (insertions + 1, deletions),
+            LineChange::Removed => (insertions, deletions + 1),
+            LineChange::Same => (insertions, deletions),
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineChange {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Largest LCS table (in cells) built in one piece; bigger inputs are split first
+const LCS_TABLE_MAX_CELLS: usize = 1 << 22;
+
+/// Line-level edit script between `old` and `new` (longest common subsequence)
+/// The common prefix and suffix are matched directly and the rest is split in half (Hirschberg)
+/// until it fits in a small table, so memory stays linear in the input size.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(LineChange, &'a str)> {
+    let (n, m) = (old.len(), new.len());
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut changes = Vec::with_capacity(n.max(m));
+    changes.extend(old[..prefix].iter().map(|line| (LineChange::Same, *line)));
+    diff_middle(&old[prefix..n - suffix], &new[prefix..m - suffix], &mut changes);
+    changes.extend(old[n - suffix..].iter().map(|line| (LineChange::Same, *line)));
+    changes
+}
+
+/// Append the edit script between `old` and `new`, splitting inputs too big for one table
+fn diff_middle<'a>(old: &[&'a str], new: &[&'a str], changes: &mut Vec<(LineChange, &'a str)>) {
+    let (n, m) = (old.len(), new.len());
+    if n <= 1 || m <= 1 || n.saturating_mul(m) <= LCS_TABLE_MAX_CELLS {
+        diff_table(old, new, changes);
+        return;
+    }
+
+    // Split `new` where an LCS of the two halves of `old` meets
+    let mid = n / 2;
+    let forward = lcs_lengths(&old[..mid], new);
+    let old_tail: Vec<&str> = old[mid..].iter().rev().copied().collect();
+    let new_reversed: Vec<&str> = new.iter().rev().copied().collect();
+    let backward = lcs_lengths(&old_tail, &new_reversed);
+    let split = (0..=m)
+        .max_by_key(|&j| (forward[j] + backward[m - j], std::cmp::Reverse(j)))
+        .unwrap_or(0);
+
+    diff_middle(&old[..mid], &new[..split], changes);
+    diff_middle(&old[mid..], &new[split..], changes);
+}
+
+/// LCS length of `old` with every prefix of `new` (index j is the prefix of length j), in O(|new|) memory
+fn lcs_lengths(old: &[&str], new: &[&str]) -> Vec<u32> {
+    let mut prev = vec![0u32; new.len() + 1];
+    let mut cur = vec![0u32; new.len() + 1];
+    for line in old {
+        for (j, other) in new.iter().enumerate() {
+            cur[j + 1] = if line == other { prev[j] + 1 } else { prev[j + 1].max(cur[j]) };
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev
+}
+
+/// Append the edit script between `old` and `new` from a full LCS table
+fn diff_table<'a>(old: &[&'a str], new: &[&'a str], changes: &mut Vec<(LineChange, &'a str)>) {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            changes.push((LineChange::Same, old[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            changes.push((LineChange::Removed, old[i]));
+            i += 1;
+        } else {
+            changes.push((LineChange::Added, new[j]));
+            j += 1;
+        }
+    }
+}
+
+/// Render a unified diff (empty when the contents are identical)
+fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let changes = diff_lines(&old_lines, &new_lines);
+
+    let changed: Vec<usize> = changes.iter()
+        .enumerate()
+        .filter(|(_, (kind, _))| *kind != LineChange::Same)
+        .map(|(idx, _)| idx)
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // Group changes whose context windows touch into the same hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &idx in &changed {
+        let start = idx.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (idx + DIFF_CONTEXT_LINES + 1).min(changes.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+    for (start, end) in hunks {
+        // Line numbers where the hunk starts in each file
+        let old_before = changes[..start].iter().filter(|(k, _)| *k != LineChange::Added).count();
+        let new_before = changes[..start].iter().filter(|(k, _)| *k != LineChange::Removed).count();
+        let hunk = &changes[start..end];
+        let old_count = hunk.iter().filter(|(k, _)| *k != LineChange::Added).count();
+        let new_count = hunk.iter().filter(|(k, _)| *k != LineChange::Removed).count();
+
+        out.push_str(&format!("@@ -{},{} +{},{} @@\n",
+            if old_count == 0 { old_before } else { old_before + 1 },
+            old_count,
+            if new_count == 0 { new_before } else { new_before + 1 },
+            new_count
+        ));
+        for (kind, line) in hunk {
+            let prefix = match kind {
+                LineChange::Same => ' ',
+                LineChange::Removed => '-',
+                LineChange::Added => '+',
+            };
+            out.push(prefix);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Markers written around conflicting lines by `pull --merge` and `sync --strategy manual`
+const MERGE_MARKER_LOCAL: &str = "<<<<<<< local";
+const MERGE_MARKER_SEPARATOR: &str = "=======";
+const MERGE_MARKER_REMOTE: &str = ">>>>>>> remote";
+
+/// Interleave local and remote content, wrapping each differing run of lines in merge markers
+pub fn merge_with_markers(local: &str, remote: &str) -> String {
+    let local_lines: Vec<&str> = local.lines().collect();
+    let remote_lines: Vec<&str> = remote.lines().collect();
+    let changes = diff_lines(&local_lines, &remote_lines);
+
+    let mut out = String::new();
+    let mut idx = 0;
+    while idx < changes.len() {
+        if changes[idx].0 == LineChange::Same {
+            push_lines(&mut out, &[changes[idx].1]);
+            idx += 1;
+            continue;
+        }
+
+        let end = changes[idx..].iter()
+            .position(|(kind, _)| *kind == LineChange::Same)
+            .map_or(changes.len(), |offset| idx + offset);
+        let run = &changes[idx..end];
+        let side = |wanted: LineChange| -> Vec<&str> {
+            run.iter().filter(|(kind, _)| *kind == wanted).map(|(_, line)| *line).collect()
+        };
+        push_conflict(&mut out, &side(LineChange::Removed), &side(LineChange::Added));
+        idx = end;
+    }
+    out
+}
+
+/// Three-way merge of local and remote edits made since `base`
+/// Changes on one side only are applied; overlapping changes are wrapped in merge markers.
+/// Returns the merged content and the number of conflicting hunks.
+pub fn merge_three_way(base: &str, local: &str, remote: &str) -> (String, usize) {
+    let base_lines: Vec<&str> = base.lines().collect();
+    let local_lines: Vec<&str> = local.lines().collect();
+    let remote_lines: Vec<&str> = remote.lines().collect();
+    let local_match = base_line_matches(&base_lines, &local_lines);
+    let remote_match = base_line_matches(&base_lines, &remote_lines);
+
+    let mut out = String::new();
+    let mut conflicts = 0;
+    let (mut i, mut a, mut b) = (0, 0, 0);
+    while i < base_lines.len() || a < local_lines.len() || b < remote_lines.len() {
+        // Base lines kept on both sides are stable
+        if i < base_lines.len() && local_match[i] == Some(a) && remote_match[i] == Some(b) {
+            push_lines(&mut out, &[base_lines[i]]);
+            i += 1;
+            a += 1;
+            b += 1;
+            continue;
+        }
+
+        // The unstable chunk runs up to the next base line both sides kept
+        let next = (i..base_lines.len()).find(|&k| local_match[k].is_some() && remote_match[k].is_some());
+        let (k, a_end, b_end) = match next {
+            Some(k) => (k, local_match[k].unwrap_or(a), remote_match[k].unwrap_or(b)),
+            None => (base_lines.len(), local_lines.len(), remote_lines.len()),
+        };
+        let (base_chunk, local_chunk, remote_chunk) = (&base_lines[i..k], &local_lines[a..a_end], &remote_lines[b..b_end]);
+
+        if local_chunk == base_chunk || local_chunk == remote_chunk {
+            push_lines(&mut out, remote_chunk);
+        } else if remote_chunk == base_chunk {
+            push_lines(&mut out, local_chunk);
+        } else {
+            push_conflict(&mut out, local_chunk, remote_chunk);
+            conflicts += 1;
+        }
+        (i, a, b) = (k, a_end, b_end);
+    }
+    (out, conflicts)
+}
+
+/// For each base line, the index of the line it is kept as in `other`
+fn base_line_matches(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+    let (mut i, mut j) = (0, 0);
+    for (kind, _) in diff_lines(base, other) {
+        match kind {
+            LineChange::Same => {
+                matches[i] = Some(j);
+                i += 1;
+                j += 1;
+            }
+            LineChange::Removed => i += 1,
+            LineChange::Added => j += 1,
+        }
+    }
+    matches
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+    for line in lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+fn push_conflict(out: &mut String, local: &[&str], remote: &[&str]) {
+    push_lines(out, &[MERGE_MARKER_LOCAL]);
+    push_lines(out, local);
+    push_lines(out, &[MERGE_MARKER_SEPARATOR]);
+    push_lines(out, remote);
+    push_lines(out, &[MERGE_MARKER_REMOTE]);
+}
+
+/// Whether `content` still holds merge markers written by `merge_with_markers`
+pub fn has_conflict_markers(content: &str) -> bool {
+    let mut lines = content.lines();
+    lines.any(|line| line == MERGE_MARKER_LOCAL) && lines.any(|line| line == MERGE_MARKER_REMOTE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LocalLayout;
+    use crate::utils::{calculate_checksum, select_documents};
+
+    #[test]
+    fn test_name_only_output_selects_push_documents() {
+        let root = std::env::temp_dir().join(format!("teamturbo-name-only-{}", uuid::Uuid::new_v4()));
+        let mut config: DocuramConfig = serde_json::from_str(r#"{
+            "project": {"id": 1, "name": "Demo", "description": null, "url": "http://127.0.0.1:9", "created_at": ""},
+            "docuram": {"version": "1.0.0", "category_id": 1, "category_name": "Cat", "category_uuid": "c1",
+                        "category_path": "Cat", "task_id": null, "task_name": null},
+            "documents": [
+                {"id": 1, "uuid": "d1", "title": "a.md", "category_id": 1, "category_name": "organic", "category_path": "Cat/organic",
+                 "category_uuid": "c1", "doc_type": "knowledge", "version": 1, "path": "docuram/Cat/organic/a.md",
+                 "checksum": "", "is_required": false, "local_checksum": "sha256:0"},
+                {"id": 2, "uuid": "d2", "title": "b.md", "category_id": 1, "category_name": "organic", "category_path": "Cat/organic",
+                 "category_uuid": "c1", "doc_type": "knowledge", "version": 1, "path": "docuram/Cat/organic/b.md",
+                 "checksum": "", "is_required": false}
+            ],
+            "requires": [], "dependencies": [], "category_tree": null
+        }"#).unwrap();
+
+        // a.md was edited since its last sync, b.md still matches
+        for doc in config.all_documents() {
+            let path = root.join(doc.local_path(&LocalLayout::new("Cat")));
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, "edited").unwrap();
+        }
+        config.documents[1].local_checksum = Some(calculate_checksum("edited"));
+
+        let modified = modified_document_paths(&config, &root, None, false);
+        assert_eq!(modified, vec![config.documents[0].local_path(&LocalLayout::new("Cat"))]);
+
+        let list = root.join("changed.txt");
+        std::fs::write(&list, format!("{}\n", modified.join("\n"))).unwrap();
+        let selected = select_documents(&config, Vec::new(), list.to_str()).unwrap();
+        assert_eq!(selected, Some(vec!["d1".to_string()]));
+
+        std::fs::write(&list, "\n").unwrap();
+        assert_eq!(select_documents(&config, Vec::new(), list.to_str()).unwrap(), None);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_diff_stat_ignores_front_matter() {
+        let remote = "---\ntitle: A\n---\n# A\nold line\nkept\n";
+        let local = "---\ntitle: Renamed\n---\n# A\nnew line\nkept\nadded\n";
+        let stat = diff_stat(extract_front_matter(remote).1, extract_front_matter(local).1);
+        assert_eq!(stat, (2, 1));
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "old", "new"), "");
+
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n";
+        assert_eq!(
+            unified_diff(old, new, "old", "new"),
+            "--- old\n+++ new\n@@ -2,8 +2,9 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n 9\n+10\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_with_markers() {
+        assert_eq!(merge_with_markers("a\nb\n", "a\nb\n"), "a\nb\n");
+
+        let merged = merge_with_markers("a\nlocal\nc\n", "a\nremote\nc\nd\n");
+        assert_eq!(
+            merged,
+            "a\n<<<<<<< local\nlocal\n=======\nremote\n>>>>>>> remote\nc\n<<<<<<< local\n=======\nd\n>>>>>>> remote\n"
+        );
+        assert!(has_conflict_markers(&merged));
+        assert!(!has_conflict_markers("a\n=======\nb\n"));
+    }
+
+    #[test]
+    fn test_merge_three_way() {
+        let base = "title\none\ntwo\nthree\n";
+        let local = "title\nONE\ntwo\nthree\n";
+        let remote = "title\none\ntwo\nthree\nfour\n";
+        assert_eq!(merge_three_way(base, local, remote), ("title\nONE\ntwo\nthree\nfour\n".to_string(), 0));
+
+        // The same edit on both sides is not a conflict
+        assert_eq!(merge_three_way(base, local, local), (local.to_string(), 0));
+
+        let (merged, conflicts) = merge_three_way(base, local, "title\nuno\ntwo\nthree\n");
+        assert_eq!(conflicts, 1);
+        assert_eq!(merged, "title\n<<<<<<< local\nONE\n=======\nuno\n>>>>>>> remote\ntwo\nthree\n");
+    }
+
+    #[test]
+    fn test_diff_lines_splits_large_inputs() {
+        // 3000 x 3000 lines is over LCS_TABLE_MAX_CELLS, so the middle is split before diffing
+        let old: Vec<String> = (0..3000).map(|i| format!("line {}", i % 7)).collect();
+        let new: Vec<String> = (0..3000).map(|i| format!("line {}", i % 5)).collect();
+        let old: Vec<&str> = old.iter().map(String::as_str).collect();
+        let new: Vec<&str> = new.iter().map(String::as_str).collect();
+
+        let changes = diff_lines(&old, &new);
+        let side = |skip: LineChange| changes.iter().filter(|(k, _)| *k != skip).map(|(_, l)| *l).collect::<Vec<_>>();
+        assert_eq!(side(LineChange::Added), old);
+        assert_eq!(side(LineChange::Removed), new);
+
+        let mut table = Vec::new();
+        diff_table(&old, &new, &mut table);
+        let same = |c: &[(LineChange, &str)]| c.iter().filter(|(k, _)| *k == LineChange::Same).count();
+        assert_eq!(same(&changes), same(&table));
+    }
+}
@@ -0,0 +1,107 @@
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::config::DocuramConfig;
+use crate::utils::{extract_front_matter, write_file};
+
+/// Layout of the exported bundle
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// One markdown file with a heading per document
+    Markdown,
+    /// A JSON array of documents
+    Json,
+}
+
+/// One document in a JSON export
+#[derive(Debug, Serialize)]
+struct ExportEntry {
+    uuid: String,
+    title: String,
+    category_path: String,
+    /// Document body without front matter
+    content: String,
+}
+
+/// Bundle the local copies of all tracked documents into one file
+/// Content comes from disk only; documents whose file is missing are skipped with a warning.
+pub fn execute(format: ExportFormat, output: String) -> Result<()> {
+    crate::outln!("{}", style("Export Documents").cyan().bold());
+    crate::outln!();
+
+    let docuram_config = DocuramConfig::load_with_migration()
+        .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+    let layout = docuram_config.layout();
+
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    for doc in docuram_config.all_documents() {
+        let local_path = doc.local_path(&layout);
+        let content = match fs::read_to_string(&local_path) {
+            Ok(content) => content,
+            Err(e) => {
+                crate::outln!("{} Skipping {}: {}", style("⚠").yellow(), local_path, e);
+                skipped += 1;
+                continue;
+            }
+        };
+
+        entries.push(ExportEntry {
+            uuid: doc.uuid.clone(),
+            title: doc.title.clone(),
+            category_path: doc.category_path.clone(),
+            content: extract_front_matter(&content).1.to_string(),
+        });
+    }
+
+    let bundle = match format {
+        ExportFormat::Markdown => render_markdown(&docuram_config.project.name, &entries),
+        ExportFormat::Json => serde_json::to_string_pretty(&entries)
+            .context("Failed to serialize documents")?,
+    };
+    write_file(Path::new(&output), &bundle)
+        .with_context(|| format!("Failed to write {}", output))?;
+
+    if skipped > 0 {
+        crate::outln!();
+    }
+    crate::outln!("{} Exported {} document(s) to {}", style("✓").green(), entries.len(), output);
+    if skipped > 0 {
+        crate::outln!("{}", style(format!("⚠ {} document(s) skipped (file missing or unreadable)", skipped)).yellow());
+    }
+
+    Ok(())
+}
+
+/// Concatenate documents under a heading each, separated by horizontal rules
+fn render_markdown(project_name: &str, entries: &[ExportEntry]) -> String {
+    let mut bundle = format!("# {}\n", project_name);
+    for entry in entries {
+        bundle.push_str(&format!("\n## {}\n\n_Category: {}_\n\n", entry.title, entry.category_path));
+        bundle.push_str(entry.content.trim_end());
+        bundle.push_str("\n\n---\n");
+    }
+    bundle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown() {
+        let entries = vec![ExportEntry {
+            uuid: "u1".to_string(),
+            title: "a.md".to_string(),
+            category_path: "Cat/impl".to_string(),
+            content: "Body\n\n".to_string(),
+        }];
+        assert_eq!(
+            render_markdown("Demo", &entries),
+            "# Demo\n\n## a.md\n\n_Category: Cat/impl_\n\nBody\n\n---\n"
+        );
+    }
+}
@@ -3,16 +3,31 @@ use console::style;
 use regex::Regex;
 
 use crate::api::ApiClient;
+use crate::api::client::Recipient;
+use crate::commands::mv::resolve_target_category;
 use crate::config::{CliConfig, DocuramConfig};
 use crate::utils::logger;
 
-/// Execute feedback command
-pub async fn execute(targets: Vec<String>, message: String, verbose: bool) -> Result<()> {
-    println!("{}", style("Send Feedback").cyan().bold());
-    println!();
+/// Targets the server accepts in a single feedback request
+const FEEDBACK_BATCH_SIZE: usize = 10;
+
+/// Overall target limit, larger lists are split into batches
+const MAX_FEEDBACK_TARGETS: usize = 100;
 
-    // Validate inputs
-    validate_inputs(&targets, &message)?;
+/// Execute feedback command
+/// With `category`, every document directly in that category is targeted as well
+/// (relative to the working category or a full path), counting towards the same limit.
+pub async fn execute(targets: Vec<String>, category: Option<String>, message: String, reply_to: Option<String>, verbose: bool) -> Result<()> {
+    crate::outln!("{}", style("Send Feedback").cyan().bold());
+    crate::outln!();
+
+    // Validate inputs; documents found through --category are checked once resolved
+    if category.is_none() {
+        validate_inputs(&targets, &message)?;
+    } else {
+        validate_uuids(&targets)?;
+        validate_message(&message)?;
+    }
 
     // Load docuram config
     let docuram_config = DocuramConfig::load()
@@ -27,53 +42,119 @@ pub async fn execute(targets: Vec<String>, message: String, verbose: bool) -> Re
 
     // Get auth for this server
     let auth = cli_config
-        .get_auth(server_url)
+        .resolve_auth(server_url)
         .context(format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
 
     // Create API client
-    let client = ApiClient::new(server_url.to_string(), auth.access_token.clone());
+    let client = ApiClient::from_auth(server_url.to_string(), &auth);
+
+    let mut targets = targets;
+    if let Some(category) = &category {
+        let merged_category_paths: Vec<&str> = docuram_config.merged_categories.iter()
+            .map(|m| m.category_path.as_str())
+            .collect();
+        let category_path = resolve_target_category(category, &docuram_config.docuram.category_path, &merged_category_paths);
+
+        let category_targets: Vec<String> = client.get_category_documents(&category_path).await
+            .with_context(|| format!("Failed to list documents in {}", category_path))?
+            .into_iter()
+            .map(|doc| doc.uuid)
+            .filter(|uuid| !targets.contains(uuid))
+            .collect();
+        if category_targets.is_empty() && targets.is_empty() {
+            anyhow::bail!("No documents found in category {}", category_path);
+        }
+        validate_target_count(targets.len() + category_targets.len())
+            .with_context(|| format!("Category {} has {} document(s); target a subcategory instead", category_path, category_targets.len()))?;
+
+        crate::outln!("Category {}: {} document(s)", style(&category_path).cyan(), category_targets.len());
+        // Listed documents need no type lookup
+        client.mark_document_targets(&category_targets);
+        targets.extend(category_targets);
+    }
+    let batches: Vec<Vec<String>> = targets.chunks(FEEDBACK_BATCH_SIZE).map(|c| c.to_vec()).collect();
 
     if verbose {
-        println!("{}:", style("Request").cyan());
-        println!("  Target UUIDs: {:?}", targets);
-        println!("  Message: \"{}\"", message);
-        println!();
+        crate::outln!("{}:", style("Request").cyan());
+        crate::outln!("  Target UUIDs: {:?}", targets);
+        crate::outln!("  Message: \"{}\"", message);
+        if let Some(thread_id) = &reply_to {
+            crate::outln!("  Reply to: {}", thread_id);
+        }
+        crate::outln!();
+    }
+
+    // Send feedback, in batches if there are more targets than one request accepts
+    if batches.len() > 1 {
+        crate::outln!("Sending feedback to {} targets in {} batches...", targets.len(), batches.len());
+    } else {
+        crate::outln!("Sending feedback...");
     }
 
-    // Send feedback
-    println!("Sending feedback...");
-    
-    let response = client
-        .send_feedback(targets, message)
-        .await
-        .context("Failed to send feedback")?;
+    let mut recipients: Vec<Recipient> = Vec::new();
+    let mut message_count = 0;
+    let mut thread_id = reply_to;
+    for (index, batch) in batches.iter().enumerate() {
+        let response = client
+            .send_feedback(batch.clone(), message.clone(), thread_id.clone())
+            .await
+            .with_context(|| if index == 0 {
+                "Failed to send feedback".to_string()
+            } else {
+                format!(
+                    "Failed to send feedback batch {}/{} ({} of {} targets already sent)",
+                    index + 1, batches.len(), batches[..index].iter().map(Vec::len).sum::<usize>(), targets.len()
+                )
+            })?;
+
+        if batches.len() > 1 {
+            crate::item!("  {} Batch {}/{} ({} targets)", style("✓").green(), index + 1, batches.len(), batch.len());
+        }
+
+        // Later batches reply to the thread the first one started
+        if thread_id.is_none() {
+            thread_id = response.thread_id;
+        }
+        message_count += response.message_count;
+        for recipient in response.recipients {
+            if !recipients.iter().any(|r| r.user_id == recipient.user_id) {
+                recipients.push(recipient);
+            }
+        }
+    }
 
     if verbose {
-        println!();
-        println!("{}:", style("Response").cyan());
-        println!("  Status: {}", style("200 OK").green());
-        println!("  Recipients: {}", response.recipients.len());
+        crate::outln!();
+        crate::outln!("{}:", style("Response").cyan());
+        crate::outln!("  Status: {}", style("200 OK").green());
+        crate::outln!("  Messages: {}", message_count);
+        crate::outln!("  Recipients: {}", recipients.len());
     }
 
-    println!();
-    println!("{}", style("✓ Feedback sent successfully").green().bold());
+    crate::outln!();
+    crate::outln!("{}", style("✓ Feedback sent successfully").green().bold());
+
+    if let Some(thread_id) = &thread_id {
+        crate::outln!("  {} {}", style("Thread:").dim(), thread_id);
+        crate::outln!("{}", style(format!("  Reply with: teamturbo feedback <uuid> --reply-to {} -m \"...\"", thread_id)).dim());
+    }
 
-    if !response.recipients.is_empty() {
-        println!();
-        println!("{}:", style("Recipients").bold());
-        for recipient in &response.recipients {
-            println!("  • {} ({})", recipient.user_name, recipient.email);
+    if !recipients.is_empty() {
+        crate::outln!();
+        crate::outln!("{}:", style("Recipients").bold());
+        for recipient in &recipients {
+            crate::outln!("  • {} ({})", recipient.user_name, recipient.email);
         }
 
-        let count = response.recipients.len();
+        let count = recipients.len();
         if count > 1 {
-            println!(
+            crate::outln!(
                 "\n{}",
                 style(format!("Your feedback has been delivered to {} recipients.", count))
                     .green()
             );
         } else {
-            println!("\n{}", style("Your feedback has been delivered.").green());
+            crate::outln!("\n{}", style("Your feedback has been delivered.").green());
         }
     }
 
@@ -82,7 +163,24 @@ pub async fn execute(targets: Vec<String>, message: String, verbose: bool) -> Re
 
 /// Validate input parameters
 fn validate_inputs(targets: &[String], message: &str) -> Result<()> {
-    // Validate UUIDs
+    validate_uuids(targets)?;
+    validate_target_count(targets.len())?;
+    validate_message(message)?;
+
+    logger::debug(
+        "validate_inputs",
+        &format!(
+            "Validated {} target(s) and message ({} chars)",
+            targets.len(),
+            message.trim().len()
+        ),
+    );
+
+    Ok(())
+}
+
+/// Check that explicit targets are UUIDs
+fn validate_uuids(targets: &[String]) -> Result<()> {
     let uuid_regex = Regex::new(
         r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
     )
@@ -97,16 +195,24 @@ fn validate_inputs(targets: &[String], message: &str) -> Result<()> {
         }
     }
 
-    // Validate target count
-    if targets.is_empty() {
+    Ok(())
+}
+
+/// Check the number of targets against the overall limit
+fn validate_target_count(count: usize) -> Result<()> {
+    if count == 0 {
         anyhow::bail!("At least one target UUID is required.\n\nUsage:\n  teamturbo feedback <uuid> --message \"Your message\"");
     }
 
-    if targets.len() > 10 {
-        anyhow::bail!("Too many targets specified (maximum 10, got {}).", targets.len());
+    if count > MAX_FEEDBACK_TARGETS {
+        anyhow::bail!("Too many targets specified (maximum {}, got {}).", MAX_FEEDBACK_TARGETS, count);
     }
 
-    // Validate message
+    Ok(())
+}
+
+/// Check that the message is not empty or too long
+fn validate_message(message: &str) -> Result<()> {
     let trimmed = message.trim();
     if trimmed.is_empty() {
         anyhow::bail!("Message cannot be empty.\n\nUsage:\n  teamturbo feedback <uuid> --message \"Your message\"");
@@ -119,15 +225,6 @@ fn validate_inputs(targets: &[String], message: &str) -> Result<()> {
         );
     }
 
-    logger::debug(
-        "validate_inputs",
-        &format!(
-            "Validated {} target(s) and message ({} chars)",
-            targets.len(),
-            trimmed.len()
-        ),
-    );
-
     Ok(())
 }
 
@@ -165,11 +262,26 @@ mod tests {
 
     #[test]
     fn test_validate_too_many_targets() {
-        let targets = vec!["12345678-1234-1234-1234-123456789abc".to_string(); 11];
+        let targets = vec!["12345678-1234-1234-1234-123456789abc".to_string(); MAX_FEEDBACK_TARGETS + 1];
         let message = "Test message".to_string();
         assert!(validate_inputs(&targets, &message).is_err());
     }
 
+    #[test]
+    fn test_validate_targets_beyond_one_batch() {
+        let targets = vec!["12345678-1234-1234-1234-123456789abc".to_string(); FEEDBACK_BATCH_SIZE + 1];
+        let message = "Test message".to_string();
+        assert!(validate_inputs(&targets, &message).is_ok());
+    }
+
+    #[test]
+    fn test_validate_uuids_and_target_count() {
+        assert!(validate_uuids(&[]).is_ok());
+        assert!(validate_uuids(&["not-a-uuid".to_string()]).is_err());
+        assert!(validate_target_count(MAX_FEEDBACK_TARGETS).is_ok());
+        assert!(validate_target_count(MAX_FEEDBACK_TARGETS + 1).is_err());
+    }
+
     #[test]
     fn test_validate_empty_targets() {
         let targets = vec![];
@@ -1,33 +1,107 @@
 use anyhow::{Result, Context};
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::ProgressStyle;
 use std::path::{Path, PathBuf};
 use std::fs;
 use walkdir::WalkDir;
 
-use crate::config::DocuramConfig;
-use crate::utils::write_file;
+use crate::config::{DocuramConfig, DocumentDefaults, LocalOnlyDocument};
+use crate::utils::{ensure_category_dir, exceeds_size, format_size, is_ignored, normalize_category_path, rst, sanitize_filename, write_file, logger};
+
+/// Non-markdown source formats `import --include-ext` can add
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum SourceFormat {
+    /// Plain text (.txt), imported unchanged
+    Txt,
+    /// reStructuredText (.rst), converted to markdown
+    Rst,
+}
+
+impl SourceFormat {
+    fn of(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "txt" => Some(SourceFormat::Txt),
+            "rst" => Some(SourceFormat::Rst),
+            _ => None,
+        }
+    }
+}
+
+/// Check if `path` is a markdown file or one of the formats in `include_ext`
+fn is_importable(path: &Path, include_ext: &[SourceFormat]) -> bool {
+    path.extension().map(|e| e == "md" || e == "markdown").unwrap_or(false)
+        || SourceFormat::of(path).is_some_and(|format| include_ext.contains(&format))
+}
+
+/// Options for `import`
+pub struct ImportOptions {
+    /// Source (git URL or local path) for a remote import
+    pub from: Option<String>,
+    /// Target category path for a remote import
+    pub to: Option<String>,
+    /// Server doc_type/priority used on push (project defaults apply otherwise)
+    pub overrides: DocumentDefaults,
+    /// Keep at most this many source directory levels as subcategories
+    pub flatten_depth: Option<usize>,
+    /// Import each directory containing one of these files as a single document
+    pub index_files: Option<Vec<String>>,
+    /// Skip (and report) files larger than this many bytes
+    pub max_file_size: u64,
+    /// Create a missing `docuram/<to>` directory instead of failing
+    pub parents: bool,
+    /// Also import these non-markdown formats (remote import only)
+    pub include_ext: Vec<SourceFormat>,
+}
 
 /// Import documents from a git repository or local directory
-pub async fn execute(paths: Vec<String>, from: Option<String>, to: Option<String>) -> Result<()> {
-    println!("{}", style("Import Documents").cyan().bold());
-    println!();
+pub async fn execute(paths: Vec<String>, options: ImportOptions) -> Result<()> {
+    let ImportOptions { from, to, overrides, flatten_depth, index_files, max_file_size, parents, include_ext } = options;
+
+    crate::outln!("{}", style("Import Documents").cyan().bold());
+    crate::outln!();
 
     // Load docuram config to validate we're in a docuram project
-    let _docuram_config = DocuramConfig::load()
+    let mut docuram_config = DocuramConfig::load()
         .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
 
     // Determine the import mode
     let import_mode = determine_import_mode(&paths, &from, &to)?;
 
-    match import_mode {
+    let imported = match import_mode {
         ImportMode::InPlace(in_place_paths) => {
-            execute_in_place_import(in_place_paths).await
+            execute_in_place_import(in_place_paths, max_file_size).await?
         }
         ImportMode::Remote { source, target_category } => {
-            execute_remote_import(source, target_category).await
+            execute_remote_import(source, target_category, flatten_depth, index_files, max_file_size, parents, &include_ext).await?
+        }
+    };
+
+    // Record metadata overrides so push creates the documents with them
+    if !overrides.is_empty() && !imported.is_empty() {
+        for file_path in &imported {
+            let content = fs::read_to_string(file_path)
+                .with_context(|| format!("Failed to read imported file: {}", file_path.display()))?;
+            let path = project_relative_path(file_path);
+            docuram_config.add_local_document(LocalOnlyDocument::new(path, &content, &overrides));
         }
+        docuram_config.save()?;
     }
+
+    Ok(())
+}
+
+/// Convert a path to the project-relative form used in docuram.json (e.g. docuram/organic/a.md)
+fn project_relative_path(file_path: &Path) -> String {
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| {
+            let abs = file_path.canonicalize().ok()?;
+            let cwd = cwd.canonicalize().ok()?;
+            abs.strip_prefix(&cwd).ok().map(|p| p.to_path_buf())
+        })
+        .unwrap_or_else(|| file_path.to_path_buf());
+
+    relative.to_string_lossy().replace('\\', "/")
 }
 
 /// Import mode enum
@@ -84,10 +158,11 @@ fn determine_import_mode(paths: &[String], from: &Option<String>, to: &Option<St
 }
 
 /// Execute in-place import for multiple paths
-async fn execute_in_place_import(paths: Vec<PathBuf>) -> Result<()> {
-    println!("{}", style("Mode: In-place conversion").cyan().bold());
-    println!("{}", style("Documents will be converted to Docuram format in their current location").dim());
-    println!();
+/// Returns the files that were imported successfully
+async fn execute_in_place_import(paths: Vec<PathBuf>, max_file_size: u64) -> Result<Vec<PathBuf>> {
+    crate::outln!("{}", style("Mode: In-place conversion").cyan().bold());
+    crate::outln!("{}", style("Documents will be converted to Docuram format in their current location").dim());
+    crate::outln!();
 
     let mut all_files = Vec::new();
 
@@ -96,30 +171,33 @@ async fn execute_in_place_import(paths: Vec<PathBuf>) -> Result<()> {
         if path.is_file() {
             // Single file
             if !path.extension().map(|e| e == "md" || e == "markdown").unwrap_or(false) {
-                println!("{}", style(format!("Skipping non-markdown file: {:?}", path)).yellow());
+                crate::outln!("{}", style(format!("Skipping non-markdown file: {:?}", path)).yellow());
                 continue;
             }
             all_files.push(path.clone());
         } else if path.is_dir() {
             // Directory - scan recursively
-            let files = scan_markdown_files(path)?;
+            let files = scan_markdown_files(path, &[])?;
             all_files.extend(files);
         }
     }
 
+    let all_files = skip_oversized(all_files, max_file_size);
+
     if all_files.is_empty() {
-        println!("{}", style("No markdown files found").yellow());
-        return Ok(());
+        crate::outln!("{}", style("No markdown files found").yellow());
+        return Ok(Vec::new());
     }
 
-    println!("{}", style(format!("Found {} markdown file(s)", all_files.len())).bold());
-    println!();
+    crate::outln!("{}", style(format!("Found {} markdown file(s)", all_files.len())).bold());
+    crate::outln!();
 
     // Process files
     let mut success_count = 0;
+    let mut imported_files = Vec::new();
     let mut failed_files = Vec::new();
 
-    let pb = ProgressBar::new(all_files.len() as u64);
+    let pb = logger::progress_bar(all_files.len() as u64);
     pb.set_style(
         ProgressStyle::default_bar()
             .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
@@ -134,6 +212,7 @@ async fn execute_in_place_import(paths: Vec<PathBuf>) -> Result<()> {
         match import_file_in_place(file_path).await {
             Ok(_) => {
                 success_count += 1;
+                imported_files.push(file_path.clone());
             },
             Err(e) => {
                 failed_files.push((display_path, e.to_string()));
@@ -146,28 +225,61 @@ async fn execute_in_place_import(paths: Vec<PathBuf>) -> Result<()> {
     pb.finish_with_message("Done");
 
     // Report results
-    println!();
+    crate::outln!();
     if failed_files.is_empty() {
-        println!("{}", style(format!("✓ Successfully converted {} document(s)", success_count)).green());
-        println!("{}", style("Note: Documents are converted locally. Use 'teamturbo push' to sync them to the server.").cyan());
+        crate::outln!("{}", style(format!("✓ Successfully converted {} document(s)", success_count)).green());
+        crate::outln!("{}", style("Note: Documents are converted locally. Use 'teamturbo push' to sync them to the server.").cyan());
     } else {
-        println!("{}", style(format!("✓ Successfully converted {} document(s)", success_count)).green());
-        println!("{}", style(format!("✗ Failed to convert {} document(s):", failed_files.len())).red());
+        crate::outln!("{}", style(format!("✓ Successfully converted {} document(s)", success_count)).green());
+        crate::outln!("{}", style(format!("✗ Failed to convert {} document(s):", failed_files.len())).red());
         for (file, error) in failed_files {
-            println!("  - {}: {}", file, error);
+            crate::outln!("  - {}: {}", file, error);
         }
-        println!();
-        println!("{}", style("Note: Successfully converted documents are local only. Use 'teamturbo push' to sync them to the server.").cyan());
+        crate::outln!();
+        crate::outln!("{}", style("Note: Successfully converted documents are local only. Use 'teamturbo push' to sync them to the server.").cyan());
     }
 
-    Ok(())
+    Ok(imported_files)
 }
 
 /// Execute remote import (git clone or external directory to target category)
-async fn execute_remote_import(from: String, to: String) -> Result<()> {
+/// Returns the files that were written into docuram/
+/// `flatten_depth` limits how many source directory levels become subcategories
+/// `index_files` turns directories containing an index file into one document each
+/// `include_ext` adds .txt/.rst sources; .rst is converted to markdown where possible
+async fn execute_remote_import(
+    from: String,
+    to: String,
+    flatten_depth: Option<usize>,
+    index_files: Option<Vec<String>>,
+    max_file_size: u64,
+    parents: bool,
+    include_ext: &[SourceFormat],
+) -> Result<Vec<PathBuf>> {
+    // Normalize target category path
+    let normalized_to = normalize_category_path(&to);
+
+    // Validate category path format
+    if normalized_to.is_empty() {
+        anyhow::bail!("Category path cannot be empty");
+    }
+    if normalized_to.contains("//") {
+        anyhow::bail!("Invalid category path: contains consecutive slashes");
+    }
+
+    // The target directory must exist unless --parents is given; subdirectories
+    // mirrored from the source are created beneath it as part of the import
+    let target_root = PathBuf::from("docuram").join(&normalized_to);
+    if ensure_category_dir(&target_root, parents)? {
+        crate::outln!("{} Created directory: {}",
+            style("ℹ").blue().bold(),
+            style(target_root.display()).dim()
+        );
+    }
+
     // Determine source type and prepare source
     let (source_path, is_git_repo, is_single_file) = if from.starts_with("http://") || from.starts_with("https://") || from.starts_with("git@") {
-        println!("{}", style(format!("Cloning repository: {}", from)).cyan());
+        crate::outln!("{}", style(format!("Cloning repository: {}", from)).cyan());
         let cloned_dir = clone_git_repo(&from)?;
         (cloned_dir, true, false)
     } else {
@@ -182,50 +294,55 @@ async fn execute_remote_import(from: String, to: String) -> Result<()> {
     // Get markdown files to import
     let md_files = if is_single_file {
         // Single file import
-        if !source_path.extension().map(|e| e == "md" || e == "markdown").unwrap_or(false) {
-            anyhow::bail!("File must be a markdown file (.md or .markdown): {:?}", source_path);
+        if !is_importable(&source_path, include_ext) {
+            anyhow::bail!("File must be a markdown file (.md or .markdown, or .txt/.rst with --include-ext): {:?}", source_path);
         }
-        println!("{}", style(format!("Importing single file: {:?}", source_path.file_name().unwrap())).cyan());
-        println!();
-        vec![source_path.clone()]
+        crate::outln!("{}", style(format!("Importing single file: {:?}", source_path.file_name().unwrap())).cyan());
+        crate::outln!();
+        skip_oversized(vec![source_path.clone()], max_file_size)
     } else {
         // Directory import
-        println!("{}", style(format!("Scanning for markdown files in {:?}...", source_path)).cyan());
-        println!();
+        crate::outln!("{}", style(format!("Scanning for markdown files in {:?}...", source_path)).cyan());
+        crate::outln!();
 
-        let files = scan_markdown_files(&source_path)?;
+        let files = skip_oversized(scan_markdown_files(&source_path, include_ext)?, max_file_size);
 
         if files.is_empty() {
-            println!("{}", style("No markdown files found").yellow());
-            return Ok(());
+            crate::outln!("{}", style("No markdown files found").yellow());
+            return Ok(Vec::new());
         }
 
-        println!("{}", style(format!("Found {} markdown file(s)", files.len())).bold());
-        println!();
+        crate::outln!("{}", style(format!("Found {} markdown file(s)", files.len())).bold());
+        crate::outln!();
         files
     };
 
-    // Normalize target category path
-    let normalized_to = normalize_category_path(&to);
-
-    // Validate category path format
-    if normalized_to.is_empty() {
-        anyhow::bail!("Category path cannot be empty");
-    }
-    if normalized_to.contains("//") {
-        anyhow::bail!("Invalid category path: contains consecutive slashes");
-    }
+    // Each source document is built from one or more files
+    let units: Vec<(PathBuf, Vec<PathBuf>)> = match &index_files {
+        Some(names) if !is_single_file => {
+            let units = group_by_index_dirs(&md_files, &source_path, names);
+            crate::outln!("{}", style(format!("Importing {} file(s) as {} document(s) (directories with {} become one document)",
+                md_files.len(), units.len(), names.join("/"))).dim());
+            crate::outln!();
+            units
+        }
+        _ => md_files.iter().map(|f| (f.clone(), vec![f.clone()])).collect(),
+    };
 
     // Display target category (will be created during push)
-    println!("{}", style(format!("Target category: {}", normalized_to)).cyan());
-    println!("{}", style("Category will be created when you push documents").dim());
-    println!();
+    crate::outln!("{}", style(format!("Target category: {}", normalized_to)).cyan());
+    crate::outln!("{}", style("Category will be created when you push documents").dim());
+    crate::outln!();
 
     // Import files
     let mut success_count = 0;
+    let mut imported_files = Vec::new();
     let mut failed_files = Vec::new();
+    let mut placements = Vec::new();
+    let mut renames = Vec::new();
+    let mut verbatim = Vec::new();
 
-    let pb = ProgressBar::new(md_files.len() as u64);
+    let pb = logger::progress_bar(units.len() as u64);
     pb.set_style(
         ProgressStyle::default_bar()
             .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
@@ -233,7 +350,7 @@ async fn execute_remote_import(from: String, to: String) -> Result<()> {
             .progress_chars("=> ")
     );
 
-    for md_file in &md_files {
+    for (md_file, sources) in &units {
         let relative_path = if is_single_file {
             // For single file, use just the filename
             md_file.file_name()
@@ -250,9 +367,14 @@ async fn execute_remote_import(from: String, to: String) -> Result<()> {
 
         pb.set_message(format!("{}", relative_path));
 
-        match import_file_remote(md_file, &source_path, &normalized_to, is_single_file).await {
-            Ok(_) => {
+        match import_file_remote(md_file, sources, &source_path, &normalized_to, is_single_file, flatten_depth, &mut verbatim).await {
+            Ok((target_file, category, renamed_from)) => {
                 success_count += 1;
+                if let Some(wanted) = renamed_from {
+                    renames.push((wanted, target_file.clone()));
+                }
+                imported_files.push(target_file);
+                placements.push((relative_path, category));
             },
             Err(e) => {
                 failed_files.push((relative_path, e.to_string()));
@@ -264,31 +386,55 @@ async fn execute_remote_import(from: String, to: String) -> Result<()> {
 
     pb.finish_with_message("Done");
 
+    // Report where each file landed
+    if !placements.is_empty() {
+        crate::outln!();
+        for (file, category) in &placements {
+            crate::item!("  {} → {}", file, style(category).cyan());
+        }
+    }
+
+    if !renames.is_empty() {
+        crate::outln!();
+        crate::outln!("{}", style(format!("⚠ Renamed {} file(s) whose name was already taken:", renames.len())).yellow());
+        for (wanted, target) in &renames {
+            crate::outln!("  {} → {}", wanted.display(), target.display());
+        }
+    }
+
+    if !verbatim.is_empty() {
+        crate::outln!();
+        crate::outln!("{}", style(format!("⚠ Could not convert {} reStructuredText file(s); imported them as verbatim code blocks:", verbatim.len())).yellow());
+        for source in &verbatim {
+            crate::outln!("  {}", source.strip_prefix(&source_path).unwrap_or(source).display());
+        }
+    }
+
     // Report results
-    println!();
+    crate::outln!();
     if failed_files.is_empty() {
-        println!("{}", style(format!("✓ Successfully imported {} document(s) locally", success_count)).green());
-        println!("{}", style("Note: Documents are imported locally. Use 'teamturbo push' to sync them to the server.").cyan());
+        crate::outln!("{}", style(format!("✓ Successfully imported {} document(s) locally", success_count)).green());
+        crate::outln!("{}", style("Note: Documents are imported locally. Use 'teamturbo push' to sync them to the server.").cyan());
     } else {
-        println!("{}", style(format!("✓ Successfully imported {} document(s) locally", success_count)).green());
-        println!("{}", style(format!("✗ Failed to import {} document(s):", failed_files.len())).red());
+        crate::outln!("{}", style(format!("✓ Successfully imported {} document(s) locally", success_count)).green());
+        crate::outln!("{}", style(format!("✗ Failed to import {} document(s):", failed_files.len())).red());
         for (file, error) in failed_files {
-            println!("  - {}: {}", file, error);
+            crate::outln!("  - {}: {}", file, error);
         }
-        println!();
-        println!("{}", style("Note: Successfully imported documents are local only. Use 'teamturbo push' to sync them to the server.").cyan());
+        crate::outln!();
+        crate::outln!("{}", style("Note: Successfully imported documents are local only. Use 'teamturbo push' to sync them to the server.").cyan());
     }
 
     // Clean up temporary directory if we cloned a repo
     if is_git_repo {
-        println!();
-        println!("{}", style("Cleaning up temporary directory...").dim());
+        crate::outln!();
+        crate::outln!("{}", style("Cleaning up temporary directory...").dim());
         if let Err(e) = fs::remove_dir_all(&source_path) {
-            println!("{}", style(format!("Warning: Failed to clean up: {}", e)).yellow());
+            crate::outln!("{}", style(format!("Warning: Failed to clean up: {}", e)).yellow());
         }
     }
 
-    Ok(())
+    Ok(imported_files)
 }
 
 /// Clone a git repository to a temporary directory
@@ -309,13 +455,19 @@ fn clone_git_repo(repo_url: &str) -> Result<PathBuf> {
         anyhow::bail!("Git clone failed: {}", String::from_utf8_lossy(&output.stderr));
     }
 
-    println!("{}", style("✓ Repository cloned").green());
+    crate::outln!("{}", style("✓ Repository cloned").green());
     Ok(temp_dir)
 }
 
-/// Scan for all markdown files in a directory recursively
-fn scan_markdown_files(dir: &Path) -> Result<Vec<PathBuf>> {
+/// Scan for all markdown files (plus the `include_ext` formats) in a directory recursively
+/// Files excluded by .docuramignore are skipped. For a source outside the project (a cloned
+/// repository or --from directory) the patterns match relative to that directory.
+fn scan_markdown_files(dir: &Path, include_ext: &[SourceFormat]) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
+    let project_root = std::env::current_dir()?;
+    let outside_project = fs::canonicalize(dir)
+        .map(|dir| !dir.starts_with(&project_root))
+        .unwrap_or(false);
 
     for entry in WalkDir::new(dir)
         .follow_links(true)
@@ -333,19 +485,37 @@ fn scan_markdown_files(dir: &Path) -> Result<Vec<PathBuf>> {
             continue;
         }
 
-        // Check if it's a markdown file
-        if path.is_file() {
-            if let Some(ext) = path.extension() {
-                if ext == "md" || ext == "markdown" {
-                    files.push(path.to_path_buf());
-                }
-            }
+        let ignore_path = if outside_project { path.strip_prefix(dir).unwrap_or(path) } else { path };
+        if is_ignored(ignore_path) {
+            continue;
+        }
+
+        if path.is_file() && is_importable(path, include_ext) {
+            files.push(path.to_path_buf());
         }
     }
 
     Ok(files)
 }
 
+/// Drop files larger than `max_file_size`, reporting each one
+fn skip_oversized(files: Vec<PathBuf>, max_file_size: u64) -> Vec<PathBuf> {
+    let (oversized, files): (Vec<_>, Vec<_>) = files.into_iter()
+        .partition(|f| exceeds_size(f, max_file_size).is_some());
+
+    if !oversized.is_empty() {
+        crate::outln!("{}", style(format!("⚠ Skipping {} file(s) larger than {}:", oversized.len(), format_size(max_file_size))).yellow());
+        for file in &oversized {
+            let size = exceeds_size(file, max_file_size).unwrap_or_default();
+            crate::outln!("  - {} ({})", file.display(), format_size(size));
+        }
+        crate::outln!("{}", style("Use --max-file-size to raise the limit").dim());
+        crate::outln!();
+    }
+
+    files
+}
+
 /// Import a single file in-place (validate it's under docuram/)
 async fn import_file_in_place(file_path: &Path) -> Result<()> {
     // Validate file is readable
@@ -360,19 +530,43 @@ async fn import_file_in_place(file_path: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Import a single file from remote source to target category
+/// Import a single document from remote source to target category
+/// `file_path` places the document; its content is read from `sources` (joined in order)
+/// Returns the path of the written document, the category it was placed in, and the path it
+/// would have had when that name was taken by another document
+/// .rst sources that cannot be converted are added to `verbatim`.
 async fn import_file_remote(
     file_path: &Path,
+    sources: &[PathBuf],
     source_dir: &Path,
     target_category: &str,
     is_single_file: bool,
-) -> Result<()> {
-    // Read file content
-    let content = fs::read_to_string(file_path)
-        .context("Failed to read file")?;
+    flatten_depth: Option<usize>,
+    verbatim: &mut Vec<PathBuf>,
+) -> Result<(PathBuf, String, Option<PathBuf>)> {
+    // Read file content (a grouped directory is joined into one document)
+    let mut parts = Vec::new();
+    for source in sources {
+        let part = fs::read_to_string(source)
+            .with_context(|| format!("Failed to read file: {}", source.display()))?;
+        let part = match SourceFormat::of(source) {
+            Some(SourceFormat::Rst) => rst::to_markdown(&part).unwrap_or_else(|| {
+                verbatim.push(source.clone());
+                rst::fenced(&part, "rst")
+            }),
+            _ => part,
+        };
+        parts.push(part);
+    }
+    let content = if parts.len() == 1 {
+        parts.remove(0)
+    } else {
+        let parts: Vec<&str> = parts.iter().map(|p| p.trim_end()).collect();
+        format!("{}\n", parts.join("\n\n"))
+    };
 
     // Extract title from filename
-    let title = extract_title(file_path, &content)?;
+    let mut title = extract_title(file_path, &content)?;
 
     // Determine the full category path
     let full_category = if is_single_file {
@@ -383,14 +577,23 @@ async fn import_file_remote(
         let relative_path = file_path.strip_prefix(source_dir)
             .unwrap_or(file_path);
 
-        let parent_dirs = relative_path.parent()
-            .and_then(|p| p.to_str())
-            .filter(|s| !s.is_empty());
+        let parent_dirs: Vec<String> = relative_path.parent()
+            .map(|p| p.components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect())
+            .unwrap_or_default();
 
-        if let Some(parent) = parent_dirs {
-            format!("{}/{}", target_category, parent.replace("\\", "/"))
-        } else {
+        let (subcategories, collapsed) = split_directory_levels(&parent_dirs, flatten_depth);
+
+        // Levels beyond the flatten depth become a filename prefix
+        if !collapsed.is_empty() {
+            title = format!("{}-{}", collapsed.join("-"), title);
+        }
+
+        if subcategories.is_empty() {
             target_category.to_string()
+        } else {
+            format!("{}/{}", target_category, subcategories.join("/"))
         }
     };
 
@@ -399,12 +602,8 @@ async fn import_file_remote(
     fs::create_dir_all(&target_dir)?;
 
     // Use filename with .md extension
-    let target_file = target_dir.join(sanitize_filename(&title));
-
-    // Check if target file already exists
-    if target_file.exists() {
-        anyhow::bail!("Document already exists at path: {}", target_file.display());
-    }
+    let wanted_file = target_dir.join(sanitize_filename(&title));
+    let target_file = unique_target_file(&wanted_file, &content)?;
 
     // Write file as pure markdown (no frontmatter)
     write_file(&target_file, &content)?;
@@ -412,7 +611,74 @@ async fn import_file_remote(
     // Note: We don't update local state here because the document hasn't been synced to server yet
     // The push command will handle syncing to server and updating state.json
 
-    Ok(())
+    let renamed_from = (target_file != wanted_file).then_some(wanted_file);
+    Ok((target_file, full_category, renamed_from))
+}
+
+/// Free path for an imported document
+/// When `wanted` is taken by a different document, `-2`, `-3`, ... is appended to the file stem.
+/// Fails when a document with the same content is already there (imported before).
+fn unique_target_file(wanted: &Path, content: &str) -> Result<PathBuf> {
+    let stem = wanted.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let extension = wanted.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+
+    let mut candidate = wanted.to_path_buf();
+    for n in 2.. {
+        match fs::read_to_string(&candidate) {
+            Ok(existing) if existing == content => {
+                anyhow::bail!("Document already exists at path: {}", candidate.display())
+            }
+            Err(_) if !candidate.exists() => return Ok(candidate),
+            _ => candidate = wanted.with_file_name(format!("{}-{}{}", stem, n, extension)),
+        }
+    }
+    unreachable!("suffix search is unbounded")
+}
+
+/// Group markdown files so each directory containing an index file becomes one document
+/// Returns (placement path, source files) pairs. A grouped directory `a/b/` is placed as `a/b.md`,
+/// with the index first and the directory's other markdown files after it in name order.
+/// Files in subdirectories are grouped separately; the source root itself is never grouped.
+fn group_by_index_dirs(files: &[PathBuf], source_dir: &Path, index_files: &[String]) -> Vec<(PathBuf, Vec<PathBuf>)> {
+    let is_index = |file: &Path| {
+        file.file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| index_files.iter().any(|index| index.eq_ignore_ascii_case(n)))
+            .unwrap_or(false)
+    };
+
+    let index_dirs: std::collections::HashSet<&Path> = files.iter()
+        .filter(|f| is_index(f))
+        .filter_map(|f| f.parent())
+        .filter(|dir| *dir != source_dir)
+        .collect();
+
+    let mut units: std::collections::BTreeMap<PathBuf, Vec<PathBuf>> = std::collections::BTreeMap::new();
+    for file in files {
+        match file.parent().filter(|dir| index_dirs.contains(dir)) {
+            Some(dir) => {
+                let dir_name = dir.file_name().unwrap_or_default().to_string_lossy();
+                let placement = dir.parent().unwrap_or(source_dir).join(format!("{}.md", dir_name));
+                units.entry(placement).or_default().push(file.clone());
+            }
+            None => {
+                units.entry(file.clone()).or_default().push(file.clone());
+            }
+        }
+    }
+
+    for sources in units.values_mut() {
+        sources.sort_by_key(|f| (!is_index(f), f.clone()));
+    }
+
+    units.into_iter().collect()
+}
+
+/// Split source directory levels into those kept as subcategories and those collapsed
+/// `flatten_depth` of None keeps every level
+fn split_directory_levels(levels: &[String], flatten_depth: Option<usize>) -> (Vec<String>, Vec<String>) {
+    let keep = flatten_depth.unwrap_or(levels.len()).min(levels.len());
+    (levels[..keep].to_vec(), levels[keep..].to_vec())
 }
 
 /// Extract title from filename
@@ -432,39 +698,6 @@ fn extract_title(file_path: &Path, _content: &str) -> Result<String> {
     Ok(title)
 }
 
-/// Normalize category path by removing ./docuram/ or docuram/ prefix and trailing slashes
-fn normalize_category_path(path: &str) -> String {
-    let trimmed = path.trim();
-
-    let mut result = trimmed;
-
-    // Remove ./docuram/ prefix
-    if let Some(stripped) = result.strip_prefix("./docuram/") {
-        result = stripped;
-    }
-    // Remove docuram/ prefix
-    else if let Some(stripped) = result.strip_prefix("docuram/") {
-        result = stripped;
-    }
-    // Remove ./ prefix
-    else if let Some(stripped) = result.strip_prefix("./") {
-        result = stripped;
-    }
-
-    // Remove trailing slashes
-    result.trim_end_matches('/').to_string()
-}
-
-/// Sanitize filename to remove invalid characters
-fn sanitize_filename(name: &str) -> String {
-    name.chars()
-        .map(|c| match c {
-            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            _ => c,
-        })
-        .collect()
-}
-
 /// Derive category from file path (for in-place conversion)
 /// Expects path to be under docuram/ directory
 fn derive_category_from_path(file_path: &Path) -> Result<String> {
@@ -506,3 +739,55 @@ fn derive_category_from_path(file_path: &Path) -> Result<String> {
         Ok(category_parts.join("/"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_directory_levels() {
+        let levels: Vec<String> = vec!["a".into(), "b".into(), "c".into()];
+
+        assert_eq!(split_directory_levels(&levels, None), (levels.clone(), vec![]));
+        assert_eq!(
+            split_directory_levels(&levels, Some(1)),
+            (vec!["a".to_string()], vec!["b".to_string(), "c".to_string()])
+        );
+        assert_eq!(split_directory_levels(&levels, Some(0)), (vec![], levels.clone()));
+        assert_eq!(split_directory_levels(&levels, Some(5)), (levels.clone(), vec![]));
+    }
+
+    #[test]
+    fn test_unique_target_file_appends_suffix() {
+        let root = std::env::temp_dir().join(format!("teamturbo-import-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        let wanted = root.join("Readme.md");
+
+        assert_eq!(unique_target_file(&wanted, "a").unwrap(), wanted);
+        fs::write(&wanted, "a").unwrap();
+        assert!(unique_target_file(&wanted, "a").is_err());
+        assert_eq!(unique_target_file(&wanted, "b").unwrap(), root.join("Readme-2.md"));
+        fs::write(root.join("Readme-2.md"), "b").unwrap();
+        assert_eq!(unique_target_file(&wanted, "c").unwrap(), root.join("Readme-3.md"));
+        assert!(unique_target_file(&wanted, "b").unwrap_err().to_string().contains("Readme-2.md"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_group_by_index_dirs() {
+        let root = PathBuf::from("/src");
+        let files: Vec<PathBuf> = ["README.md", "guide/setup.md", "guide/index.md", "notes/a.md"]
+            .iter()
+            .map(|f| root.join(f))
+            .collect();
+
+        let units = group_by_index_dirs(&files, &root, &["index.md".to_string(), "README.md".to_string()]);
+
+        assert_eq!(units, vec![
+            (root.join("README.md"), vec![root.join("README.md")]),
+            (root.join("guide.md"), vec![root.join("guide/index.md"), root.join("guide/setup.md")]),
+            (root.join("notes/a.md"), vec![root.join("notes/a.md")]),
+        ]);
+    }
+}
@@ -1,17 +1,35 @@
 use anyhow::{Context, Result};
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
+use futures_util::StreamExt;
+use indicatif::ProgressStyle;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 use dialoguer::Confirm;
 
 use crate::api::{ApiClient, PublicApiClient};
-use crate::config::{CliConfig, DocuramConfig, DocumentInfo, PublicDependency};
-use crate::utils::{write_file, logger, calculate_checksum};
+use crate::commands::pull::{report_failed_downloads, save_downloaded_document, save_failed_downloads, FailedDownload};
+use crate::config::{normalize_server_url, CliConfig, DocuramConfig, DocumentInfo, PublicDependency};
+use crate::utils::{write_file, write_file_atomic, logger, calculate_checksum, transfer_summary, STANDARD_DIRS};
+
+/// Initialize a checkout from a category config URL
+/// `merge` adds the category to an existing checkout instead of creating a new one
+pub async fn execute(config_url: Option<String>, force: bool, no_download: bool, no_gitignore: bool, merge: bool) -> Result<()> {
+    crate::outln!("{}", style("Initialize Docuram Project").cyan().bold());
+    crate::outln!();
+    let started = Instant::now();
+
+    // Load CLI config to get auth and the default server
+    let cli_config = CliConfig::load()?;
+    logger::debug("init", "Loaded CLI config");
+    let config_url = config_url
+        .map(|url| resolve_config_url(&url, cli_config.default_server.as_deref()))
+        .transpose()?;
 
-pub async fn execute(config_url: Option<String>, force: bool, no_download: bool) -> Result<()> {
-    println!("{}", style("Initialize Docuram Project").cyan().bold());
-    println!();
+    if merge {
+        let config_source = config_url.context("--merge needs --config-url for the category to add")?;
+        return merge_category(&config_source, no_download, started).await;
+    }
 
     // Check if docuram.json already exists
     let config_path = Path::new("docuram.json");
@@ -32,25 +50,22 @@ pub async fn execute(config_url: Option<String>, force: bool, no_download: bool)
         }
     };
 
-    // Load CLI config to get auth
-    let cli_config = CliConfig::load()?;
-    logger::debug("init", "Loaded CLI config");
-
     // Determine server URL from config URL
     let server_url = extract_server_url(&config_source)?;
     logger::debug("init", &format!("Server URL: {}", server_url));
 
-    // Get auth for this server
-    let auth = cli_config
-        .get_auth(&server_url)
-        .context(format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
+    // Get auth for this server before any network call
+    let auth = match cli_config.resolve_auth(&server_url) {
+        Some(auth) => auth,
+        None => anyhow::bail!("{}", not_logged_in_message(&server_url, &cli_config.servers())),
+    };
     logger::debug("init", "Authentication token found");
 
     // Create API client
-    let client = ApiClient::new(server_url.clone(), auth.access_token.clone());
+    let client = ApiClient::from_auth(server_url.clone(), &auth);
 
     // Download docuram config
-    println!("Downloading configuration from {}...", style(&config_source).cyan());
+    crate::outln!("Downloading configuration from {}...", style(&config_source).cyan());
     let api_config = client.get_docuram_config(&config_source).await?;
 
     // Ensure docuram directory exists
@@ -58,77 +73,74 @@ pub async fn execute(config_url: Option<String>, force: bool, no_download: bool)
         .context("Failed to create docuram directory")?;
 
     // Save docuram.json
-    println!("Saving {}...", style("docuram.json").cyan());
+    crate::outln!("Saving {}...", style("docuram.json").cyan());
     let config_json = serde_json::to_string_pretty(&api_config)
         .context("Failed to serialize config")?;
-    fs::write(&config_path, config_json)
+    write_file_atomic(config_path, &config_json)
         .context("Failed to write docuram.json")?;
 
-    println!("{}", style("✓ Configuration saved").green());
-    println!();
+    crate::outln!("{}", style("✓ Configuration saved").green());
+
+    // Keep machine-specific sync state out of version control
+    if !no_gitignore && ensure_docuram_gitignore()? {
+        crate::outln!("{}", style("✓ Created .docuram/.gitignore").green());
+    }
+    crate::outln!();
 
     // Reload config as our local DocuramConfig type (with local state fields)
     let docuram_config = DocuramConfig::load()
         .context("Failed to reload docuram.json")?;
 
     // Display project info
-    println!("{}", style("Project Information:").bold());
-    println!("  Name: {}", docuram_config.project.name);
+    crate::outln!("{}", style("Project Information:").bold());
+    crate::outln!("  Name: {}", docuram_config.project.name);
     if let Some(desc) = &docuram_config.project.description {
-        println!("  Description: {}", desc);
+        crate::outln!("  Description: {}", desc);
     }
-    println!("  Category: {}", docuram_config.docuram.category_path);
+    crate::outln!("  Category: {}", docuram_config.docuram.category_path);
     if let Some(task_name) = &docuram_config.docuram.task_name {
-        println!("  Task: {}", task_name);
+        crate::outln!("  Task: {}", task_name);
     }
-    println!();
+    crate::outln!();
 
     // Count all documents (working documents + dependencies)
     let total_docs = docuram_config.documents.len() + docuram_config.requires.len();
 
-    println!("{}", style(format!("Documents: {} total ({} working, {} dependencies)",
+    crate::outln!("{}", style(format!("Documents: {} total ({} working, {} dependencies)",
         total_docs,
         docuram_config.documents.len(),
         docuram_config.requires.len())).bold());
 
     if no_download {
-        println!();
-        println!("{}", style("⚠ Skipping document download (--no-download flag)").yellow());
-        println!();
-        println!("{}", style("Project structure created. Run 'teamturbo pull' to download documents.").dim());
+        crate::outln!();
+        crate::outln!("{}", style("⚠ Skipping document download (--no-download flag)").yellow());
+        crate::outln!();
+        crate::outln!("{}", style("Project structure created. Run 'teamturbo pull' to download documents.").dim());
         return Ok(());
     }
 
     // Confirm download
     if !force {
-        println!();
+        crate::outln!();
         let should_download = Confirm::new()
             .with_prompt("Download all documents now?")
             .default(true)
             .interact()?;
 
         if !should_download {
-            println!();
-            println!("{}", style("Project initialized without downloading documents.").yellow());
-            println!();
-            println!("{}", style("Run 'teamturbo pull' to download documents later.").dim());
+            crate::outln!();
+            crate::outln!("{}", style("Project initialized without downloading documents.").yellow());
+            crate::outln!();
+            crate::outln!("{}", style("Run 'teamturbo pull' to download documents later.").dim());
             return Ok(());
         }
     }
 
     // Create standard docuram subdirectories
-    println!("{}", style("Creating standard directories...").bold());
+    crate::outln!("{}", style("Creating standard directories...").bold());
     let mut created_count = 0;
 
-    // Standard subdirectories (organic, req, impl, manual)
-    let standard_dirs = vec![
-        ("organic", "User-maintained natural language documents (req*.md, bug*.md)"),
-        ("req", "AI Agent and user-maintained extended requirement documents"),
-        ("impl", "Implementation documents for each development iteration"),
-        ("manual", "User manuals and operation guides"),
-    ];
-
-    for (dir_name, _description) in &standard_dirs {
+    for dir_name in STANDARD_DIRS {
         let dir_path = PathBuf::from("docuram").join(dir_name);
         if !dir_path.exists() {
             fs::create_dir_all(&dir_path)
@@ -150,16 +162,16 @@ pub async fn execute(config_url: Option<String>, force: bool, no_download: bool)
     }
 
     if created_count > 0 {
-        println!("{}", style(format!("✓ Created {} director(ies)", created_count)).green());
+        crate::outln!("{}", style(format!("✓ Created {} director(ies)", created_count)).green());
     }
-    println!();
+    crate::outln!();
 
-    println!();
-    println!("{}", style("Downloading documents...").bold());
-    println!();
+    crate::outln!();
+    crate::outln!("{}", style("Downloading documents...").bold());
+    crate::outln!();
 
     // Create progress bar
-    let pb = ProgressBar::new(total_docs as u64);
+    let pb = logger::progress_bar(total_docs as u64);
     pb.set_style(
         ProgressStyle::default_bar()
             .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
@@ -179,19 +191,20 @@ pub async fn execute(config_url: Option<String>, force: bool, no_download: bool)
         .map(|d| d.uuid.clone())
         .collect();
 
-    for doc_uuid in &uuids_to_download {
+    // Downloads run concurrently; docuram.json is updated here as each one completes
+    let mut downloads = client.download_documents(&uuids_to_download);
+    while let Some((doc_uuid, result)) = downloads.next().await {
         let title = docuram_config.get_document_by_uuid(doc_uuid)
             .map(|d| d.title.clone())
             .unwrap_or_default();
-        pb.set_message(format!("{}", title));
+        pb.set_message(title.clone());
 
-        let working_category_path = docuram_config.docuram.category_path.clone();
-        match download_document(&client, doc_uuid, &mut docuram_config, &working_category_path).await {
+        match result.and_then(|doc| save_downloaded_document(&mut docuram_config, doc_uuid, doc)) {
             Ok(_) => {
                 success_count += 1;
             }
             Err(e) => {
-                failed_docs.push((doc_uuid.clone(), e.to_string()));
+                failed_docs.push(FailedDownload::new(doc_uuid, &title, &e));
             }
         }
 
@@ -203,33 +216,174 @@ pub async fn execute(config_url: Option<String>, force: bool, no_download: bool)
     // Save docuram config with updated local state fields
     docuram_config.save()
         .context("Failed to save docuram.json")?;
+    save_failed_downloads("init", &failed_docs)?;
 
-    println!();
+    crate::outln!();
     if failed_docs.is_empty() {
-        println!("{}", style(format!("✓ Successfully downloaded {} documents", success_count)).green());
+        crate::outln!("{}", style(format!("✓ Successfully downloaded {} documents", success_count)).green());
     } else {
-        println!("{}", style(format!("✓ Downloaded {} documents", success_count)).green());
-        println!("{}", style(format!("✗ Failed to download {} documents:", failed_docs.len())).red());
-        for (slug, error) in failed_docs {
-            println!("  - {}: {}", slug, error);
-        }
+        crate::outln!("{}", style(format!("✓ Downloaded {} documents", success_count)).green());
+        crate::outln!("{}", style(format!("✗ Failed to download {} documents:", failed_docs.len())).red());
+        report_failed_downloads(&failed_docs);
     }
+    crate::outln!("{}", style(transfer_summary("Downloaded", success_count, client.bytes_transferred(), started.elapsed())).dim());
 
     // Fetch and download public dependencies from docuram.teamturbo.io
-    println!();
+    crate::outln!();
     fetch_public_dependencies(&mut docuram_config).await?;
 
-    println!();
-    println!("{}", style("Project initialized successfully!").green().bold());
-    println!();
-    println!("{}", style("You can now:").dim());
-    println!("  {} {}", style("teamturbo pull").dim(), style("- Update documents").dim());
-    println!("  {} {}", style("teamturbo push").dim(), style("- Push changes").dim());
-    println!("  {} {}", style("teamturbo diff").dim(), style("- View changes").dim());
+    crate::outln!();
+    crate::outln!("{}", style("Project initialized successfully!").green().bold());
+    crate::outln!();
+    crate::outln!("{}", style("You can now:").dim());
+    crate::outln!("  {} {}", style("teamturbo pull").dim(), style("- Update documents").dim());
+    crate::outln!("  {} {}", style("teamturbo push").dim(), style("- Push changes").dim());
+    crate::outln!("  {} {}", style("teamturbo diff").dim(), style("- View changes").dim());
 
     Ok(())
 }
 
+/// Add another category to the existing checkout and download its documents
+async fn merge_category(config_source: &str, no_download: bool, started: Instant) -> Result<()> {
+    let mut docuram_config = DocuramConfig::load()
+        .context("No docuram.json to merge into. Run 'teamturbo init' without --merge first.")?;
+
+    // Documents from both categories are synced with one login, so they must share a server
+    let server_url = extract_server_url(config_source)?;
+    if normalize_server_url(&server_url) != normalize_server_url(docuram_config.server_url()) {
+        anyhow::bail!(
+            "Cannot merge a category from {} into a checkout of {}",
+            server_url,
+            docuram_config.server_url()
+        );
+    }
+
+    let cli_config = CliConfig::load()?;
+    let auth = match cli_config.resolve_auth(&server_url) {
+        Some(auth) => auth,
+        None => anyhow::bail!("{}", not_logged_in_message(&server_url, &cli_config.servers())),
+    };
+    let client = ApiClient::from_auth(server_url.clone(), &auth);
+
+    crate::outln!("Downloading configuration from {}...", style(config_source).cyan());
+    let api_config = client.get_docuram_config(config_source).await?;
+    let incoming: DocuramConfig = serde_json::to_value(&api_config)
+        .and_then(serde_json::from_value)
+        .context("Failed to read the downloaded configuration")?;
+    let category_path = incoming.docuram.category_path.clone();
+
+    let added = docuram_config.merge_category(incoming, config_source)?;
+    docuram_config.save()
+        .context("Failed to save docuram.json")?;
+
+    crate::outln!("{}", style(format!("✓ Merged category {} ({} new document(s))", category_path, added.len())).green());
+    crate::outln!("  {}", style(format!("Documents are stored under docuram/{}/", category_path)).dim());
+
+    if no_download || added.is_empty() {
+        if no_download {
+            crate::outln!();
+            crate::outln!("{}", style("Run 'teamturbo pull' to download the merged documents.").dim());
+        }
+        return Ok(());
+    }
+
+    crate::outln!();
+    crate::outln!("{}", style("Downloading documents...").bold());
+    let pb = logger::progress_bar(added.len() as u64);
+    let mut success_count = 0;
+    let mut failed_docs = Vec::new();
+
+    let mut downloads = client.download_documents(&added);
+    while let Some((doc_uuid, result)) = downloads.next().await {
+        let title = docuram_config.get_document_by_uuid(doc_uuid)
+            .map(|d| d.title.clone())
+            .unwrap_or_default();
+        pb.set_message(title.clone());
+
+        match result.and_then(|doc| save_downloaded_document(&mut docuram_config, doc_uuid, doc)) {
+            Ok(_) => success_count += 1,
+            Err(e) => failed_docs.push(FailedDownload::new(doc_uuid, &title, &e)),
+        }
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+
+    docuram_config.save()
+        .context("Failed to save docuram.json")?;
+    save_failed_downloads("init", &failed_docs)?;
+
+    if !failed_docs.is_empty() {
+        crate::outln!("{}", style(format!("✗ Failed to download {} documents:", failed_docs.len())).red());
+        report_failed_downloads(&failed_docs);
+    }
+    crate::outln!("{}", style(transfer_summary("Downloaded", success_count, client.bytes_transferred(), started.elapsed())).dim());
+
+    Ok(())
+}
+
+/// Explain a missing login for the config URL's server, listing the servers we do have
+fn not_logged_in_message(server_url: &str, servers: &[&str]) -> String {
+    let mut message = format!("Not logged in to {} (the server in the config URL).", server_url);
+    if servers.is_empty() {
+        message.push_str("\nYou are not logged in to any server.");
+    } else {
+        message.push_str("\nLogged in to:");
+        for server in servers {
+            message.push_str(&format!("\n  - {}", server));
+        }
+    }
+    message.push_str(&format!("\nRun 'teamturbo login --domain {}' first, or check the config URL.", server_url));
+    message
+}
+
+/// Create `.docuram/.gitignore` so local state, backups and quarantine are not committed
+/// Only the shared project config is kept. Returns false if ignoring is already handled.
+fn ensure_docuram_gitignore() -> Result<bool> {
+    let docuram_dir = Path::new(".docuram");
+    let gitignore_path = docuram_dir.join(".gitignore");
+
+    if gitignore_path.exists() {
+        return Ok(false);
+    }
+
+    // A root .gitignore entry for .docuram/ already covers it
+    if let Ok(root_gitignore) = fs::read_to_string(".gitignore") {
+        let handled = root_gitignore.lines()
+            .map(|line| line.trim().trim_start_matches('/').trim_end_matches('/'))
+            .any(|entry| entry == ".docuram");
+        if handled {
+            return Ok(false);
+        }
+    }
+
+    fs::create_dir_all(docuram_dir)
+        .context("Failed to create .docuram directory")?;
+    fs::write(&gitignore_path, DOCURAM_GITIGNORE)
+        .context("Failed to write .docuram/.gitignore")?;
+
+    Ok(true)
+}
+
+const DOCURAM_GITIGNORE: &str = "\
+# Local teamturbo state (machine-specific, do not commit)
+*
+!.gitignore
+# Shared project defaults
+!config.toml
+";
+
+/// Absolute config URL, resolving a bare path against the default server
+fn resolve_config_url(config_url: &str, default_server: Option<&str>) -> Result<String> {
+    if url::Url::parse(config_url).is_ok() {
+        return Ok(config_url.to_string());
+    }
+    let default_server = default_server.with_context(|| format!(
+        "Config URL '{}' is not a full URL. Pass one, or set a default server with 'teamturbo config set default_server <url>'",
+        config_url
+    ))?;
+    Ok(format!("{}/{}", default_server.trim_end_matches('/'), config_url.trim_start_matches('/')))
+}
+
 /// Extract server URL from config URL
 fn extract_server_url(config_url: &str) -> Result<String> {
     let url = url::Url::parse(config_url)
@@ -268,47 +422,9 @@ fn extract_server_url(config_url: &str) -> Result<String> {
     Ok(server_url)
 }
 
-/// Download a single document
-async fn download_document(
-    client: &ApiClient,
-    doc_uuid: &str,
-    docuram_config: &mut DocuramConfig,
-    working_category_path: &str,
-) -> Result<()> {
-    // Download document content
-    logger::debug("download", &format!("Fetching document: {}", doc_uuid));
-    let doc = client.download_document(doc_uuid).await?;
-
-    let content = doc.content.unwrap_or_default();
-    logger::debug("download", &format!("Document size: {} bytes", content.len()));
-
-    // Get document info to calculate local path
-    let doc_info = docuram_config.get_document_by_uuid(doc_uuid)
-        .context("Document not found in config")?;
-    let local_file_path = doc_info.local_path(working_category_path);
-    let file_path = PathBuf::from(&local_file_path);
-
-    write_file(&file_path, &content)
-        .with_context(|| format!("Failed to write document to {:?}", file_path))?;
-    logger::debug("download", &format!("Saved to: {:?}", file_path));
-
-    // Calculate checksum of the actual file content
-    let actual_checksum = calculate_checksum(&content);
-
-    // Update document's local state in docuram config
-    if let Some(doc_mut) = docuram_config.get_document_by_uuid_mut(doc_uuid) {
-        doc_mut.local_checksum = Some(actual_checksum);
-        doc_mut.last_sync = Some(chrono::Utc::now().to_rfc3339());
-        doc_mut.version = doc.version;
-        doc_mut.pending_deletion = false;
-    }
-
-    Ok(())
-}
-
 /// Fetch and download public dependencies from docuram.teamturbo.io
 async fn fetch_public_dependencies(docuram_config: &mut DocuramConfig) -> Result<()> {
-    println!("{}", style("Fetching public dependencies from Docuram Official...").bold());
+    crate::outln!("{}", style("Fetching public dependencies from Docuram Official...").bold());
 
     let public_client = PublicApiClient::new(PublicApiClient::default_url().to_string());
 
@@ -316,18 +432,18 @@ async fn fetch_public_dependencies(docuram_config: &mut DocuramConfig) -> Result
     let global_deps = match public_client.get_global_dependencies().await {
         Ok(deps) => deps,
         Err(e) => {
-            println!("{}", style(format!("⚠ Could not fetch public dependencies: {}", e)).yellow());
-            println!("{}", style("  (This is optional - your project will work without public dependencies)").dim());
+            crate::outln!("{}", style(format!("⚠ Could not fetch public dependencies: {}", e)).yellow());
+            crate::outln!("{}", style("  (This is optional - your project will work without public dependencies)").dim());
             return Ok(());
         }
     };
 
     if global_deps.global_dependencies.is_empty() {
-        println!("{}", style("  No public dependencies available").dim());
+        crate::outln!("{}", style("  No public dependencies available").dim());
         return Ok(());
     }
 
-    println!("{}", style(format!("Found {} public dependency categor(ies)", global_deps.global_dependencies.len())).dim());
+    crate::outln!("{}", style(format!("Found {} public dependency categor(ies)", global_deps.global_dependencies.len())).dim());
 
     // Use dependencies directory for public dependencies as well
     let deps_dir = PathBuf::from("dependencies");
@@ -340,14 +456,14 @@ async fn fetch_public_dependencies(docuram_config: &mut DocuramConfig) -> Result
     let mut public_deps_list: Vec<PublicDependency> = Vec::new();
 
     for dep_category in &global_deps.global_dependencies {
-        println!();
-        println!("{}", style(format!("Downloading: {} ({} documents)", dep_category.name, dep_category.document_count)).cyan());
+        crate::outln!();
+        crate::outln!("{}", style(format!("Downloading: {} ({} documents)", dep_category.name, dep_category.document_count)).cyan());
 
         // Download the dependency's documents
         let download_result = match public_client.download_global_dependency(&dep_category.uuid).await {
             Ok(result) => result,
             Err(e) => {
-                println!("{}", style(format!("  ⚠ Failed to download {}: {}", dep_category.name, e)).yellow());
+                crate::outln!("{}", style(format!("  ⚠ Failed to download {}: {}", dep_category.name, e)).yellow());
                 continue;
             }
         };
@@ -397,8 +513,13 @@ async fn fetch_public_dependencies(docuram_config: &mut DocuramConfig) -> Result
                 checksum: doc.checksum.clone(),
                 is_required: true,
                 local_checksum: Some(checksum),
+                local_content_checksum: None,
                 last_sync: Some(chrono::Utc::now().to_rfc3339()),
                 pending_deletion: false,
+                provisional: false,
+                keep_local: false,
+                local_name: None,
+                extra: Default::default(),
             };
             dep_documents.push(doc_info);
             total_docs_downloaded += 1;
@@ -415,7 +536,7 @@ async fn fetch_public_dependencies(docuram_config: &mut DocuramConfig) -> Result
         };
         public_deps_list.push(public_dep);
 
-        println!("{}", style(format!("  ✓ Downloaded {} documents", download_result.documents.len())).green());
+        crate::outln!("{}", style(format!("  ✓ Downloaded {} documents", download_result.documents.len())).green());
     }
 
     // Update docuram config with public dependencies
@@ -423,8 +544,8 @@ async fn fetch_public_dependencies(docuram_config: &mut DocuramConfig) -> Result
     docuram_config.save()
         .context("Failed to save docuram.json with public dependencies")?;
 
-    println!();
-    println!("{}", style(format!("✓ Downloaded {} public dependency document(s)", total_docs_downloaded)).green());
+    crate::outln!();
+    crate::outln!("{}", style(format!("✓ Downloaded {} public dependency document(s)", total_docs_downloaded)).green());
 
     Ok(())
 }
\ No newline at end of file
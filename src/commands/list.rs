@@ -1,20 +1,23 @@
 use anyhow::Result;
 use console::style;
+use serde::Serialize;
 use std::path::Path;
+use std::time::Duration;
 use std::collections::{HashSet, HashMap};
 use walkdir::WalkDir;
 use crate::config::{DocuramConfig, CliConfig};
 use crate::utils;
 use crate::api::{ApiClient, PublicApiClient};
+use crate::api::client::is_offline;
 
 /// Simple struct representing a new local document
-struct NewLocalDocument {
-    file_path: String,
-    title: String,
+pub struct NewLocalDocument {
+    pub file_path: String,
+    pub title: String,
 }
 
 /// Scan docuram/ directory for markdown files
-fn scan_markdown_files(dir: &str) -> Result<Vec<NewLocalDocument>> {
+pub fn scan_markdown_files(dir: &str) -> Result<Vec<NewLocalDocument>> {
     let mut documents = Vec::new();
 
     for entry in WalkDir::new(dir)
@@ -38,6 +41,10 @@ fn scan_markdown_files(dir: &str) -> Result<Vec<NewLocalDocument>> {
             continue;
         }
 
+        if utils::is_ignored(path) {
+            continue;
+        }
+
         // Extract title from filename (without .md extension for display)
         let title = path.file_stem()
             .and_then(|s| s.to_str())
@@ -53,14 +60,153 @@ fn scan_markdown_files(dir: &str) -> Result<Vec<NewLocalDocument>> {
     Ok(documents)
 }
 
-pub async fn execute() -> Result<()> {
-    println!("{}", style("Document List").cyan().bold());
-    println!();
+/// How often `list --watch` checks for changes
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Quiet period after a change before `list --watch` redraws
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Keep only documents by when they were last synced (`list --since`)
+#[derive(Clone, Copy)]
+pub struct SyncFilter {
+    pub since: chrono::DateTime<chrono::Utc>,
+    /// Keep the documents not synced since then instead, including those never synced
+    pub stale: bool,
+}
+
+impl SyncFilter {
+    fn keeps(&self, last_sync: Option<&str>) -> bool {
+        let synced_since = last_sync
+            .and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+            .is_some_and(|time| time >= self.since);
+        synced_since != self.stale
+    }
+
+    /// Drop rejected documents and the directories left empty by that
+    fn apply(&self, tree: &mut HashMap<String, Vec<ListDocumentInfo>>, docuram_config: &DocuramConfig) {
+        // New local and new remote documents have no entry, so they count as never synced
+        let last_syncs: HashMap<&str, Option<&str>> = docuram_config.all_documents()
+            .chain(docuram_config.public_dependencies.iter().flat_map(|dep| dep.documents.iter()))
+            .map(|doc| (doc.uuid.as_str(), doc.last_sync.as_deref()))
+            .collect();
+
+        for docs in tree.values_mut() {
+            docs.retain(|doc| self.keeps(last_syncs.get(doc.uuid.as_str()).copied().flatten()));
+        }
+        tree.retain(|_, docs| !docs.is_empty());
+    }
+}
+
+/// Remote version shown when the server was not contacted (--offline)
+const UNKNOWN_REMOTE_VERSION: &str = "?";
+
+/// Remote state shown next to local documents
+struct RemoteInfo {
+    versions: Result<HashMap<String, i64>>,
+    docs: Result<Vec<crate::api::client::DocumentInfo>>,
+    public_versions: HashMap<String, i64>,
+    public_new_docs: Vec<(String, PublicDocInfo)>,
+    /// Remote state is unknown rather than empty
+    offline: bool,
+}
+
+impl RemoteInfo {
+    async fn fetch(docuram_config: &DocuramConfig) -> Self {
+        if is_offline() {
+            return RemoteInfo {
+                versions: Err(anyhow::anyhow!("Offline mode")),
+                docs: Err(anyhow::anyhow!("Offline mode")),
+                public_versions: HashMap::new(),
+                public_new_docs: Vec::new(),
+                offline: true,
+            };
+        }
+
+        let (versions, docs) = fetch_remote_documents(docuram_config).await;
+        let (public_versions, public_new_docs) = fetch_public_dependencies_info(docuram_config).await;
+        RemoteInfo { versions, docs, public_versions, public_new_docs, offline: false }
+    }
+
+    fn version(&self, uuid: &str) -> String {
+        if self.offline {
+            return UNKNOWN_REMOTE_VERSION.to_string();
+        }
+        get_remote_version(uuid, &self.versions)
+    }
+}
+
+/// List documents as a tree
+/// `include_empty_categories` seeds every category from `category_tree`;
+/// `hide_empty` drops the standard directories when they have no documents;
+/// `watch` redraws the tree whenever local files change;
+/// `json` prints the documents as a JSON array and nothing else;
+/// `sync_filter` keeps only documents synced (or, when stale, not synced) since a point in time
+pub async fn execute(include_empty_categories: bool, hide_empty: bool, watch: bool, json: bool, sync_filter: Option<SyncFilter>) -> Result<()> {
+    // Remote state is fetched once; only local statuses are refreshed while watching
+    let docuram_config = DocuramConfig::load_with_migration()?;
+    let remote = RemoteInfo::fetch(&docuram_config).await;
+
+    if json {
+        return render_json(&docuram_config, &remote, sync_filter);
+    }
+
+    if !watch {
+        return render_list(include_empty_categories, hide_empty, &remote, sync_filter);
+    }
+
+    let fetched_at = chrono::Local::now().format("%H:%M:%S");
+    let term = console::Term::stdout();
+    loop {
+        // Render off-screen first so the redraw does not flicker
+        let (rendered, frame) = crate::utils::output::capture(|| {
+            render_list(include_empty_categories, hide_empty, &remote, sync_filter)
+        });
+        term.clear_screen()?;
+        crate::out!("{}", frame);
+        rendered?;
+        crate::outln!("{}", style(format!(
+            "Watching for changes (remote versions as of {}). Press Ctrl+C to stop.", fetched_at
+        )).dim());
+        wait_for_local_change().await;
+    }
+}
+
+/// Poll until documents or docuram.json change, then wait for writes to settle
+async fn wait_for_local_change() {
+    let initial = local_change_stamp();
+    while local_change_stamp() == initial {
+        tokio::time::sleep(WATCH_POLL_INTERVAL).await;
+    }
+
+    // Editors often save in several steps; redraw once things are quiet
+    let mut last = local_change_stamp();
+    loop {
+        tokio::time::sleep(WATCH_DEBOUNCE).await;
+        let current = local_change_stamp();
+        if current == last {
+            return;
+        }
+        last = current;
+    }
+}
+
+fn local_change_stamp() -> (String, Option<std::time::SystemTime>) {
+    let config_modified = std::fs::metadata(DocuramConfig::config_path())
+        .and_then(|m| m.modified())
+        .ok();
+    (utils::markdown_change_stamp(Path::new("docuram")), config_modified)
+}
+
+/// Print the document tree using already fetched remote state
+fn render_list(include_empty_categories: bool, hide_empty: bool, remote: &RemoteInfo, sync_filter: Option<SyncFilter>) -> Result<()> {
+    crate::outln!("{}", style("Document List").cyan().bold());
+    crate::outln!();
 
     // Load docuram config with migration from state.json
     let mut docuram_config = DocuramConfig::load_with_migration()?;
 
     // Get working category path
+    let layout = docuram_config.layout();
     let working_category_path = &docuram_config.docuram.category_path.clone();
 
     // Auto-detect missing files and mark them as pending deletion
@@ -68,13 +214,13 @@ pub async fn execute() -> Result<()> {
     for doc in docuram_config.all_documents_mut() {
         // Only check documents that have been synced (have local_checksum)
         if !doc.pending_deletion && doc.local_checksum.is_some() {
-            let local_file_path = doc.local_path(working_category_path);
+            let local_file_path = doc.local_path(&layout);
             let file_path = Path::new(&local_file_path);
             if !file_path.exists() {
                 // File is missing - mark for deletion
                 doc.pending_deletion = true;
                 config_changed = true;
-                println!("{} File missing, marked for deletion: {}",
+                crate::outln!("{} File missing, marked for deletion: {}",
                     style("⚠").yellow(), local_file_path);
             }
         }
@@ -83,46 +229,18 @@ pub async fn execute() -> Result<()> {
     // Save config if any changes were made
     if config_changed {
         docuram_config.save()?;
-        println!();
+        crate::outln!();
     }
 
-    // Try to fetch remote documents and versions
-    let (remote_versions, remote_docs) = fetch_remote_documents(&docuram_config).await;
-
     // Scan docuram directory for new local documents (by comparing files vs JSON)
-    let new_local_docs = match scan_markdown_files("docuram") {
-        Ok(docs) => {
-            // Build a set of LOCAL file paths from docuram.json for quick lookup
-            // Use local_path() to convert server paths to local file system paths
-            let docuram_paths: HashSet<String> = docuram_config
-                .all_documents()
-                .map(|d| d.local_path(working_category_path))
-                .collect();
-
-            // Build a set of file paths from local_documents
-            let local_doc_paths: HashSet<String> = docuram_config
-                .local_documents
-                .iter()
-                .map(|d| d.path.clone())
-                .collect();
-
-            // Filter: new documents are those NOT in docuram.json AND NOT in local_documents (by path)
-            docs.into_iter()
-                .filter(|d| {
-                    let in_docuram = docuram_paths.contains(&d.file_path);
-                    let in_local = local_doc_paths.contains(&d.file_path);
-
-                    // Document is new if not found by path in either array
-                    !in_docuram && !in_local
-                })
-                .collect::<Vec<_>>()
-        }
-        Err(_) => Vec::new()
-    };
+    let new_local_docs = find_new_local_docs(&docuram_config);
 
     // Print project info
-    println!("{}", style(format!("Project: {} ({})", docuram_config.project.name, docuram_config.project.url)).bold());
-    println!();
+    crate::outln!("{}", style(format!("Project: {} ({})", docuram_config.project.name, docuram_config.project.url)).bold());
+    if remote.offline {
+        crate::outln!("{}", style("Offline: server not contacted, remote versions and new server documents are unknown").dim());
+    }
+    crate::outln!();
 
     // Collect all documents with their status
     let all_docs: Vec<_> = docuram_config.all_documents().collect();
@@ -132,10 +250,164 @@ pub async fn execute() -> Result<()> {
         .filter(|doc| doc.pending_deletion)
         .collect();
 
-    // No more state_only_docs since all synced documents are now in docuram.json
-    let state_only_docs: Vec<&crate::config::DocumentInfo> = Vec::new();
-
     // Find remote documents that are not in local docuram.json
+    let remote_new_docs = find_remote_new_docs(&docuram_config, remote);
+
+    // Note: pending_deletion_docs are already included in all_docs, so don't double-count
+    let total_count = all_docs.len() + new_local_docs.len() + remote_new_docs.len();
+    if total_count == 0 {
+        crate::outln!("{}", style("No documents found").yellow());
+        return Ok(());
+    }
+
+    crate::outln!("{}", style(format!("Total documents: {} ({} in docuram.json, {} new local, {} new on server, {} pending deletion)",
+        total_count, all_docs.len(), new_local_docs.len(), remote_new_docs.len(), pending_deletion_docs.len())).bold());
+    crate::outln!();
+
+    // Build a tree structure grouped by category
+    let mut tree = collect_document_tree(&docuram_config, &new_local_docs, &remote_new_docs, remote);
+
+    if let Some(filter) = sync_filter {
+        filter.apply(&mut tree, &docuram_config);
+        let shown: usize = tree.values().map(|docs| docs.len()).sum();
+        let since = filter.since.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+        let label = if filter.stale { "not synced since" } else { "synced since" };
+        crate::outln!("{}", style(format!("Showing {} document(s) {} {}", shown, label, since)).dim());
+        crate::outln!();
+    }
+
+    // Empty categories from category_tree are only shown on request
+    if include_empty_categories {
+        if let Some(category_tree) = &docuram_config.category_tree {
+            add_empty_categories_to_tree(&mut tree, category_tree, working_category_path);
+        }
+    }
+
+    // Ensure standard directories are shown (docuram/organic, docuram/impl, docuram/req, docuram/manual) even if empty
+    if !hide_empty {
+        for standard_dir in ["docuram/organic", "docuram/impl", "docuram/req", "docuram/manual"] {
+            if !tree.contains_key(standard_dir) {
+                tree.insert(standard_dir.to_string(), Vec::new());
+            }
+        }
+    }
+
+    // Build hierarchical tree structure
+    let tree_structure = build_tree_structure(&tree);
+
+    // Print tree
+    crate::outln!("{}", style("Document Tree:").bold());
+    crate::outln!();
+
+    print_tree_node(&tree_structure, &tree, "", true, 1, TreeOptions::default());
+
+    crate::outln!();
+    crate::outln!("{}", style("Legend:").bold());
+    crate::outln!("  {} - File synced and unchanged", style("✓ Synced").green());
+    crate::outln!("  {} - File has local modifications", style("⚠ Modified").yellow());
+    crate::outln!("  {} - Error reading file", style("✗ Error").red());
+    crate::outln!("  {} - File not downloaded yet", style("○ Not downloaded").dim());
+    crate::outln!("  {} - New local document (run 'teamturbo push' to upload)", style("+ New").cyan().bold());
+    crate::outln!("  {} - New document on server (run 'teamturbo pull' to download)", style("⬇ Remote").blue().bold());
+    crate::outln!("  {} - File deleted, pending server sync (run 'teamturbo push' to delete from server)", style("🗑 Pending deletion").red().dim());
+    crate::outln!("  {} - Remote version has updates available", style("[v1→v2]").yellow());
+    if remote.offline {
+        crate::outln!("  {} - Remote version unknown (--offline)", style("[v1→?]").dim());
+    }
+    crate::outln!("  {} - Public dependency from docuram.teamturbo.io", style("[PUBLIC]").magenta().bold());
+    crate::outln!();
+
+    Ok(())
+}
+
+/// One document in `list --json` output
+#[derive(Serialize)]
+pub struct ListJsonEntry {
+    pub title: String,
+    /// Absent for new local documents that have no UUID yet
+    pub uuid: Option<String>,
+    /// Directory the document lives in
+    pub category_path: String,
+    pub doc_type: String,
+    /// Stable identifier, see `status_id`
+    pub status: &'static str,
+    pub local_version: Option<i64>,
+    /// Absent when unknown (offline, server unreachable, or not on the server)
+    pub remote_version: Option<i64>,
+    pub public: bool,
+}
+
+/// Print the documents as JSON for scripts
+/// Unlike the tree view this does not mark missing files in docuram.json; they are reported
+/// with the "pending_deletion" status instead.
+fn render_json(docuram_config: &DocuramConfig, remote: &RemoteInfo, sync_filter: Option<SyncFilter>) -> Result<()> {
+    let new_local_docs = find_new_local_docs(docuram_config);
+    let remote_new_docs = find_remote_new_docs(docuram_config, remote);
+    let mut tree = collect_document_tree(docuram_config, &new_local_docs, &remote_new_docs, remote);
+    if let Some(filter) = sync_filter {
+        filter.apply(&mut tree, docuram_config);
+    }
+
+    let mut entries: Vec<ListJsonEntry> = tree
+        .into_iter()
+        .flat_map(|(dir, docs)| {
+            docs.into_iter().map(move |doc| ListJsonEntry {
+                title: doc.title,
+                uuid: Some(doc.uuid).filter(|uuid| !uuid.is_empty()),
+                category_path: dir.clone(),
+                doc_type: doc.doc_type,
+                status: status_id(&doc.status),
+                local_version: doc.local_version.parse().ok(),
+                remote_version: doc.remote_version.parse().ok(),
+                public: doc.is_public,
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| (&a.category_path, &a.title).cmp(&(&b.category_path, &b.title)));
+
+    crate::outln!("{}", serde_json::to_string_pretty(&entries)?);
+    Ok(())
+}
+
+/// Stable machine-readable identifier for a document status label
+fn status_id(status: &str) -> &'static str {
+    match status {
+        "Synced" => "synced",
+        "Modified" => "modified",
+        "Not synced" => "not_synced",
+        "Not downloaded" => "missing",
+        "New" => "new",
+        "Remote" => "remote",
+        "Pending deletion" => "pending_deletion",
+        _ => "error",
+    }
+}
+
+/// Markdown files under docuram/ that push would create as new documents
+/// Provisional entries are listed with their docuram.json entry instead.
+pub fn find_new_local_docs(docuram_config: &DocuramConfig) -> Vec<NewLocalDocument> {
+    let layout = docuram_config.layout();
+    match scan_markdown_files("docuram") {
+        Ok(docs) => {
+            let new_document_filter = docuram_config.new_document_filter();
+            let provisional_paths: HashSet<String> = docuram_config
+                .all_documents()
+                .filter(|d| d.provisional)
+                .map(|d| d.local_path(&layout))
+                .collect();
+
+            docs.into_iter()
+                .filter(|d| new_document_filter.is_candidate(&d.file_path) && !provisional_paths.contains(&d.file_path))
+                .collect::<Vec<_>>()
+        }
+        Err(_) => Vec::new()
+    }
+}
+
+/// Documents on the server that are not in docuram.json
+fn find_remote_new_docs(docuram_config: &DocuramConfig, remote: &RemoteInfo) -> Vec<crate::api::client::DocumentInfo> {
+    let all_docs: Vec<_> = docuram_config.all_documents().collect();
+    let remote_docs = &remote.docs;
     let mut remote_new_docs = Vec::new();
     if let Ok(ref remote_doc_list) = remote_docs {
         let local_uuids: HashSet<String> = all_docs.iter()
@@ -159,24 +431,28 @@ pub async fn execute() -> Result<()> {
         }
     }
 
-    // Note: pending_deletion_docs are already included in all_docs, so don't double-count
-    let total_count = all_docs.len() + new_local_docs.len() + remote_new_docs.len();
-    if total_count == 0 {
-        println!("{}", style("No documents found").yellow());
-        return Ok(());
-    }
+    remote_new_docs
+}
 
-    println!("{}", style(format!("Total documents: {} ({} in docuram.json, {} new local, {} new on server, {} pending deletion)",
-        total_count, all_docs.len(), new_local_docs.len(), remote_new_docs.len(), pending_deletion_docs.len())).bold());
-    println!();
+/// Group every known document (tracked, new local, new remote, public) by directory
+fn collect_document_tree(
+    docuram_config: &DocuramConfig,
+    new_local_docs: &[NewLocalDocument],
+    remote_new_docs: &[crate::api::client::DocumentInfo],
+    remote: &RemoteInfo,
+) -> HashMap<String, Vec<ListDocumentInfo>> {
+    let layout = docuram_config.layout();
+    let all_docs: Vec<_> = docuram_config.all_documents().collect();
+
+    // No more state_only_docs since all synced documents are now in docuram.json
+    let state_only_docs: Vec<&crate::config::DocumentInfo> = Vec::new();
 
-    // Build a tree structure grouped by category
     let mut tree: HashMap<String, Vec<ListDocumentInfo>> = HashMap::new();
 
     // Group documents by actual file directory path (not category_path)
     for doc in &all_docs {
         // Use the path stored in docuram.json (was migrated from state.json if applicable)
-        let actual_file_path = doc.local_path(working_category_path);
+        let actual_file_path = doc.local_path(&layout);
 
         // Extract directory path from actual file path (preserve full path for tree display)
         let file_path = Path::new(&actual_file_path);
@@ -200,7 +476,7 @@ pub async fn execute() -> Result<()> {
                 doc_type: doc.doc_type.clone(),
                 status: get_document_status_from_doc(doc, &actual_file_path),
                 local_version: get_local_version_from_doc(doc),
-                remote_version: get_remote_version(&doc.uuid, &remote_versions),
+                remote_version: remote.version(&doc.uuid),
                 source: DocumentSource::Docuram,
                 is_public: false,
             });
@@ -230,14 +506,14 @@ pub async fn execute() -> Result<()> {
                 doc_type: "?".to_string(),
                 status: "Synced".to_string(),
                 local_version: state_doc.version.to_string(),
-                remote_version: get_remote_version(&state_doc.uuid, &remote_versions),
+                remote_version: remote.version(&state_doc.uuid),
                 source: DocumentSource::StateOnly,
                 is_public: false,
             });
     }
 
     // Add new local documents
-    for new_doc in &new_local_docs {
+    for new_doc in new_local_docs {
         // Extract directory path from the actual file path (preserve full path)
         let file_path = Path::new(&new_doc.file_path);
         let dir_path = if let Some(parent) = file_path.parent() {
@@ -267,9 +543,9 @@ pub async fn execute() -> Result<()> {
     }
 
     // Add remote new documents (on server but not in local docuram.json)
-    for remote_doc in &remote_new_docs {
+    for remote_doc in remote_new_docs {
         // Use local_path() to get correct path (dependencies go in dependencies/ at project root)
-        let local_file_path = remote_doc.local_path(working_category_path);
+        let local_file_path = remote_doc.local_path(&layout);
 
         // Extract directory path from local file path (preserve full path)
         let file_path = Path::new(&local_file_path);
@@ -297,8 +573,8 @@ pub async fn execute() -> Result<()> {
     // The status is determined by get_document_status_from_doc() based on pending_deletion flag
     // No need to add them separately
 
-    // Fetch remote public dependencies for version comparison
-    let (public_remote_versions, public_new_docs) = fetch_public_dependencies_info(&docuram_config).await;
+    // Remote public dependency versions for comparison
+    let public_remote_versions = &remote.public_versions;
 
     // Build set of existing public doc UUIDs
     let existing_public_uuids: HashSet<String> = docuram_config.public_dependencies
@@ -327,10 +603,14 @@ pub async fn execute() -> Result<()> {
             let status = get_document_status_from_doc(doc, &doc.path);
 
             // Get remote version for public dependency
-            let remote_ver = public_remote_versions
-                .get(&doc.uuid)
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| doc.version.to_string());
+            let remote_ver = if remote.offline {
+                UNKNOWN_REMOTE_VERSION.to_string()
+            } else {
+                public_remote_versions
+                    .get(&doc.uuid)
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| doc.version.to_string())
+            };
 
             tree.entry(dir_path)
                 .or_insert_with(Vec::new)
@@ -348,7 +628,7 @@ pub async fn execute() -> Result<()> {
     }
 
     // Add new public documents (on remote but not local)
-    for (category_path, doc) in &public_new_docs {
+    for (category_path, doc) in &remote.public_new_docs {
         if existing_public_uuids.contains(&doc.uuid) {
             continue;
         }
@@ -369,76 +649,57 @@ pub async fn execute() -> Result<()> {
             });
     }
 
-    // No longer add empty categories from category_tree
-    // We only show document type directories (docuram/organic, docuram/impl, etc.) with actual content
-
-    // Ensure standard directories are always shown (docuram/organic, docuram/impl, docuram/req, docuram/manual) even if empty
-    for standard_dir in ["docuram/organic", "docuram/impl", "docuram/req", "docuram/manual"] {
-        if !tree.contains_key(standard_dir) {
-            tree.insert(standard_dir.to_string(), Vec::new());
-        }
-    }
-
-    // Build hierarchical tree structure
-    let tree_structure = build_tree_structure(&tree);
-
-    // Print tree
-    println!("{}", style("Document Tree:").bold());
-    println!();
-
-    print_tree_node(&tree_structure, &tree, "", true);
-
-    println!();
-    println!("{}", style("Legend:").bold());
-    println!("  {} - File synced and unchanged", style("✓ Synced").green());
-    println!("  {} - File has local modifications", style("⚠ Modified").yellow());
-    println!("  {} - Error reading file", style("✗ Error").red());
-    println!("  {} - File not downloaded yet", style("○ Not downloaded").dim());
-    println!("  {} - New local document (run 'teamturbo push' to upload)", style("+ New").cyan().bold());
-    println!("  {} - New document on server (run 'teamturbo pull' to download)", style("⬇ Remote").blue().bold());
-    println!("  {} - File deleted, pending server sync (run 'teamturbo push' to delete from server)", style("🗑 Pending deletion").red().dim());
-    println!("  {} - Remote version has updates available", style("[v1→v2]").yellow());
-    println!("  {} - Public dependency from docuram.teamturbo.io", style("[PUBLIC]").magenta().bold());
-    println!();
-
-    Ok(())
+    tree
 }
 
 // Helper structures
-struct ListDocumentInfo {
-    title: String,
-    uuid: String,
-    doc_type: String,
-    status: String,
-    local_version: String,
-    remote_version: String,
-    source: DocumentSource,
-    is_public: bool,
+pub struct ListDocumentInfo {
+    pub title: String,
+    pub uuid: String,
+    pub doc_type: String,
+    pub status: String,
+    pub local_version: String,
+    pub remote_version: String,
+    pub source: DocumentSource,
+    pub is_public: bool,
 }
 
-enum DocumentSource {
+pub enum DocumentSource {
     Docuram,
     StateOnly,
     New,
     Remote,
 }
 
+/// Rendering options for the document tree
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TreeOptions {
+    /// Use plain ASCII connectors and markers instead of box-drawing characters and emoji
+    pub ascii: bool,
+    /// Maximum directory depth to render (None renders the full tree)
+    pub max_depth: Option<usize>,
+}
+
 // Helper functions
-fn get_document_status_from_doc(doc: &crate::config::DocumentInfo, path: &str) -> String {
+pub fn get_document_status_from_doc(doc: &crate::config::DocumentInfo, path: &str) -> String {
     // Check if marked for deletion first
     if doc.pending_deletion {
         return "Pending deletion".to_string();
     }
 
+    // Registered with 'add --register' but not pushed yet
+    if doc.provisional {
+        return "New".to_string();
+    }
+
     // Check if document has been synced (has local_checksum)
     if let Some(local_checksum) = &doc.local_checksum {
         let file_path = Path::new(path);
         if file_path.exists() {
             match utils::read_file(path) {
                 Ok(content) => {
-                    // Calculate checksum of complete content
-                    let current_checksum = utils::calculate_checksum(&content);
-                    if current_checksum == *local_checksum {
+                    // Complete content, ignoring CRLF/LF differences
+                    if utils::content_matches_checksum(&content, local_checksum) {
                         "Synced".to_string()
                     } else {
                         "Modified".to_string()
@@ -460,7 +721,7 @@ fn get_document_status_from_doc(doc: &crate::config::DocumentInfo, path: &str) -
     }
 }
 
-fn get_local_version_from_doc(doc: &crate::config::DocumentInfo) -> String {
+pub fn get_local_version_from_doc(doc: &crate::config::DocumentInfo) -> String {
     if doc.local_checksum.is_some() {
         doc.version.to_string()
     } else {
@@ -479,7 +740,21 @@ fn get_remote_version(uuid: &str, remote_versions: &Result<HashMap<String, i64>>
     }
 }
 
-fn get_status_colored(status: &str) -> console::StyledObject<String> {
+fn get_status_colored(status: &str, ascii: bool) -> console::StyledObject<String> {
+    if ascii {
+        let marker = match status {
+            "Synced" => "ok",
+            "Modified" | "Not synced" => "!",
+            "Error" => "x",
+            "Not downloaded" => "o",
+            "New" => "+",
+            "Remote" => "v",
+            "Pending deletion" => "-",
+            _ => "",
+        };
+        return style(format!("[{}] {}", marker, status)).white();
+    }
+
     match status {
         "Synced" => style(format!("✓ {}", status)).green(),
         "Modified" => style(format!("⚠ {}", status)).yellow(),
@@ -494,7 +769,9 @@ fn get_status_colored(status: &str) -> console::StyledObject<String> {
 }
 
 fn format_version_info(local_version: &str, remote_version: &str) -> console::StyledObject<String> {
-    if local_version != "-" && remote_version != "-" {
+    if local_version != "-" && remote_version == UNKNOWN_REMOTE_VERSION {
+        style(format!("[v{}→?]", local_version)).dim()
+    } else if local_version != "-" && remote_version != "-" {
         let local_ver: i64 = local_version.parse().unwrap_or(0);
         let remote_ver: i64 = remote_version.parse().unwrap_or(0);
         if remote_ver > local_ver {
@@ -511,13 +788,13 @@ fn format_version_info(local_version: &str, remote_version: &str) -> console::St
 
 // Tree structure for hierarchical display
 #[derive(Debug)]
-struct TreeNode {
+pub struct TreeNode {
     path: String,
     children: Vec<TreeNode>,
 }
 
 /// Build hierarchical tree structure from flat category paths
-fn build_tree_structure(tree: &HashMap<String, Vec<ListDocumentInfo>>) -> Vec<TreeNode> {
+pub fn build_tree_structure(tree: &HashMap<String, Vec<ListDocumentInfo>>) -> Vec<TreeNode> {
     let mut all_paths: Vec<String> = tree.keys().cloned().collect();
     all_paths.sort();
 
@@ -600,23 +877,31 @@ fn get_parent_path(path: &str) -> Option<String> {
 }
 
 /// Print tree node recursively
-fn print_tree_node(
+pub fn print_tree_node(
     nodes: &[TreeNode],
     tree: &HashMap<String, Vec<ListDocumentInfo>>,
     prefix: &str,
     is_root: bool,
+    depth: usize,
+    options: TreeOptions,
 ) {
+    let (branch, last_branch, pipe, dir_icon, doc_icon) = if options.ascii {
+        ("|--", "`--", "|", "[D]", "-")
+    } else {
+        ("├──", "└──", "│", "📁", "📄")
+    };
+
     for (idx, node) in nodes.iter().enumerate() {
         let is_last = idx == nodes.len() - 1;
 
         // Print category - extract just the directory name from the full path
-        let category_prefix = if is_last { "└──" } else { "├──" };
+        let category_prefix = if is_last { last_branch } else { branch };
         let dir_name = node.path.split('/').last().unwrap_or(&node.path);
 
-        println!("{}{} {} {}",
+        crate::outln!("{}{} {} {}",
             prefix,
             style(category_prefix).dim(),
-            style("📁").cyan(),
+            style(dir_icon).cyan(),
             style(dir_name).bold().cyan()
         );
 
@@ -624,23 +909,25 @@ fn print_tree_node(
         let node_prefix = if is_last {
             format!("{}   ", prefix)
         } else {
-            format!("{}│  ", prefix)
+            format!("{}{}  ", prefix, pipe)
         };
 
+        // Children beyond the depth limit are collapsed
+        let show_children = options.max_depth.map_or(true, |max| depth < max);
+        let has_children = show_children && !node.children.is_empty();
+
         // Print documents in this category
         if let Some(docs) = tree.get(&node.path) {
-            let has_children = !node.children.is_empty();
-
             if docs.is_empty() && !has_children {
                 // Empty directory with no children
-                println!("{}   {}", node_prefix, style("(empty)").dim().italic());
+                crate::outln!("{}   {}", node_prefix, style("(empty)").dim().italic());
             } else {
                 for (doc_idx, doc) in docs.iter().enumerate() {
                     let is_last_doc = doc_idx == docs.len() - 1 && !has_children;
-                    let doc_prefix = if is_last_doc { "└──" } else { "├──" };
+                    let doc_prefix = if is_last_doc { last_branch } else { branch };
 
                     // Format document line
-                    let status_colored = get_status_colored(&doc.status);
+                    let status_colored = get_status_colored(&doc.status, options.ascii);
                     let version_info = format_version_info(&doc.local_version, &doc.remote_version);
 
                     // Apply strikethrough to title if pending deletion
@@ -657,10 +944,10 @@ fn print_tree_node(
                         style("").white()
                     };
 
-                    println!("{}{} {} {} {} {} {}",
+                    crate::outln!("{}{} {} {} {} {} {}",
                         node_prefix,
                         style(doc_prefix).dim(),
-                        style("📄").dim(),
+                        style(doc_icon).dim(),
                         title_styled,
                         public_marker,
                         status_colored,
@@ -671,13 +958,15 @@ fn print_tree_node(
         }
 
         // Print children categories
-        if !node.children.is_empty() {
-            print_tree_node(&node.children, tree, &node_prefix, false);
+        if has_children {
+            print_tree_node(&node.children, tree, &node_prefix, false, depth + 1, options);
+        } else if !node.children.is_empty() {
+            crate::outln!("{}{} {}", node_prefix, style(last_branch).dim(), style("...").dim());
         }
 
         // Print vertical line between root categories
         if is_root && !is_last {
-            println!("{}", style("│").dim());
+            crate::outln!("{}", style(pipe).dim());
         }
     }
 }
@@ -693,7 +982,7 @@ async fn fetch_remote_documents(docuram_config: &DocuramConfig) -> (Result<HashM
     let server_url = docuram_config.server_url();
 
     // Get auth for this server
-    let auth = match cli_config.get_auth(server_url) {
+    let auth = match cli_config.resolve_auth(server_url) {
         Some(auth) => auth,
         None => {
             let err_msg = format!("Not logged in to {}. Showing local versions only.", server_url);
@@ -702,19 +991,10 @@ async fn fetch_remote_documents(docuram_config: &DocuramConfig) -> (Result<HashM
     };
 
     // Create API client
-    let client = ApiClient::new(server_url.to_string(), auth.access_token.clone());
-
-    // Get category UUID from docuram config
-    let category_uuid = match &docuram_config.docuram.category_uuid {
-        Some(uuid) => uuid,
-        None => {
-            let err_msg = "No category UUID in docuram.json";
-            return (Err(anyhow::anyhow!("{}", err_msg)), Err(anyhow::anyhow!("{}", err_msg)));
-        }
-    };
+    let client = ApiClient::from_auth(server_url.to_string(), &auth);
 
-    // Fetch document versions
-    let remote_docs = match client.get_document_versions(category_uuid).await {
+    // Fetch document versions (working category and merged categories)
+    let remote_docs = match crate::commands::pull::fetch_document_versions(&client, docuram_config).await {
         Ok(docs) => docs,
         Err(e) => return (Err(e.into()), Err(anyhow::anyhow!("Failed to fetch remote documents"))),
     };
@@ -728,30 +1008,26 @@ async fn fetch_remote_documents(docuram_config: &DocuramConfig) -> (Result<HashM
     (Ok(versions_map), Ok(remote_docs))
 }
 
-// Function removed - no longer needed with the new flat document type structure
-// We only show directories that actually contain documents
-//
-// /// Recursively add empty categories to the tree
-// fn add_empty_categories_to_tree(
-//     tree: &mut HashMap<String, Vec<DocumentInfo>>,
-//     category: &crate::config::CategoryTree,
-//     _parent_path: &str,
-// ) {
-//     // Use the full path from the category tree
-//     let current_path = &category.path;
-//
-//     // If this category has no documents and is not already in the tree, add it as empty
-//     if category.document_count == 0 && !tree.contains_key(current_path) {
-//         tree.insert(current_path.clone(), Vec::new());
-//     }
-//
-//     // Recursively process subcategories
-//     if let Some(ref subcategories) = category.subcategories {
-//         for subcat in subcategories {
-//             add_empty_categories_to_tree(tree, subcat, "");
-//         }
-//     }
-// }
+/// Recursively add empty categories to the tree
+/// Category paths are mapped to local directories the same way documents are:
+/// `<working>/organic/sub` -> `docuram/organic/sub`
+fn add_empty_categories_to_tree(
+    tree: &mut HashMap<String, Vec<ListDocumentInfo>>,
+    category: &crate::config::CategoryTree,
+    working_category_path: &str,
+) {
+    // Only subcategories of the working category have a local directory
+    if let Some(relative) = category.path.strip_prefix(&format!("{}/", working_category_path)) {
+        tree.entry(format!("docuram/{}", relative)).or_default();
+    }
+
+    // Recursively process subcategories
+    if let Some(ref subcategories) = category.subcategories {
+        for subcat in subcategories {
+            add_empty_categories_to_tree(tree, subcat, working_category_path);
+        }
+    }
+}
 
 /// Public dependency document info for list display
 struct PublicDocInfo {
@@ -1,13 +1,15 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
 use console::style;
 use dialoguer::Input;
+use crate::api::ApiClient;
 use crate::auth;
 use crate::config::CliConfig;
 
 /// Parse domain input and convert to full URL
 /// - If input starts with http:// or https://, use as-is
 /// - Otherwise, treat as subdomain and construct https://{subdomain}.teamturbo.io
-fn parse_domain(domain: &str) -> String {
+pub fn parse_domain(domain: &str) -> String {
     let domain = domain.trim().trim_end_matches('/');
 
     if domain.starts_with("http://") || domain.starts_with("https://") {
@@ -18,14 +20,54 @@ fn parse_domain(domain: &str) -> String {
     }
 }
 
-pub async fn execute(domain: Option<String>, _force_browser: bool, force_manual: bool) -> Result<()> {
-    println!("{}", style("TeamTurbo CLI Login").cyan().bold());
-    println!();
+/// Tokens valid for less than this many days trigger a warning after login
+const SHORT_TOKEN_LIFETIME_DAYS: i64 = 7;
+
+/// Remaining lifetime of a token, or None if `expires_at` is not RFC 3339
+fn token_lifetime(expires_at: &str, now: DateTime<Utc>) -> Option<Duration> {
+    DateTime::parse_from_rfc3339(expires_at)
+        .ok()
+        .map(|expires| expires.with_timezone(&Utc).signed_duration_since(now))
+}
+
+fn print_token_lifetime(expires_at: &str) {
+    let Some(lifetime) = token_lifetime(expires_at, Utc::now()) else {
+        crate::outln!("{} {}", style("Token expires:").dim(), expires_at);
+        return;
+    };
+
+    let days = lifetime.num_days();
+    let remaining = if days > 0 {
+        format!("{} day{}", days, if days == 1 { "" } else { "s" })
+    } else {
+        let hours = lifetime.num_hours().max(0);
+        format!("{} hour{}", hours, if hours == 1 { "" } else { "s" })
+    };
+    crate::outln!("{} {}, until {}", style("Token valid for").dim(), remaining, expires_at);
+
+    if days < SHORT_TOKEN_LIFETIME_DAYS {
+        crate::outln!("{}", style(format!(
+            "⚠ This token is unusually short-lived; you will need to run 'teamturbo login' again within {}",
+            remaining
+        )).yellow());
+    }
+}
+
+/// `token` logs in with an existing CLI token (`-` reads it from stdin) instead of authorizing
+pub async fn execute(domain: Option<String>, _force_browser: bool, force_manual: bool, token: Option<String>) -> Result<()> {
+    crate::outln!("{}", style("TeamTurbo CLI Login").cyan().bold());
+    crate::outln!();
 
     // Get server URL
+    let default_server = CliConfig::load()?.default_server;
     let server_url: String = if let Some(domain_input) = domain {
         // Use provided domain parameter
         parse_domain(&domain_input)
+    } else if let Some(default_server) = default_server {
+        crate::outln!("{}", style("Using the default server (change it with 'teamturbo config set default_server <url>')").dim());
+        parse_domain(&default_server)
+    } else if token.is_some() {
+        anyhow::bail!("--token needs --domain, or a default server set with 'teamturbo config set default_server <url>'");
     } else {
         // Interactive prompt
         let input: String = Input::new()
@@ -35,8 +77,8 @@ pub async fn execute(domain: Option<String>, _force_browser: bool, force_manual:
         parse_domain(&input)
     };
 
-    println!("{} {}", style("→ Connecting to:").dim(), style(&server_url).cyan());
-    println!();
+    crate::outln!("{} {}", style("→ Connecting to:").dim(), style(&server_url).cyan());
+    crate::outln!();
 
     // Determine authentication mode
     let use_browser = if force_manual {
@@ -47,23 +89,50 @@ pub async fn execute(domain: Option<String>, _force_browser: bool, force_manual:
     };
 
     // Perform authorization
-    let auth_config = if use_browser {
+    let auth_config = if let Some(token) = token {
+        let token = if token == "-" {
+            let mut token = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut token)
+                .context("Failed to read token from stdin")?;
+            token
+        } else {
+            token
+        };
+        auth::manual::verify_token(&server_url, &token).await?
+    } else if use_browser {
         auth::browser::authorize(&server_url).await?
     } else {
         auth::manual::authorize(&server_url).await?
     };
 
+    // Record what the server supports so later commands can adapt
+    let mut auth_config = auth_config;
+    let client = ApiClient::new(server_url.clone(), auth_config.access_token.clone());
+    match client.get_capabilities().await {
+        Ok(capabilities) => {
+            if let Some(version) = &capabilities.version {
+                crate::outln!("  {} {}", style("Server version:").dim(), version);
+            }
+            auth_config.capabilities = Some(capabilities);
+        }
+        Err(e) => {
+            crate::outln!("{}", style(format!("⚠ Could not detect server capabilities: {}", e)).yellow());
+        }
+    }
+
     // Save to config
+    let expires_at = auth_config.expires_at.clone();
     let mut config = CliConfig::load()?;
     config.set_auth(server_url.clone(), auth_config);
     config.save()?;
 
-    println!();
-    println!("{}", style("✓ Token saved to ~/.teamturbo-cli/config.toml").green());
-    println!();
-    println!("{}", style("You can now use other commands like:").dim());
-    println!("  {} {}", style("teamturbo init --config-url").dim(), style("<config_url>").yellow());
-    println!("  {} {}", style("teamturbo pull").dim(), style("").yellow());
+    crate::outln!();
+    crate::outln!("{}", style("✓ Token saved to ~/.teamturbo-cli/config.toml").green());
+    print_token_lifetime(&expires_at);
+    crate::outln!();
+    crate::outln!("{}", style("You can now use other commands like:").dim());
+    crate::outln!("  {} {}", style("teamturbo init --config-url").dim(), style("<config_url>").yellow());
+    crate::outln!("  {} {}", style("teamturbo pull").dim(), style("").yellow());
 
     Ok(())
 }
@@ -93,6 +162,14 @@ mod tests {
         assert_eq!(parse_domain("http://example.com/"), "http://example.com");
     }
 
+    #[test]
+    fn test_token_lifetime() {
+        let now = DateTime::parse_from_rfc3339("2030-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(token_lifetime("2030-01-31T00:00:00Z", now).unwrap().num_days(), 30);
+        assert_eq!(token_lifetime("2030-01-01T06:00:00+00:00", now).unwrap().num_hours(), 6);
+        assert!(token_lifetime("next month", now).is_none());
+    }
+
     #[test]
     fn test_parse_domain_whitespace() {
         assert_eq!(parse_domain("  example  "), "https://example.teamturbo.io");
@@ -0,0 +1,144 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::api::ApiClient;
+use crate::config::{CliConfig, DocuramConfig, DocumentInfo};
+
+/// Machine-readable description of the checkout
+#[derive(Debug, Serialize)]
+struct Manifest {
+    project: String,
+    server_url: String,
+    category_path: String,
+    category_uuid: Option<String>,
+    /// Whether remote versions were refreshed from the server
+    remote: bool,
+    documents: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct ManifestEntry {
+    uuid: String,
+    title: String,
+    /// "working", "dependency" or "public"
+    kind: &'static str,
+    doc_type: String,
+    category_path: String,
+    /// Local file path relative to project root
+    local_path: String,
+    /// Server-side document path
+    server_path: String,
+    exists: bool,
+    version: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_version: Option<i64>,
+    checksum: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    local_checksum: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_sync: Option<String>,
+    pending_deletion: bool,
+    remote_url: String,
+}
+
+/// Print a JSON manifest of all tracked documents to stdout
+pub async fn execute(remote: bool) -> Result<()> {
+    // Load docuram config with migration from state.json
+    let docuram_config = DocuramConfig::load_with_migration()
+        .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+
+    let remote_versions = if remote {
+        Some(fetch_remote_versions(&docuram_config).await?)
+    } else {
+        None
+    };
+
+    let layout = docuram_config.layout();
+    let working_category_path = &docuram_config.docuram.category_path;
+    let project_url = docuram_config.project.url.trim_end_matches('/');
+
+    let mut documents = Vec::new();
+
+    for doc in &docuram_config.documents {
+        let local_path = doc.local_path(&layout);
+        documents.push(manifest_entry(doc, "working", local_path, project_url, &remote_versions));
+    }
+
+    for doc in &docuram_config.requires {
+        let local_path = doc.local_path(&layout);
+        documents.push(manifest_entry(doc, "dependency", local_path, project_url, &remote_versions));
+    }
+
+    // Public dependency paths are already local (dependencies/...)
+    for public_dep in &docuram_config.public_dependencies {
+        for doc in &public_dep.documents {
+            let source_url = public_dep.source_url.trim_end_matches('/');
+            documents.push(manifest_entry(doc, "public", doc.path.clone(), source_url, &None));
+        }
+    }
+
+    let manifest = Manifest {
+        project: docuram_config.project.name.clone(),
+        server_url: docuram_config.server_url().to_string(),
+        category_path: working_category_path.clone(),
+        category_uuid: docuram_config.docuram.category_uuid.clone(),
+        remote,
+        documents,
+    };
+
+    let json = serde_json::to_string_pretty(&manifest)
+        .context("Failed to serialize manifest")?;
+    crate::outln!("{}", json);
+
+    Ok(())
+}
+
+fn manifest_entry(
+    doc: &DocumentInfo,
+    kind: &'static str,
+    local_path: String,
+    base_url: &str,
+    remote_versions: &Option<HashMap<String, i64>>,
+) -> ManifestEntry {
+    ManifestEntry {
+        uuid: doc.uuid.clone(),
+        title: doc.title.clone(),
+        kind,
+        doc_type: doc.doc_type.clone(),
+        category_path: doc.category_path.clone(),
+        exists: Path::new(&local_path).exists(),
+        local_path,
+        server_path: doc.path.clone(),
+        version: doc.version,
+        remote_version: remote_versions.as_ref().and_then(|v| v.get(&doc.uuid).copied()),
+        checksum: doc.checksum.clone(),
+        local_checksum: doc.local_checksum.clone(),
+        last_sync: doc.last_sync.clone(),
+        pending_deletion: doc.pending_deletion,
+        remote_url: doc.remote_url(base_url),
+    }
+}
+
+/// Fetch current document versions from the server
+async fn fetch_remote_versions(docuram_config: &DocuramConfig) -> Result<HashMap<String, i64>> {
+    let cli_config = CliConfig::load()?;
+    let server_url = docuram_config.server_url();
+
+    let auth = cli_config
+        .resolve_auth(server_url)
+        .context(format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
+
+    let client = ApiClient::from_auth(server_url.to_string(), &auth);
+
+    let category_uuid = docuram_config.docuram.category_uuid.as_ref()
+        .context("No category UUID in docuram.json")?;
+
+    let remote_docs = client.get_document_versions(category_uuid).await?;
+
+    Ok(remote_docs
+        .into_iter()
+        .map(|doc| (doc.uuid, doc.version))
+        .collect())
+}
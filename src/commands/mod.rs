@@ -7,9 +7,17 @@ pub mod push;
 pub mod sync;
 pub mod diff;
 pub mod list;
+pub mod tree;
+pub mod manifest;
+pub mod export;
 pub mod import;
 pub mod delete;
+pub mod mv;
+pub mod clean;
 pub mod feedback;
 pub mod upgrade;
 pub mod add;
 pub mod verify;
+pub mod serve;
+pub mod config;
+pub mod status;
@@ -0,0 +1,144 @@
+use anyhow::{Context, Result};
+use console::style;
+use std::fs;
+use std::path::Path;
+
+use crate::api::ApiClient;
+use crate::auth::FEATURE_DOCUMENT_MOVE;
+use crate::config::{CliConfig, DocuramConfig};
+use crate::utils::normalize_category_path;
+
+/// Move a document to another category, keeping its uuid and history
+/// `category` is relative to the working category (e.g. `impl/feature`) or a full category path.
+pub async fn execute(path: String, category: String) -> Result<()> {
+    crate::outln!("{}", style("Move Document").cyan().bold());
+    crate::outln!();
+
+    let mut docuram_config = DocuramConfig::load_with_migration()
+        .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+    let layout = docuram_config.layout();
+    let working_category_path = docuram_config.docuram.category_path.clone();
+    let merged_category_paths: Vec<&str> = docuram_config.merged_categories.iter()
+        .map(|m| m.category_path.as_str())
+        .collect();
+    let target_category = resolve_target_category(&category, &working_category_path, &merged_category_paths);
+
+    let index = find_document(&docuram_config, &path).with_context(|| {
+        if docuram_config.requires.iter().any(|d| d.local_path(&layout) == path) {
+            format!("{} is a dependency and cannot be moved", path)
+        } else {
+            format!("{} is not a tracked document (move untracked files yourself; push picks the category from the location)", path)
+        }
+    })?;
+
+    let doc = &docuram_config.documents[index];
+    if doc.category_path == target_category {
+        crate::outln!("{}", style(format!("{} is already in {}", path, target_category)).yellow());
+        return Ok(());
+    }
+
+    let old_local_path = doc.local_path(&layout);
+    let filename = Path::new(&old_local_path).file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&doc.title)
+        .to_string();
+
+    let mut moved = doc.clone();
+    moved.category_path = target_category.clone();
+    moved.category_name = target_category.rsplit('/').next().unwrap_or(&target_category).to_string();
+    moved.path = format!("docuram/{}/{}", target_category, filename);
+    let new_local_path = moved.local_path(&layout);
+
+    if Path::new(&new_local_path).exists() {
+        anyhow::bail!("Cannot move {}: {} already exists", old_local_path, new_local_path);
+    }
+
+    // Documents registered with 'add --register' are not on the server yet; push creates them
+    // in the category matching their new location
+    if !doc.provisional {
+        let server_url = docuram_config.server_url().to_string();
+        let cli_config = CliConfig::load()?;
+        let auth = cli_config.resolve_auth(&server_url)
+            .with_context(|| format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
+        if !auth.supports(FEATURE_DOCUMENT_MOVE) {
+            anyhow::bail!(
+                "{} does not support moving documents (feature '{}' missing). Upgrade the server or move the document in the web UI.",
+                server_url, FEATURE_DOCUMENT_MOVE
+            );
+        }
+        let client = ApiClient::from_auth(server_url.clone(), &auth);
+
+        let category_id = client.ensure_category_by_path(&target_category).await
+            .with_context(|| format!("Failed to find or create category '{}'", target_category))?;
+        let updated = client.move_document(&doc.uuid, category_id).await?;
+
+        moved.category_id = category_id;
+        moved.version = updated.version;
+        if let Some(category) = updated.category {
+            moved.category_uuid = category.uuid;
+            moved.category_name = category.name;
+        }
+    }
+
+    if Path::new(&old_local_path).exists() {
+        if let Some(parent) = Path::new(&new_local_path).parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        fs::rename(&old_local_path, &new_local_path)
+            .with_context(|| format!("Failed to move {} to {} (the server copy was moved; run 'teamturbo pull' to resync)", old_local_path, new_local_path))?;
+    }
+
+    docuram_config.documents[index] = moved;
+    docuram_config.save()
+        .context("Failed to save docuram.json")?;
+
+    crate::outln!("{} {} → {}", style("✓").green(), old_local_path, new_local_path);
+    crate::outln!("  Category: {}", style(&target_category).cyan());
+
+    Ok(())
+}
+
+/// Index in `documents` of the document stored at `path`
+fn find_document(docuram_config: &DocuramConfig, path: &str) -> Option<usize> {
+    let layout = docuram_config.layout();
+    let path = path.trim_start_matches("./");
+    let canonical = Path::new(path).canonicalize().ok();
+
+    docuram_config.documents.iter().position(|doc| {
+        let local_path = doc.local_path(&layout);
+        local_path == path || (canonical.is_some() && Path::new(&local_path).canonicalize().ok() == canonical)
+    })
+}
+
+/// Full category path for a destination given relative to the working category
+/// Paths already under the working category or a merged category are kept as they are.
+pub fn resolve_target_category(category: &str, working_category_path: &str, merged_category_paths: &[&str]) -> String {
+    let category = normalize_category_path(category);
+    let is_full_path = std::iter::once(working_category_path)
+        .chain(merged_category_paths.iter().copied())
+        .any(|root| category == root || category.starts_with(&format!("{}/", root)));
+
+    if is_full_path {
+        category
+    } else if category.is_empty() {
+        working_category_path.to_string()
+    } else {
+        format!("{}/{}", working_category_path, category)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_target_category() {
+        assert_eq!(resolve_target_category("impl/feat", "Cat", &[]), "Cat/impl/feat");
+        assert_eq!(resolve_target_category("docuram/organic/", "Cat", &[]), "Cat/organic");
+        assert_eq!(resolve_target_category("Cat/impl", "Cat", &[]), "Cat/impl");
+        assert_eq!(resolve_target_category("Cat", "Cat", &[]), "Cat");
+        assert_eq!(resolve_target_category("Other/organic", "Cat", &["Other"]), "Other/organic");
+        assert_eq!(resolve_target_category("Catalog", "Cat", &[]), "Cat/Catalog");
+    }
+}
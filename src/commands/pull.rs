@@ -1,22 +1,93 @@
 use anyhow::{Context, Result};
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
+use futures_util::StreamExt;
+use indicatif::ProgressStyle;
 use std::collections::{HashSet, HashMap};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::time::Instant;
 
 use crate::api::{ApiClient, PublicApiClient};
-use crate::config::{CliConfig, DocuramConfig, DocumentInfo, PublicDependency};
-use crate::utils::{write_file, read_file, calculate_checksum, logger};
+use crate::api::client::{download_concurrency, set_download_concurrency, DocumentContent, DownloadError};
+use crate::auth::FEATURE_CONCURRENT_DOWNLOADS;
+use crate::commands::diff::{has_conflict_markers, merge_three_way, merge_with_markers};
+use crate::config::{reconcile_document_path, CliConfig, DocuramConfig, DocumentInfo, PublicDependency};
+use crate::utils::{write_file, read_file, calculate_checksum, calculate_content_checksum, checksums_match, matches_ignoring_line_endings, select_documents, transfer_summary, logger, PathFilter};
+
+/// Remote versions for the working category and any categories added with `init --merge`
+/// Documents listed by several categories (e.g. shared dependencies) appear once.
+pub async fn fetch_document_versions(
+    client: &ApiClient,
+    docuram_config: &DocuramConfig,
+) -> Result<Vec<crate::api::client::DocumentInfo>> {
+    if docuram_config.docuram.category_uuid.is_none() {
+        anyhow::bail!("No category UUID in docuram.json");
+    }
+
+    let mut remote_docs: Vec<crate::api::client::DocumentInfo> = Vec::new();
+    for category_uuid in docuram_config.category_uuids() {
+        let docs = client.get_document_versions(&category_uuid).await?;
+        let known: HashSet<String> = remote_docs.iter().map(|d| d.uuid.clone()).collect();
+        remote_docs.extend(docs.into_iter().filter(|d| !known.contains(&d.uuid)));
+    }
+    Ok(remote_docs)
+}
 
-pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
-    println!("{}", style("Pull Document Updates").cyan().bold());
-    println!();
+/// Options for `pull`
+pub struct PullOptions {
+    /// Only pull documents of these types (empty means all)
+    pub document_types: Vec<String>,
+    /// Overwrite local changes
+    pub force: bool,
+    /// Overwrite local modifications only when the remote version is newer than the last
+    /// synced version, so local edits are never discarded for an unchanged remote
+    pub force_with_lease: bool,
+    /// Treat line-ending-only differences as unmodified and update the stored checksum
+    pub heal: bool,
+    /// Download just the documents discovered in this pull and skip existing ones
+    pub only_new: bool,
+    /// Export into this directory instead of the working tree
+    pub to: Option<String>,
+    /// File (or `-` for stdin) listing UUIDs or paths to pull, one per line
+    pub document_list: Option<String>,
+    /// How to resolve documents changed both locally and on the server
+    pub conflict_strategy: Option<ConflictStrategy>,
+    /// Only pull documents whose local path passes `--include`/`--exclude`
+    pub filter: PathFilter,
+}
+
+/// Resolution for a document modified locally while the server got a newer version
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ConflictStrategy {
+    /// Keep the local file (the following push uploads it)
+    Ours,
+    /// Take the remote version, discarding local changes
+    Theirs,
+    /// Write both versions into the file between merge markers
+    Manual,
+    /// Three-way merge against the last synced version; only overlapping edits get merge markers
+    Merge,
+}
+
+/// Pull document updates from server
+pub async fn execute(documents: Vec<String>, options: PullOptions) -> Result<()> {
+    crate::outln!("{}", style("Pull Document Updates").cyan().bold());
+    crate::outln!();
+    let PullOptions { document_types, force, force_with_lease, heal, only_new, to, document_list, conflict_strategy, filter } = options;
+    let started = Instant::now();
 
     // Load docuram config with migration from state.json
     let mut docuram_config = DocuramConfig::load_with_migration()
         .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
 
+    // Move files still at the locations recorded in the legacy state.json
+    docuram_config.reconcile_state_file()?;
+
+    let Some(documents) = select_documents(&docuram_config, documents, document_list.as_deref())? else {
+        crate::outln!("{}", style("Document list is empty, nothing to pull").yellow());
+        return Ok(());
+    };
+
     // Load CLI config
     let cli_config = CliConfig::load()?;
 
@@ -24,21 +95,26 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
 
     // Get auth for this server
     let auth = cli_config
-        .get_auth(&server_url)
+        .resolve_auth(&server_url)
         .context(format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
 
-    // Create API client
-    let client = ApiClient::new(server_url.clone(), auth.access_token.clone());
+    // Servers that cannot serve downloads in parallel get them one at a time
+    if !auth.supports(FEATURE_CONCURRENT_DOWNLOADS) && download_concurrency() > 1 {
+        logger::debug("pull", "Server does not support concurrent downloads, downloading sequentially");
+        set_download_concurrency(1);
+    }
 
-    // Get category UUID from docuram config
-    let category_uuid = match &docuram_config.docuram.category_uuid {
-        Some(uuid) => uuid.clone(),
-        None => anyhow::bail!("No category UUID in docuram.json"),
-    };
+    // Create API client
+    let client = ApiClient::from_auth(server_url.clone(), &auth);
 
     // Fetch all remote documents (including dependencies and new documents)
-    println!("{}", style("Fetching remote documents...").dim());
-    let remote_docs = client.get_document_versions(&category_uuid).await?;
+    crate::outln!("{}", style("Fetching remote documents...").dim());
+    let remote_docs = fetch_document_versions(&client, &docuram_config).await?;
+
+    // Export mode: write documents to another directory, leaving docuram/ and docuram.json untouched
+    if let Some(output_dir) = to {
+        return export_documents(&client, &remote_docs, &documents, &document_types, Path::new(&output_dir)).await;
+    }
 
     // Build a map of remote versions for quick lookup
     let remote_versions: std::collections::HashMap<String, i64> = remote_docs
@@ -47,7 +123,7 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
         .collect();
 
     // Ensure document type directories exist
-    println!("{}", style("Ensuring document type directories exist...").dim());
+    crate::outln!("{}", style("Ensuring document type directories exist...").dim());
     let mut created_count = 0;
 
     let organic_path = PathBuf::from("docuram/organic");
@@ -75,9 +151,9 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
     }
 
     if created_count > 0 {
-        println!("{}", style(format!("✓ Created {} director(ies)", created_count)).green());
+        crate::outln!("{}", style(format!("✓ Created {} director(ies)", created_count)).green());
     }
-    println!();
+    crate::outln!();
 
     // Check for new documents (not in docuram.json)
     let local_doc_uuids: HashSet<String> = docuram_config
@@ -92,42 +168,73 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
         .collect();
 
     // Check for documents deleted on server (in local but not in remote)
+    let layout = docuram_config.layout();
     let deleted_on_server: Vec<_> = docuram_config
         .all_documents()
-        .filter(|doc| !remote_doc_uuids.contains(&doc.uuid))
-        .map(|doc| (doc.uuid.clone(), doc.title.clone(), doc.local_path(&docuram_config.docuram.category_path)))
+        .filter(|doc| !doc.provisional && !remote_doc_uuids.contains(&doc.uuid))
+        .map(|doc| (doc.uuid.clone(), doc.title.clone(), doc.local_path(&layout)))
         .collect();
 
     if !deleted_on_server.is_empty() {
-        println!("{}", style(format!("🗑 {} document(s) deleted on server, removing locally:", deleted_on_server.len())).yellow());
+        crate::outln!("{}", style(format!("🗑 {} document(s) deleted on server, removing locally:", deleted_on_server.len())).yellow());
         for (uuid, title, local_path) in &deleted_on_server {
-            println!("  - {} ({})", title, uuid);
+            crate::item!("  - {} ({})", title, uuid);
             // Delete local file if exists
             let file_path = PathBuf::from(local_path);
             if file_path.exists() {
                 let _ = fs::remove_file(&file_path);
             }
-            // Remove from docuram.json
-            docuram_config.remove_document_by_uuid(uuid);
+            // Remove from docuram.json, remembering the path in case the file is left behind
+            docuram_config.forget_server_deleted(uuid);
         }
         // Save updated docuram config
         docuram_config.save()
             .context("Failed to save docuram.json after removing deleted documents")?;
-        println!();
+        crate::outln!();
+    }
+
+    // Follow documents the server moved: update their location and migrate the local file
+    let mut moved_count = 0;
+    for remote_doc in &remote_docs {
+        if let Some(doc) = docuram_config.get_document_by_uuid_mut(&remote_doc.uuid) {
+            if doc.path == remote_doc.path {
+                continue;
+            }
+
+            let old_local_path = doc.local_path(&layout);
+            doc.path = remote_doc.path.clone();
+            doc.category_id = remote_doc.category_id;
+            doc.category_name = remote_doc.category_name.clone();
+            doc.category_path = remote_doc.category_path.clone();
+            doc.category_uuid = remote_doc.category_uuid.clone();
+            let new_local_path = doc.local_path(&layout);
+
+            logger::debug("pull", &format!("Document {} moved on server: {} -> {}", doc.uuid, old_local_path, new_local_path));
+            reconcile_document_path(Path::new(&old_local_path), Path::new(&new_local_path))?;
+            moved_count += 1;
+        }
+    }
+
+    if moved_count > 0 {
+        docuram_config.save()
+            .context("Failed to save docuram.json after updating moved documents")?;
+        crate::outln!("{}", style(format!("✓ Updated location of {} moved document(s)", moved_count)).green());
+        crate::outln!();
     }
 
     let new_docs: Vec<_> = remote_docs
         .iter()
         .filter(|doc| !local_doc_uuids.contains(&doc.uuid))
         .collect();
+    let new_doc_uuids: HashSet<&str> = new_docs.iter().map(|doc| doc.uuid.as_str()).collect();
 
     if !new_docs.is_empty() {
-        println!();
-        println!("{}", style(format!("Found {} new document(s) from dependencies:", new_docs.len())).yellow());
+        crate::outln!();
+        crate::outln!("{}", style(format!("Found {} new document(s) from dependencies:", new_docs.len())).yellow());
         for doc in &new_docs {
-            println!("  + {}/{}", doc.category_path, doc.title);
+            crate::item!("  + {}/{}", doc.category_path, doc.title);
         }
-        println!();
+        crate::outln!();
 
         // Add new documents to docuram config
         for doc in &new_docs {
@@ -146,8 +253,13 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
                 is_required: doc.is_required,
                 // Local state fields - initially empty, will be set after download
                 local_checksum: None,
+                local_content_checksum: None,
                 last_sync: None,
                 pending_deletion: false,
+                provisional: false,
+                keep_local: false,
+                local_name: None,
+                extra: doc.extra.clone(),
             };
 
             // Add document to appropriate array based on is_required flag
@@ -161,40 +273,111 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
         // Save updated docuram config
         docuram_config.save()
             .context("Failed to save updated docuram.json")?;
-        println!("{}", style("Updated docuram.json with new documents").green());
-        println!();
+        crate::outln!("{}", style("Updated docuram.json with new documents").green());
+        crate::outln!();
     }
 
     // Determine which documents to pull
+    // Provisional documents (from 'add --register') do not exist on the server yet
+    // Documents marked with 'delete' are left for push to delete unless named explicitly
     let docs_to_pull: Vec<_> = if documents.is_empty() {
         // Pull all documents (including newly added ones)
-        docuram_config.all_documents().collect()
+        let pending_deletions = docuram_config.get_pending_deletions().len();
+        if pending_deletions > 0 {
+            crate::outln!("{}", style(format!("ⓘ {} document(s) marked for deletion skipped (run 'teamturbo push' to delete them)", pending_deletions)).dim());
+        }
+        docuram_config.all_documents()
+            .filter(|doc| !doc.provisional && !doc.pending_deletion)
+            .collect()
     } else {
         // Pull specific documents
         let doc_set: HashSet<String> = documents.into_iter().collect();
         docuram_config
             .all_documents()
-            .filter(|doc| doc_set.contains(&doc.uuid))
+            .filter(|doc| !doc.provisional && doc_set.contains(&doc.uuid))
             .collect()
     };
 
+    // Only the documents added above; existing ones are not re-checked
+    let docs_to_pull: Vec<_> = if only_new {
+        docs_to_pull
+            .into_iter()
+            .filter(|doc| new_doc_uuids.contains(doc.uuid.as_str()))
+            .collect()
+    } else {
+        docs_to_pull
+    };
+
+    // Restrict to the requested document types (all documents stay recorded in docuram.json)
+    let docs_to_pull: Vec<_> = if document_types.is_empty() {
+        docs_to_pull
+    } else {
+        let type_set: HashSet<String> = document_types.into_iter().collect();
+        docs_to_pull
+            .into_iter()
+            .filter(|doc| type_set.contains(&doc.doc_type))
+            .collect()
+    };
+
+    // Restrict to --include/--exclude globs over local paths
+    let docs_to_pull: Vec<_> = docs_to_pull
+        .into_iter()
+        .filter(|doc| filter.matches(&doc.local_path(&layout)))
+        .collect();
+
     if docs_to_pull.is_empty() {
-        println!("{}", style("No documents to pull").yellow());
+        if only_new {
+            crate::outln!("{}", style("No new documents to pull").yellow());
+            return Ok(());
+        }
+        if filter.is_active() {
+            crate::outln!("{}", style("No documents match --include/--exclude").yellow());
+            return Ok(());
+        }
+        crate::outln!("{}", style("No documents to pull").yellow());
         return Ok(());
     }
 
-    println!("Checking {} document(s)...", docs_to_pull.len());
-    println!();
+    // Guard against two documents mapping to the same local file before writing anything
+    let mut targets: HashMap<String, Vec<&str>> = HashMap::new();
+    for doc_info in &docs_to_pull {
+        targets.entry(doc_info.local_path(&layout))
+            .or_default()
+            .push(&doc_info.uuid);
+    }
+
+    let mut collisions: Vec<_> = targets.iter()
+        .filter(|(_, uuids)| uuids.len() > 1)
+        .collect();
+    if !collisions.is_empty() {
+        collisions.sort();
+        crate::outln!("{}", style(format!("✗ {} local path(s) claimed by more than one document:", collisions.len())).red());
+        for (path, uuids) in &collisions {
+            crate::outln!("  - {}", path);
+            for uuid in uuids.iter() {
+                crate::outln!("      {}", uuid);
+            }
+        }
+        crate::outln!();
+        anyhow::bail!("Refusing to pull: colliding document paths would overwrite each other. Fix the document paths on the server or in docuram.json.");
+    }
+
+    crate::outln!("Checking {} document(s)...", docs_to_pull.len());
+    crate::outln!();
 
     // Check which documents need updating
     let mut to_update = Vec::new();
     let mut to_skip = Vec::new();
     let mut conflicts = Vec::new();
+    let mut lease_rejected = Vec::new();
+    let mut kept_local = Vec::new();
+    let mut to_merge = HashSet::new();
+    let mut healed: Vec<(String, String, String)> = Vec::new();
 
     for doc_info in &docs_to_pull {
         // Use local_path() to get correct path (dependencies go in working_category/dependencies/ subdirectory)
-        let working_category_path = &docuram_config.docuram.category_path;
-        let local_file_path = doc_info.local_path(working_category_path);
+        let layout = docuram_config.layout();
+        let local_file_path = doc_info.local_path(&layout);
         let file_path = PathBuf::from(&local_file_path);
 
         if file_path.exists() {
@@ -205,67 +388,124 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
             let current_checksum = calculate_checksum(&current_content);
 
             // Check if local file has been modified since last sync
-            let is_modified = match &doc_info.local_checksum {
-                Some(local_cs) => current_checksum != *local_cs,
+            let mut is_modified = match &doc_info.local_checksum {
+                Some(local_cs) => !checksums_match(&current_checksum, local_cs),
                 None => true, // No local checksum, assume modified
             };
 
-            if is_modified && !force {
-                // Local modifications detected
-                conflicts.push(doc_info.uuid.clone());
-            } else {
-                // Check if remote has updates by comparing versions
-                let local_version = if doc_info.local_checksum.is_some() { doc_info.version } else { 0 };
-                let remote_version = remote_versions.get(&doc_info.uuid).copied().unwrap_or(doc_info.version);
+            // Content that only differs in line endings is not a real modification
+            if is_modified && heal {
+                if let Some(local_cs) = &doc_info.local_checksum {
+                    if matches_ignoring_line_endings(&current_content, local_cs) {
+                        healed.push((doc_info.uuid.clone(), current_checksum.clone(), calculate_content_checksum(&current_content)));
+                        is_modified = false;
+                    }
+                }
+            }
+
+            // Check if remote has updates by comparing versions
+            let local_version = if doc_info.local_checksum.is_some() { doc_info.version } else { 0 };
+            let remote_version = remote_versions.get(&doc_info.uuid).copied().unwrap_or(doc_info.version);
 
-                if remote_version > local_version {
-                    // Remote has newer version, needs update
-                    to_update.push(doc_info);
+            if is_modified && !force && force_with_lease {
+                // Lease holds only if the remote advanced past the last synced version
+                if remote_version > doc_info.version {
+                    to_update.push(doc_info.uuid.clone());
                 } else {
-                    // Local is up to date
-                    to_skip.push(doc_info.uuid.clone());
+                    lease_rejected.push(doc_info.uuid.clone());
                 }
+            } else if is_modified && !force {
+                // Local modifications detected; with a strategy only a newer remote is a conflict
+                match conflict_strategy {
+                    None => conflicts.push(doc_info.uuid.clone()),
+                    Some(ConflictStrategy::Ours) => kept_local.push(doc_info.uuid.clone()),
+                    Some(_) if remote_version <= local_version => kept_local.push(doc_info.uuid.clone()),
+                    Some(ConflictStrategy::Theirs) => to_update.push(doc_info.uuid.clone()),
+                    Some(ConflictStrategy::Manual | ConflictStrategy::Merge) => {
+                        to_merge.insert(doc_info.uuid.clone());
+                        to_update.push(doc_info.uuid.clone());
+                    }
+                }
+            } else if remote_version > local_version {
+                // Remote has newer version, needs update
+                to_update.push(doc_info.uuid.clone());
+            } else {
+                // Local is up to date
+                to_skip.push(doc_info.uuid.clone());
             }
         } else {
             // File doesn't exist, needs download
-            to_update.push(doc_info);
+            to_update.push(doc_info.uuid.clone());
         }
     }
 
+    // Store checksums of healed documents in their current encoding
+    if !healed.is_empty() {
+        for (uuid, checksum, content_checksum) in &healed {
+            if let Some(doc) = docuram_config.get_document_by_uuid_mut(uuid) {
+                doc.local_checksum = Some(checksum.clone());
+                doc.local_content_checksum = Some(content_checksum.clone());
+            }
+        }
+        docuram_config.save()
+            .context("Failed to save docuram.json after healing checksums")?;
+        crate::outln!("{}", style(format!("✓ Healed {} document(s) that differed only in line endings", healed.len())).green());
+    }
+
     // Report conflicts
     if !conflicts.is_empty() {
-        println!("{}", style(format!("⚠ {} document(s) have local modifications:", conflicts.len())).yellow());
+        crate::outln!("{}", style(format!("⚠ {} document(s) have local modifications:", conflicts.len())).yellow());
         for slug in &conflicts {
-            println!("  - {}", slug);
+            crate::outln!("  - {}", slug);
+        }
+        crate::outln!();
+        crate::outln!("{}", style("Use --merge to merge remote changes into them, or --force to overwrite local changes").dim());
+        crate::outln!();
+    }
+
+    // Report documents kept because the remote has not changed since last sync
+    if !lease_rejected.is_empty() {
+        crate::outln!("{}", style(format!("⚠ {} locally modified document(s) kept (remote unchanged since last sync):", lease_rejected.len())).yellow());
+        for uuid in &lease_rejected {
+            crate::outln!("  - {}", uuid);
+        }
+        crate::outln!();
+        crate::outln!("{}", style("Use 'teamturbo push' to upload them, or --force to discard local changes").dim());
+        crate::outln!();
+    }
+
+    // Report local modifications kept by the conflict strategy
+    if !kept_local.is_empty() {
+        crate::outln!("{}", style(format!("⚠ {} locally modified document(s) kept:", kept_local.len())).yellow());
+        for uuid in &kept_local {
+            crate::outln!("  - {}", uuid);
         }
-        println!();
-        println!("{}", style("Use --force to overwrite local changes").dim());
-        println!();
+        crate::outln!();
     }
 
     // Report skip
     if !to_skip.is_empty() {
-        println!("{}", style(format!("✓ {} document(s) already up to date", to_skip.len())).green());
+        crate::outln!("{}", style(format!("✓ {} document(s) already up to date", to_skip.len())).green());
     }
 
     // Pull updates
     if to_update.is_empty() {
-        println!();
-        println!("{}", style("All documents are up to date").green());
+        crate::outln!();
+        crate::outln!("{}", style("All documents are up to date").green());
 
         // Still check public dependencies even when local docs are up to date
-        println!();
+        crate::outln!();
         pull_public_dependencies(&mut docuram_config, force).await?;
 
         return Ok(());
     }
 
-    println!();
-    println!("{}", style(format!("Pulling {} document(s)...", to_update.len())).bold());
-    println!();
+    crate::outln!();
+    crate::outln!("{}", style(format!("Pulling {} document(s)...", to_update.len())).bold());
+    crate::outln!();
 
     // Create progress bar
-    let pb = ProgressBar::new(to_update.len() as u64);
+    let pb = logger::progress_bar(to_update.len() as u64);
     pb.set_style(
         ProgressStyle::default_bar()
             .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
@@ -274,32 +514,46 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
     );
 
     let mut success_count = 0;
+    let mut merged_clean = Vec::new();
+    let mut merged_with_conflicts = Vec::new();
     let mut failed_docs = Vec::new();
     let mut deleted_docs = Vec::new();
 
-    // Collect UUIDs to update
-    let uuids_to_update: Vec<String> = to_update.iter().map(|d| d.uuid.clone()).collect();
+    // Downloads run concurrently; docuram.json is updated here as each one completes
+    let uuids_to_update: Vec<String> = to_update;
+    let layout = docuram_config.layout();
+    let mut downloads = client.download_documents(&uuids_to_update);
 
-    for doc_uuid in &uuids_to_update {
+    while let Some((doc_uuid, result)) = downloads.next().await {
         // Get doc_info for progress message
         let title = docuram_config.get_document_by_uuid(doc_uuid)
             .map(|d| d.title.clone())
             .unwrap_or_default();
-        pb.set_message(format!("{}", title));
-
-        let working_category_path = docuram_config.docuram.category_path.clone();
-        match pull_document(&client, doc_uuid, &mut docuram_config, &working_category_path).await {
+        pb.set_message(title.clone());
+
+        let saved = match result {
+            Ok(doc) if to_merge.contains(doc_uuid) => {
+                let base = match conflict_strategy {
+                    Some(ConflictStrategy::Merge) => fetch_merge_base(&client, &docuram_config, doc_uuid).await,
+                    _ => None,
+                };
+                save_merged_document(&mut docuram_config, doc_uuid, doc, base.as_deref())
+                    .map(|(path, conflicted)| if conflicted { merged_with_conflicts.push(path) } else { merged_clean.push(path) })
+            }
+            Ok(doc) => save_downloaded_document(&mut docuram_config, doc_uuid, doc),
+            Err(e) => Err(e),
+        };
+        match saved {
             Ok(_) => {
                 success_count += 1;
             }
             Err(e) => {
-                let error_msg = e.to_string();
                 // Check if the error indicates document was deleted on server
-                if error_msg.contains("not found") || error_msg.contains("Not found") || error_msg.contains("404") {
+                if matches!(e.downcast_ref::<DownloadError>(), Some(DownloadError::NotFound { .. })) {
                     // Document was deleted on server, remove from local
                     let doc_info = docuram_config.get_document_by_uuid(doc_uuid);
                     if let Some(info) = doc_info {
-                        let local_path = info.local_path(&working_category_path);
+                        let local_path = info.local_path(&layout);
                         // Delete local file if exists
                         let file_path = PathBuf::from(&local_path);
                         if file_path.exists() {
@@ -307,10 +561,10 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
                         }
                     }
                     // Remove from docuram.json
-                    docuram_config.remove_document_by_uuid(doc_uuid);
-                    deleted_docs.push((doc_uuid.clone(), title.clone()));
+                    docuram_config.forget_server_deleted(doc_uuid);
+                    deleted_docs.push((doc_uuid.to_string(), title.clone()));
                 } else {
-                    failed_docs.push((doc_uuid.clone(), error_msg));
+                    failed_docs.push(FailedDownload::new(doc_uuid, &title, &e));
                 }
             }
         }
@@ -323,54 +577,212 @@ pub async fn execute(documents: Vec<String>, force: bool) -> Result<()> {
     // Save updated docuram config
     docuram_config.save()
         .context("Failed to save docuram.json")?;
+    save_failed_downloads("pull", &failed_docs)?;
 
-    println!();
+    crate::outln!();
     if !deleted_docs.is_empty() {
-        println!("{}", style(format!("🗑 Removed {} document(s) deleted from server:", deleted_docs.len())).yellow());
+        crate::outln!("{}", style(format!("🗑 Removed {} document(s) deleted from server:", deleted_docs.len())).yellow());
         for (uuid, title) in &deleted_docs {
-            println!("  - {} ({})", title, uuid);
+            crate::item!("  - {} ({})", title, uuid);
         }
     }
     if failed_docs.is_empty() && deleted_docs.is_empty() {
-        println!("{}", style(format!("✓ Successfully pulled {} documents", success_count)).green());
+        crate::outln!("{}", style(format!("✓ Successfully pulled {} documents", success_count)).green());
     } else if failed_docs.is_empty() {
-        println!("{}", style(format!("✓ Pulled {} documents", success_count)).green());
+        crate::outln!("{}", style(format!("✓ Pulled {} documents", success_count)).green());
     } else {
-        println!("{}", style(format!("✓ Pulled {} documents", success_count)).green());
-        println!("{}", style(format!("✗ Failed to pull {} documents:", failed_docs.len())).red());
-        for (slug, error) in failed_docs {
-            println!("  - {}: {}", slug, error);
+        crate::outln!("{}", style(format!("✓ Pulled {} documents", success_count)).green());
+        crate::outln!("{}", style(format!("✗ Failed to pull {} documents:", failed_docs.len())).red());
+        report_failed_downloads(&failed_docs);
+    }
+    crate::outln!("{}", style(transfer_summary("Pulled", success_count, client.bytes_transferred(), started.elapsed())).dim());
+
+    if !merged_clean.is_empty() {
+        merged_clean.sort();
+        crate::outln!();
+        crate::outln!("{}", style(format!("✓ Merged remote changes into {} locally modified document(s):", merged_clean.len())).green());
+        for path in &merged_clean {
+            crate::item!("  - {}", path);
         }
     }
+    if !merged_with_conflicts.is_empty() {
+        merged_with_conflicts.sort();
+        crate::outln!();
+        crate::outln!("{}", style(format!("⚠ {} document(s) merged with conflicts:", merged_with_conflicts.len())).yellow());
+        for path in &merged_with_conflicts {
+            crate::item!("  - {}", path);
+        }
+        crate::outln!("{}", style("Resolve the <<<<<<< local / >>>>>>> remote sections, then run 'teamturbo push'").dim());
+    }
 
     // Pull public dependencies updates
-    println!();
+    crate::outln!();
     pull_public_dependencies(&mut docuram_config, force).await?;
 
     Ok(())
 }
 
-/// Pull a single document
-async fn pull_document(
+/// Where the failures of the last pull/init are recorded
+pub const PULL_ERRORS_FILE: &str = ".docuram/pull-errors.json";
+
+/// A document that could not be downloaded, with the server's response when available
+#[derive(Debug, serde::Serialize)]
+pub struct FailedDownload {
+    pub uuid: String,
+    pub title: String,
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl FailedDownload {
+    pub fn new(uuid: &str, title: &str, error: &anyhow::Error) -> Self {
+        let download_error = error.downcast_ref::<DownloadError>();
+        Self {
+            uuid: uuid.to_string(),
+            title: title.to_string(),
+            error: error.to_string(),
+            status: download_error.map(|e| e.status()),
+            body: download_error.and_then(|e| e.body()).map(|b| b.to_string()),
+        }
+    }
+}
+
+/// Print download failures, including the HTTP status and response snippet
+pub fn report_failed_downloads(failed: &[FailedDownload]) {
+    for failure in failed {
+        crate::outln!("  - {}: {}", failure.uuid, failure.error);
+        if let Some(body) = &failure.body {
+            crate::outln!("      {}", style(body).dim());
+        }
+    }
+    if !failed.is_empty() {
+        crate::outln!("{}", style(format!("Details saved to {}", PULL_ERRORS_FILE)).dim());
+    }
+}
+
+/// Record download failures in .docuram/pull-errors.json (removed when there are none)
+pub fn save_failed_downloads(command: &str, failed: &[FailedDownload]) -> Result<()> {
+    let path = Path::new(PULL_ERRORS_FILE);
+
+    if failed.is_empty() {
+        if path.exists() {
+            fs::remove_file(path).context("Failed to remove stale pull-errors.json")?;
+        }
+        return Ok(());
+    }
+
+    let report = serde_json::json!({
+        "command": command,
+        "recorded_at": chrono::Utc::now().to_rfc3339(),
+        "failures": failed,
+    });
+    write_file(path, &serde_json::to_string_pretty(&report)?)
+        .context("Failed to write pull-errors.json")?;
+
+    Ok(())
+}
+
+/// Download documents into `output_dir`, mirroring their server category structure
+async fn export_documents(
     client: &ApiClient,
-    doc_uuid: &str,
-    docuram_config: &mut DocuramConfig,
-    working_category_path: &str,
+    remote_docs: &[crate::api::client::DocumentInfo],
+    documents: &[String],
+    document_types: &[String],
+    output_dir: &Path,
 ) -> Result<()> {
-    // Download document content
-    let doc = client.download_document(doc_uuid).await?;
+    let docs_to_export: Vec<_> = remote_docs
+        .iter()
+        .filter(|doc| documents.is_empty() || documents.contains(&doc.uuid))
+        .filter(|doc| document_types.is_empty() || document_types.contains(&doc.doc_type))
+        .collect();
+
+    if docs_to_export.is_empty() {
+        crate::outln!("{}", style("No documents to export").yellow());
+        return Ok(());
+    }
+
+    crate::outln!("{}", style(format!("Exporting {} document(s) to {}...", docs_to_export.len(), output_dir.display())).bold());
+    crate::outln!();
+
+    let pb = logger::progress_bar(docs_to_export.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+            .expect("Invalid progress bar template")
+            .progress_chars("=> ")
+    );
+
+    let mut success_count = 0;
+    let mut failed_docs = Vec::new();
+
+    // Unsafe paths are rejected before anything is downloaded
+    let mut targets = HashMap::new();
+    for doc in &docs_to_export {
+        // Server paths look like docuram/<category_path>/<file>; keep the category part
+        let relative_path = Path::new(doc.path.strip_prefix("docuram/").unwrap_or(&doc.path));
+        if relative_path.is_absolute() || relative_path.components().any(|c| c == std::path::Component::ParentDir) {
+            failed_docs.push((doc.uuid.clone(), format!("Unsafe document path: {}", doc.path)));
+            pb.inc(1);
+            continue;
+        }
+        targets.insert(doc.uuid.clone(), (doc.title.clone(), output_dir.join(relative_path)));
+    }
+
+    let uuids: Vec<String> = targets.keys().cloned().collect();
+    let mut downloads = client.download_documents(&uuids);
+    while let Some((uuid, result)) = downloads.next().await {
+        let (title, target) = &targets[uuid];
+        pb.set_message(title.clone());
+
+        let result = result.and_then(|downloaded| {
+            write_file(target, &downloaded.content.unwrap_or_default())
+                .with_context(|| format!("Failed to write document to {:?}", target))
+        });
 
+        match result {
+            Ok(_) => success_count += 1,
+            Err(e) => failed_docs.push((uuid.to_string(), e.to_string())),
+        }
+
+        pb.inc(1);
+    }
+
+    pb.finish_with_message("Done");
+
+    crate::outln!();
+    crate::outln!("{}", style(format!("✓ Exported {} documents to {}", success_count, output_dir.display())).green());
+    if !failed_docs.is_empty() {
+        crate::outln!("{}", style(format!("✗ Failed to export {} documents:", failed_docs.len())).red());
+        for (uuid, error) in failed_docs {
+            crate::outln!("  - {}: {}", uuid, error);
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a downloaded document to its local path and record the sync in docuram.json
+pub fn save_downloaded_document(
+    docuram_config: &mut DocuramConfig,
+    doc_uuid: &str,
+    doc: DocumentContent,
+) -> Result<()> {
     // Get pure content without frontmatter
     let content = doc.content.unwrap_or_default();
+    logger::debug("download", &format!("Document size: {} bytes", content.len()));
 
     // Get document info to calculate local path
     let doc_info = docuram_config.get_document_by_uuid(doc_uuid)
         .context("Document not found in config")?;
-    let local_file_path = doc_info.local_path(working_category_path);
+    let local_file_path = doc_info.local_path(&docuram_config.layout());
     let file_path = PathBuf::from(&local_file_path);
 
     write_file(&file_path, &content)
         .with_context(|| format!("Failed to write document to {:?}", file_path))?;
+    logger::debug("download", &format!("Saved to: {:?}", file_path));
 
     // Calculate checksum of content
     let content_checksum = crate::utils::calculate_checksum(&content);
@@ -378,6 +790,7 @@ async fn pull_document(
     // Update document's local state in docuram config
     if let Some(doc_mut) = docuram_config.get_document_by_uuid_mut(doc_uuid) {
         doc_mut.local_checksum = Some(content_checksum);
+        doc_mut.local_content_checksum = Some(calculate_content_checksum(&content));
         doc_mut.last_sync = Some(chrono::Utc::now().to_rfc3339());
         doc_mut.version = doc.version;
         doc_mut.pending_deletion = false;
@@ -386,9 +799,65 @@ async fn pull_document(
     Ok(())
 }
 
+/// Content of the version last synced, the base of a three-way merge
+/// None when the document was never synced or that version is no longer on the server.
+async fn fetch_merge_base(client: &ApiClient, docuram_config: &DocuramConfig, doc_uuid: &str) -> Option<String> {
+    let doc_info = docuram_config.get_document_by_uuid(doc_uuid)?;
+    doc_info.local_checksum.as_ref()?;
+    match client.download_document_version(doc_uuid, doc_info.version).await {
+        Ok(base) => base.content,
+        Err(e) => {
+            logger::debug("merge", &format!("No base version for {}: {}", doc_uuid, e));
+            None
+        }
+    }
+}
+
+/// Merge local edits with the downloaded content and record the remote version as synced
+/// With a `base` only overlapping edits get merge markers; without one every difference does.
+/// The file stays modified, so push uploads it (once any markers are resolved).
+/// Returns the local path and whether conflicts were written.
+fn save_merged_document(
+    docuram_config: &mut DocuramConfig,
+    doc_uuid: &str,
+    doc: DocumentContent,
+    base: Option<&str>,
+) -> Result<(String, bool)> {
+    let remote_content = doc.content.unwrap_or_default();
+    let doc_info = docuram_config.get_document_by_uuid(doc_uuid)
+        .context("Document not found in config")?;
+    let local_file_path = doc_info.local_path(&docuram_config.layout());
+    let file_path = PathBuf::from(&local_file_path);
+
+    let local_content = read_file(&file_path)?;
+    let (merged, conflicted) = match base {
+        Some(base) => {
+            let (merged, conflicts) = merge_three_way(base, &local_content, &remote_content);
+            (merged, conflicts > 0)
+        }
+        None => {
+            let merged = merge_with_markers(&local_content, &remote_content);
+            let conflicted = has_conflict_markers(&merged);
+            (merged, conflicted)
+        }
+    };
+    write_file(&file_path, &merged)
+        .with_context(|| format!("Failed to write document to {:?}", file_path))?;
+
+    if let Some(doc_mut) = docuram_config.get_document_by_uuid_mut(doc_uuid) {
+        doc_mut.local_checksum = Some(calculate_checksum(&remote_content));
+        doc_mut.local_content_checksum = Some(calculate_content_checksum(&remote_content));
+        doc_mut.last_sync = Some(chrono::Utc::now().to_rfc3339());
+        doc_mut.version = doc.version;
+        doc_mut.pending_deletion = false;
+    }
+
+    Ok((local_file_path, conflicted))
+}
+
 /// Pull public dependencies updates from docuram.teamturbo.io
 async fn pull_public_dependencies(docuram_config: &mut DocuramConfig, force: bool) -> Result<()> {
-    println!("{}", style("Checking public dependencies from Docuram Official...").bold());
+    crate::outln!("{}", style("Checking public dependencies from Docuram Official...").bold());
 
     let public_client = PublicApiClient::new(PublicApiClient::default_url().to_string());
 
@@ -396,13 +865,13 @@ async fn pull_public_dependencies(docuram_config: &mut DocuramConfig, force: boo
     let global_deps = match public_client.get_global_dependencies().await {
         Ok(deps) => deps,
         Err(e) => {
-            println!("{}", style(format!("⚠ Could not fetch public dependencies: {}", e)).yellow());
+            crate::outln!("{}", style(format!("⚠ Could not fetch public dependencies: {}", e)).yellow());
             return Ok(());
         }
     };
 
     if global_deps.global_dependencies.is_empty() {
-        println!("{}", style("  No public dependencies available").dim());
+        crate::outln!("{}", style("  No public dependencies available").dim());
         return Ok(());
     }
 
@@ -436,7 +905,7 @@ async fn pull_public_dependencies(docuram_config: &mut DocuramConfig, force: boo
         let download_result = match public_client.download_global_dependency(&dep_category.uuid).await {
             Ok(result) => result,
             Err(e) => {
-                println!("{}", style(format!("  ⚠ Failed to fetch {}: {}", dep_category.name, e)).yellow());
+                crate::outln!("{}", style(format!("  ⚠ Failed to fetch {}: {}", dep_category.name, e)).yellow());
                 continue;
             }
         };
@@ -487,10 +956,10 @@ async fn pull_public_dependencies(docuram_config: &mut DocuramConfig, force: boo
 
                 if is_new {
                     new_docs_count += 1;
-                    println!("  {} {} [PUBLIC]", style("+").green(), doc.title);
+                    crate::item!("  {} {} [PUBLIC]", style("+").green(), doc.title);
                 } else {
                     updated_docs_count += 1;
-                    println!("  {} {} [PUBLIC] (v{} → v{})",
+                    crate::item!("  {} {} [PUBLIC] (v{} → v{})",
                         style("↑").cyan(), doc.title,
                         existing_docs.get(&doc.uuid).map(|(_, _, v)| *v).unwrap_or(0),
                         doc.version);
@@ -510,8 +979,13 @@ async fn pull_public_dependencies(docuram_config: &mut DocuramConfig, force: boo
                     checksum: doc.checksum.clone(),
                     is_required: true,
                     local_checksum: Some(checksum),
+                    local_content_checksum: None,
                     last_sync: Some(chrono::Utc::now().to_rfc3339()),
                     pending_deletion: false,
+                    provisional: false,
+                    keep_local: false,
+                    local_name: None,
+                    extra: Default::default(),
                 });
                 category_updated = true;
             } else {
@@ -555,18 +1029,18 @@ async fn pull_public_dependencies(docuram_config: &mut DocuramConfig, force: boo
         docuram_config.save()
             .context("Failed to save docuram.json")?;
 
-        println!();
+        crate::outln!();
         if new_categories_count > 0 {
-            println!("{}", style(format!("✓ Added {} new public dependency categor(ies)", new_categories_count)).green());
+            crate::outln!("{}", style(format!("✓ Added {} new public dependency categor(ies)", new_categories_count)).green());
         }
         if new_docs_count > 0 {
-            println!("{}", style(format!("✓ Downloaded {} new public document(s)", new_docs_count)).green());
+            crate::outln!("{}", style(format!("✓ Downloaded {} new public document(s)", new_docs_count)).green());
         }
         if updated_docs_count > 0 {
-            println!("{}", style(format!("✓ Updated {} public document(s)", updated_docs_count)).green());
+            crate::outln!("{}", style(format!("✓ Updated {} public document(s)", updated_docs_count)).green());
         }
     } else {
-        println!("{}", style("✓ All public dependencies are up to date").green());
+        crate::outln!("{}", style("✓ All public dependencies are up to date").green());
     }
 
     Ok(())
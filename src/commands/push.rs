@@ -1,15 +1,17 @@
 use anyhow::{Context, Result};
 use console::style;
 use dialoguer::Input;
-use indicatif::{ProgressBar, ProgressStyle};
-use std::collections::HashSet;
+use indicatif::ProgressStyle;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::Instant;
 use walkdir::WalkDir;
 
 use crate::api::ApiClient;
 use crate::api::client::{DocumentUpdate, DocumentCreate};
-use crate::config::{CliConfig, DocuramConfig, DocumentInfo};
-use crate::utils::{read_file, calculate_checksum};
+use crate::commands::diff::has_conflict_markers;
+use crate::config::{CliConfig, DocuramConfig, DocumentDefaults, DocumentInfo};
+use crate::utils::{read_file, calculate_checksum, calculate_content_checksum, checksums_match, content_matches_checksum, default_front_matter, exceeds_size, format_size, is_ignored, sanitize_filename, select_documents, transfer_summary, logger, PathFilter, STANDARD_DIRS};
 
 /// Simple struct representing a new document (no frontmatter)
 struct NewDocument {
@@ -18,9 +20,14 @@ struct NewDocument {
     title: String,
 }
 
+/// Files skipped for size: (path, size in bytes)
+type OversizedFiles = Vec<(String, u64)>;
+
 /// Scan docuram/ directory for markdown files
-fn scan_markdown_files(dir: &str) -> Result<Vec<NewDocument>> {
+/// Files larger than `max_file_size` are not read; they are returned separately with their size
+fn scan_markdown_files(dir: &str, max_file_size: u64) -> Result<(Vec<NewDocument>, OversizedFiles)> {
     let mut documents = Vec::new();
+    let mut oversized = Vec::new();
 
     for entry in WalkDir::new(dir)
         .follow_links(true)
@@ -43,6 +50,15 @@ fn scan_markdown_files(dir: &str) -> Result<Vec<NewDocument>> {
             continue;
         }
 
+        if is_ignored(path) {
+            continue;
+        }
+
+        if let Some(size) = exceeds_size(path, max_file_size) {
+            oversized.push((path.to_string_lossy().to_string(), size));
+            continue;
+        }
+
         // Read file content
         let content = match std::fs::read_to_string(path) {
             Ok(c) => c,
@@ -62,17 +78,57 @@ fn scan_markdown_files(dir: &str) -> Result<Vec<NewDocument>> {
         });
     }
 
-    Ok(documents)
+    Ok((documents, oversized))
 }
 
-pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<()> {
-    println!("{}", style("Push Document Changes").cyan().bold());
-    println!();
+/// Options for `push`
+pub struct PushOptions {
+    /// File (or `-` for stdin) listing UUIDs or paths to push, one per line
+    pub document_list: Option<String>,
+    /// Change summary (prompted for when updating documents without one)
+    pub message: Option<String>,
+    /// Prompt for a change summary per modified document; blank falls back to `message`
+    pub per_document: bool,
+    /// Skip documents whose only change is in the front matter
+    pub content_only: bool,
+    /// Rename local files whose server title no longer matches the filename
+    pub rename_on_title_change: bool,
+    /// Skip (and report) files larger than this many bytes
+    pub max_file_size: u64,
+    /// Report what would be deleted, updated and created without calling the server or saving
+    pub dry_run: bool,
+    /// Only push documents whose local path passes `--include`/`--exclude`
+    pub filter: PathFilter,
+    /// Refuse to create new documents whose directory does not map to their category
+    pub strict: bool,
+}
+
+/// Push local changes to the server
+pub async fn execute(documents: Vec<String>, options: PushOptions) -> Result<()> {
+    crate::outln!("{}", style("Push Document Changes").cyan().bold());
+    crate::outln!();
+    let PushOptions { document_list, message, per_document, content_only, rename_on_title_change, max_file_size, dry_run, filter, strict } = options;
+    let started = Instant::now();
+
+    if dry_run {
+        crate::outln!("{}", style("Dry run: nothing will be sent to the server or saved").yellow());
+        crate::outln!();
+    }
 
     // Load docuram config with migration from state.json
     let mut docuram_config = DocuramConfig::load_with_migration()
         .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
 
+    // Files still at the locations recorded in the legacy state.json would be pushed as new documents
+    if !dry_run {
+        docuram_config.reconcile_state_file()?;
+    }
+
+    let Some(documents) = select_documents(&docuram_config, documents, document_list.as_deref())? else {
+        crate::outln!("{}", style("Document list is empty, nothing to push").yellow());
+        return Ok(());
+    };
+
     // Load CLI config
     let cli_config = CliConfig::load()?;
 
@@ -80,19 +136,67 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
 
     // Get auth for this server
     let auth = cli_config
-        .get_auth(&server_url)
+        .resolve_auth(&server_url)
         .context(format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
 
     // Create API client
-    let client = ApiClient::new(server_url.clone(), auth.access_token.clone());
+    let client = ApiClient::from_auth(server_url.clone(), &auth);
 
     // Auto-detect missing files and mark them as pending deletion
     let mut newly_marked_count = 0;
-    let working_category_path_for_check = docuram_config.docuram.category_path.clone();
+    let layout = docuram_config.layout();
+
+    // Provisional documents whose file was removed never reached the server; just forget them
+    let orig_len = docuram_config.documents.len();
+    docuram_config.documents.retain(|doc| {
+        !doc.provisional || std::path::Path::new(&doc.local_path(&layout)).exists()
+    });
+    let dropped_provisional = orig_len - docuram_config.documents.len();
+    if dropped_provisional > 0 {
+        crate::outln!("{}", style(format!("Removed {} unpushed registered document(s) whose file is missing", dropped_provisional)).yellow());
+        if !dry_run {
+            docuram_config.save()?;
+        }
+    }
+
+    // Files renamed on disk keep their server document instead of becoming delete + create
+    let mut renamed_titles: HashMap<String, String> = HashMap::new();
+    let has_missing = docuram_config.documents.iter().any(|doc| {
+        !doc.pending_deletion && !doc.provisional && doc.local_checksum.is_some()
+            && !std::path::Path::new(&doc.local_path(&layout)).exists()
+    });
+    if has_missing {
+        let (files, _) = scan_markdown_files("docuram", max_file_size)?;
+        let renames = detect_renames(&docuram_config, &files);
+        if !renames.is_empty() {
+            crate::outln!("{}", style(format!("Detected {} renamed file(s):", renames.len())).bold());
+            for (uuid, old_path, new_path) in &renames {
+                let doc = docuram_config.get_document_by_uuid_mut(uuid)
+                    .expect("rename candidates come from tracked documents");
+                let new_name = std::path::Path::new(new_path).file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or_default()
+                    .to_string();
+                doc.path = match doc.path.rsplit_once('/') {
+                    Some((dir, _)) => format!("{}/{}", dir, new_name),
+                    None => new_name.clone(),
+                };
+                doc.local_name = None;
+                doc.title = new_name.clone();
+                renamed_titles.insert(uuid.clone(), new_name);
+                crate::item!("  {} {} → {}", style("→").cyan(), old_path, new_path);
+            }
+            crate::outln!();
+            if !dry_run {
+                docuram_config.save()?;
+            }
+        }
+    }
+
     for doc in docuram_config.all_documents_mut() {
         if !doc.pending_deletion && doc.local_checksum.is_some() {
             // Get the correct local path for this document
-            let local_file_path = doc.local_path(&working_category_path_for_check);
+            let local_file_path = doc.local_path(&layout);
             let file_path = std::path::Path::new(&local_file_path);
             if !file_path.exists() {
                 doc.pending_deletion = true;
@@ -102,33 +206,62 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
     }
 
     if newly_marked_count > 0 {
-        println!("{}", style(format!("Detected {} missing file(s), marked for deletion", newly_marked_count)).yellow());
-        docuram_config.save()?;
+        crate::outln!("{}", style(format!("Detected {} missing file(s), marked for deletion", newly_marked_count)).yellow());
+        if !dry_run {
+            docuram_config.save()?;
+        }
     }
 
     // First, process documents marked for deletion
+    // Documents deleted with --server-only carry the local path to keep
     let pending_deletions: Vec<_> = docuram_config.get_pending_deletions()
         .into_iter()
-        .map(|d| (d.uuid.clone(), d.path.clone()))
+        .filter(|d| filter.matches(&d.local_path(&layout)))
+        .map(|d| (
+            d.uuid.clone(),
+            d.path.clone(),
+            d.keep_local.then(|| d.local_path(&layout)),
+        ))
         .collect();
 
-    if !pending_deletions.is_empty() {
-        println!("{}", style(format!("Processing {} document(s) marked for deletion...", pending_deletions.len())).cyan());
-        println!();
+    // Files unpublished in this run are not re-created as new documents below
+    let mut kept_local_paths: HashSet<String> = HashSet::new();
+
+    if dry_run && !pending_deletions.is_empty() {
+        crate::outln!("{}", style(format!("Would delete {} document(s) from server:", pending_deletions.len())).bold());
+        for (_, path, keep_local) in &pending_deletions {
+            match keep_local {
+                Some(local_path) if std::path::Path::new(local_path).exists() => {
+                    crate::item!("  - {} (kept locally)", path);
+                    kept_local_paths.insert(local_path.clone());
+                }
+                _ => crate::item!("  - {}", path),
+            }
+        }
+        crate::outln!();
+    } else if !pending_deletions.is_empty() {
+        crate::outln!("{}", style(format!("Processing {} document(s) marked for deletion...", pending_deletions.len())).cyan());
+        crate::outln!();
 
         let mut deleted_count = 0;
         let mut deleted_uuids = Vec::new();
         let mut failed_deletions = Vec::new();
 
-        for (uuid, path) in &pending_deletions {
+        for (uuid, path, keep_local) in &pending_deletions {
             match client.delete_document(uuid).await {
                 Ok(_) => {
-                    println!("  {} Deleted from server: {}", style("✓").green(), path);
+                    match keep_local {
+                        Some(local_path) if std::path::Path::new(local_path).exists() => {
+                            crate::item!("  {} Deleted from server (kept locally): {}", style("✓").green(), path);
+                            kept_local_paths.insert(local_path.clone());
+                        }
+                        _ => crate::item!("  {} Deleted from server: {}", style("✓").green(), path),
+                    }
                     deleted_uuids.push(uuid.clone());
                     deleted_count += 1;
                 }
                 Err(e) => {
-                    println!("  {} Failed to delete from server: {} - {}",
+                    crate::outln!("  {} Failed to delete from server: {} - {}",
                         style("✗").red(), path, e);
                     failed_deletions.push((uuid.clone(), e.to_string()));
                 }
@@ -137,95 +270,119 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
 
         // Remove deleted documents from docuram.json
         if !deleted_uuids.is_empty() {
-            for uuid in &deleted_uuids {
-                docuram_config.remove_document_by_uuid(uuid);
+            // Kept files stay as unpublished local documents, which push never creates again
+            for (uuid, _, keep_local) in pending_deletions.iter().filter(|(uuid, _, _)| deleted_uuids.contains(uuid)) {
+                match keep_local {
+                    Some(local_path) if kept_local_paths.contains(local_path) => {
+                        let content = read_file(std::path::Path::new(local_path))?;
+                        docuram_config.unpublish_document(uuid, local_path, &content);
+                    }
+                    _ => {
+                        docuram_config.remove_document_by_uuid(uuid);
+                    }
+                }
             }
             docuram_config.save()?;
         }
 
-        println!();
-        println!("{}", style(format!("✓ {} document(s) deleted from server", deleted_count)).green().bold());
+        crate::outln!();
+        crate::outln!("{}", style(format!("✓ {} document(s) deleted from server", deleted_count)).green().bold());
         if !failed_deletions.is_empty() {
-            println!("{}", style(format!("✗ {} deletion(s) failed", failed_deletions.len())).red());
+            crate::outln!("{}", style(format!("✗ {} deletion(s) failed", failed_deletions.len())).red());
         }
 
-        println!();
+        crate::outln!();
     }
 
     // Scan docuram directory for new documents (by comparing files vs JSON)
-    println!("{}", style("Scanning docuram/ directory for new documents...").cyan());
-    let all_md_files = match scan_markdown_files("docuram") {
-        Ok(docs) => docs,
+    crate::outln!("{}", style("Scanning docuram/ directory for new documents...").cyan());
+    let (all_md_files, mut oversized) = match scan_markdown_files("docuram", max_file_size) {
+        Ok(scan) => scan,
         Err(_) => {
-            println!("{}", style("No docuram/ directory found, skipping new document scan").yellow());
-            Vec::new()
+            crate::outln!("{}", style("No docuram/ directory found, skipping new document scan").yellow());
+            (Vec::new(), Vec::new())
         }
     };
 
-    // Get working category path for local_path() conversion
-    let working_category_path = &docuram_config.docuram.category_path;
-
-    // Build a set of LOCAL file paths from docuram.json for quick lookup
-    // Use local_path() to convert server paths to local file system paths
-    let docuram_paths: HashSet<String> = docuram_config
-        .all_documents()
-        .map(|d| d.local_path(working_category_path))
-        .collect();
+    // Layout for local_path() conversion
+    let layout = docuram_config.layout();
 
-    // Build a set of file paths from local_documents
-    let local_doc_paths: HashSet<String> = docuram_config
-        .local_documents
-        .iter()
-        .map(|d| d.path.clone())
+    // New documents are untracked files; provisional entries (from 'add --register') are still
+    // created, files unpublished with 'delete --server-only' or left behind by a server deletion
+    // are not. Files unpublished by a dry run are only known from this run.
+    let new_document_filter = docuram_config.new_document_filter();
+    let leftovers: Vec<String> = all_md_files.iter()
+        .filter(|d| filter.matches(&d.file_path) && new_document_filter.is_left_by_server_deletion(&d.file_path))
+        .map(|d| d.file_path.clone())
         .collect();
-
-    // Filter: new documents are those NOT in docuram.json AND NOT in local_documents (by path)
-    // Also exclude documents in dependencies/ directory (they are read-only)
     let new_docs: Vec<_> = all_md_files
         .into_iter()
         .filter(|d| {
-            // Exclude documents in dependencies/ directory (at project root)
-            if d.file_path.starts_with("dependencies/") {
-                return false;
-            }
-
-            // Check if file path is in docuram.json or local_documents
-            let in_docuram = docuram_paths.contains(&d.file_path);
-            let in_local = local_doc_paths.contains(&d.file_path);
-
-            // Document is new if not found by path
-            !in_docuram && !in_local
+            filter.matches(&d.file_path)
+                && new_document_filter.is_candidate(&d.file_path)
+                && !kept_local_paths.contains(&d.file_path)
         })
         .collect();
 
+    if !leftovers.is_empty() {
+        crate::outln!("{}", style(format!("ⓘ {} file(s) of documents deleted on the server not pushed (run 'teamturbo clean' to remove them):", leftovers.len())).dim());
+        for path in &leftovers {
+            crate::item!("  - {}", path);
+        }
+        crate::outln!();
+    }
+
     if !new_docs.is_empty() {
-        println!("{}", style(format!("Found {} new document(s):", new_docs.len())).bold());
+        crate::outln!("{}", style(format!("Found {} new document(s):", new_docs.len())).bold());
         for doc in &new_docs {
-            println!("  - {} ({})", doc.title, doc.file_path);
+            crate::item!("  - {} ({})", doc.title, doc.file_path);
+        }
+        crate::outln!();
+    }
+
+    // Only the organic/, impl/, req/ and manual/ subdirectories become categories;
+    // a file anywhere else is created in the working category instead
+    let misfiled: Vec<(&str, String, String)> = new_docs.iter()
+        .filter_map(|doc| {
+            let expected = directory_category_path(&doc.file_path, &docuram_config.docuram.category_path);
+            let actual = new_document_category_path(&doc.file_path, &docuram_config.docuram.category_path);
+            (expected != actual).then_some((doc.file_path.as_str(), expected, actual))
+        })
+        .collect();
+    if !misfiled.is_empty() {
+        crate::outln!("{}", style(format!("⚠ {} new document(s) will not be created in the category matching their directory:", misfiled.len())).yellow());
+        for (path, expected, actual) in &misfiled {
+            crate::outln!("  - {}: {} instead of {}", path, actual, expected);
+        }
+        crate::outln!();
+        if strict {
+            anyhow::bail!("Refusing to push misfiled documents (move them under organic/, impl/, req/ or manual/, or push without --strict)");
         }
-        println!();
     }
 
     // Determine which documents to push
     // Only push 'documents', not 'requires' (requires are read-only dependencies)
     let docs_to_check: Vec<_> = if documents.is_empty() {
         // Check all documents (only from 'documents', not 'requires')
-        docuram_config.documents.iter().collect()
+        docuram_config.documents.iter()
+            .filter(|doc| !doc.provisional && !doc.pending_deletion && filter.matches(&doc.local_path(&layout)))
+            .collect()
     } else {
         // Check specific documents
         let doc_set: HashSet<String> = documents.into_iter().collect();
         docuram_config
             .documents
             .iter()
-            .filter(|doc| doc_set.contains(&doc.uuid))
+            .filter(|doc| !doc.provisional && !doc.pending_deletion && doc_set.contains(&doc.uuid))
+            .filter(|doc| filter.matches(&doc.local_path(&layout)))
             .collect()
     };
 
     if !docs_to_check.is_empty() {
-        println!("Checking {} document(s) for changes...", docs_to_check.len());
-        println!();
+        crate::outln!("Checking {} document(s) for changes...", docs_to_check.len());
+        crate::outln!();
     } else if new_docs.is_empty() {
-        println!("{}", style("No documents to push").yellow());
+        crate::outln!("{}", style("No documents to push").yellow());
         return Ok(());
     }
 
@@ -233,12 +390,13 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
     // Store as (uuid, title, path, content, checksum)
     let mut to_push: Vec<(String, String, String, String, String)> = Vec::new();
     let mut missing_files = Vec::new();
+    let mut unresolved = Vec::new();
 
     // Check documents from docuram.json (only 'documents', not 'requires')
     for doc_info in &docs_to_check {
         // Use local_path() to get correct path (dependencies go in working_category/dependencies/ subdirectory)
-        let working_category_path = &docuram_config.docuram.category_path;
-        let local_file_path = doc_info.local_path(working_category_path);
+        let layout = docuram_config.layout();
+        let local_file_path = doc_info.local_path(&layout);
         let file_path = PathBuf::from(&local_file_path);
 
         if !file_path.exists() {
@@ -246,20 +404,31 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
             continue;
         }
 
+        // Oversized tracked files were already reported by the scan
+        if exceeds_size(&file_path, max_file_size).is_some() {
+            continue;
+        }
+
         // Read current content
         let current_content = read_file(&file_path)?;
         let current_checksum = calculate_checksum(&current_content);
 
         // Check if modified by comparing with local_checksum (from last sync)
-        let is_modified = match &doc_info.local_checksum {
-            Some(local_cs) => current_checksum != *local_cs,
+        let is_modified = match doc_info.differs_from_synced(&current_content, content_only) {
+            Some(modified) => modified,
             None => {
                 // No local checksum, compare with remote checksum
-                current_checksum != doc_info.checksum
+                !content_matches_checksum(&current_content, &doc_info.checksum)
             }
         };
 
-        if is_modified {
+        // Left by 'sync --strategy manual' until the user resolves them
+        if is_modified && has_conflict_markers(&current_content) {
+            unresolved.push(local_file_path);
+            continue;
+        }
+
+        if is_modified || renamed_titles.contains_key(&doc_info.uuid) {
             to_push.push((
                 doc_info.uuid.clone(),
                 doc_info.title.clone(),
@@ -270,35 +439,81 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
         }
     }
 
+    // Report files skipped for size
+    if !oversized.is_empty() {
+        oversized.sort();
+        crate::outln!("{}", style(format!("⚠ Skipped {} file(s) larger than {}:", oversized.len(), format_size(max_file_size))).yellow());
+        for (path, size) in &oversized {
+            crate::outln!("  - {} ({})", path, format_size(*size));
+        }
+        crate::outln!("{}", style("Use --max-file-size to raise the limit").dim());
+        crate::outln!();
+    }
+
+    // Report documents still holding merge markers
+    if !unresolved.is_empty() {
+        crate::outln!("{}", style(format!("⚠ Skipped {} document(s) with unresolved merge markers:", unresolved.len())).yellow());
+        for path in &unresolved {
+            crate::outln!("  - {}", path);
+        }
+        crate::outln!();
+    }
+
     // Report missing files
     if !missing_files.is_empty() {
-        println!("{}", style(format!("⚠ {} document(s) not found locally:", missing_files.len())).yellow());
+        crate::outln!("{}", style(format!("⚠ {} document(s) not found locally:", missing_files.len())).yellow());
         for uuid in &missing_files {
-            println!("  - {}", uuid);
+            crate::outln!("  - {}", uuid);
         }
-        println!();
+        crate::outln!();
     }
 
     // Check if there are changes to push or new documents to create
     if to_push.is_empty() && new_docs.is_empty() {
-        println!("{}", style("No changes to push").green());
+        crate::outln!("{}", style("No changes to push").green());
+        return Ok(());
+    }
+
+    if dry_run {
+        if !to_push.is_empty() {
+            crate::outln!("{}", style(format!("Would update {} document(s):", to_push.len())).bold());
+            for (uuid, title, path, _, _) in &to_push {
+                match renamed_titles.get(uuid) {
+                    Some(new_title) => crate::item!("  - {} ({}, renamed to {})", title, path, new_title),
+                    None => crate::item!("  - {} ({})", title, path),
+                }
+            }
+            crate::outln!();
+        }
+        if !new_docs.is_empty() {
+            crate::outln!("{}", style(format!("Would create {} document(s):", new_docs.len())).bold());
+            for new_doc in &new_docs {
+                let category_path = new_document_category_path(&new_doc.file_path, &docuram_config.docuram.category_path);
+                crate::item!("  - {} ({}) in {}", new_doc.title, new_doc.file_path, category_path);
+            }
+            crate::outln!();
+        }
+        crate::outln!("{}", style("Dry run complete, nothing was pushed").green());
         return Ok(());
     }
 
     // Process document updates if there are any
     let mut success_count = 0;
     let mut failed_docs = Vec::new();
+    let mut renamed_files: Vec<(String, String)> = Vec::new();
+    let mut rename_failures: Vec<(String, String)> = Vec::new();
 
     if !to_push.is_empty() {
-        println!("{}", style(format!("Found {} modified document(s):", to_push.len())).bold());
+        crate::outln!("{}", style(format!("Found {} modified document(s):", to_push.len())).bold());
         for (uuid, title, _, _, _) in &to_push {
-            println!("  - {} ({})", title, uuid);
+            crate::item!("  - {} ({})", title, uuid);
         }
-        println!();
+        crate::outln!();
 
         // Get change summary
         let change_summary = match message {
             Some(msg) => msg,
+            None if per_document => String::new(),
             None => {
                 Input::<String>::new()
                     .with_prompt("Change summary")
@@ -313,12 +528,30 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
             Some(change_summary)
         };
 
-        println!();
-        println!("{}", style(format!("Pushing {} document(s)...", to_push.len())).bold());
-        println!();
+        // One summary per document, in the order of to_push
+        let mut summaries = Vec::with_capacity(to_push.len());
+        for (_, _, path, _, _) in &to_push {
+            if !per_document {
+                summaries.push(change_summary.clone());
+                continue;
+            }
+            let prompt = match &change_summary {
+                Some(shared) => format!("Change summary for {} (blank: {})", path, shared),
+                None => format!("Change summary for {}", path),
+            };
+            let summary = Input::<String>::new()
+                .with_prompt(prompt)
+                .allow_empty(true)
+                .interact_text()?;
+            summaries.push(if summary.trim().is_empty() { change_summary.clone() } else { Some(summary) });
+        }
+
+        crate::outln!();
+        crate::outln!("{}", style(format!("Pushing {} document(s)...", to_push.len())).bold());
+        crate::outln!();
 
         // Create progress bar
-        let pb = ProgressBar::new(to_push.len() as u64);
+        let pb = logger::progress_bar(to_push.len() as u64);
         pb.set_style(
             ProgressStyle::default_bar()
                 .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
@@ -326,14 +559,15 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
                 .progress_chars("=> ")
         );
 
-        for (uuid, title, path, content, checksum) in to_push {
+        for ((uuid, title, path, content, checksum), change_summary) in to_push.into_iter().zip(summaries) {
             pb.set_message(format!("{}", title));
 
             // Push complete content including frontmatter
             // Backend will store it as-is, frontend will hide frontmatter during preview
             let update = DocumentUpdate {
                 content: content.clone(),
-                change_summary: change_summary.clone(),
+                change_summary,
+                title: renamed_titles.get(&uuid).cloned(),
             };
 
             match client.upload_document(&uuid, update).await {
@@ -341,8 +575,22 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
                     // Update document's local state in docuram config
                     if let Some(doc_mut) = docuram_config.get_document_by_uuid_mut(&uuid) {
                         doc_mut.local_checksum = Some(checksum.clone());
+                        doc_mut.local_content_checksum = Some(calculate_content_checksum(&content));
                         doc_mut.last_sync = Some(chrono::Utc::now().to_rfc3339());
                         doc_mut.version = updated_doc.version;
+
+                        // Keep the filename in line with the server title
+                        if rename_on_title_change {
+                            match rename_to_title(&path, &updated_doc.title) {
+                                Ok(Some((new_local_path, new_name))) => {
+                                    doc_mut.local_name = Some(new_name);
+                                    doc_mut.title = updated_doc.title.clone();
+                                    renamed_files.push((path.clone(), new_local_path));
+                                }
+                                Ok(None) => {}
+                                Err(e) => rename_failures.push((path.clone(), e.to_string())),
+                            }
+                        }
                     }
                     success_count += 1;
                 }
@@ -355,6 +603,21 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
         }
 
         pb.finish_with_message("Done");
+
+        if !renamed_files.is_empty() {
+            crate::outln!();
+            crate::outln!("{}", style(format!("Renamed {} file(s) to match their title:", renamed_files.len())).bold());
+            for (from, to) in &renamed_files {
+                crate::item!("  {} {} → {}", style("✓").green(), from, to);
+            }
+        }
+        if !rename_failures.is_empty() {
+            crate::outln!();
+            crate::outln!("{}", style(format!("⚠ {} file(s) not renamed:", rename_failures.len())).yellow());
+            for (path, error) in &rename_failures {
+                crate::outln!("  - {}: {}", path, error);
+            }
+        }
     }
 
     // Process new documents
@@ -362,11 +625,11 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
     let mut failed_new_docs = Vec::new();
 
     if !new_docs.is_empty() {
-        println!();
-        println!("{}", style(format!("Creating {} new document(s)...", new_docs.len())).bold());
-        println!();
+        crate::outln!();
+        crate::outln!("{}", style(format!("Creating {} new document(s)...", new_docs.len())).bold());
+        crate::outln!();
 
-        let pb_new = ProgressBar::new(new_docs.len() as u64);
+        let pb_new = logger::progress_bar(new_docs.len() as u64);
         pb_new.set_style(
             ProgressStyle::default_bar()
                 .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
@@ -374,38 +637,21 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
                 .progress_chars("=> ")
         );
 
+        // Assign idempotency keys and persist them before creating anything, so a create whose
+        // response was lost is recognized by the server when the push is run again
+        let idempotency_keys: std::collections::HashMap<String, String> = new_docs.iter()
+            .map(|d| (d.file_path.clone(), docuram_config.idempotency_key_for(&d.file_path, &d.content)))
+            .collect();
+        docuram_config.save()?;
+
         // Get working category path from docuram config
+        let layout = docuram_config.layout();
         let working_category_path = &docuram_config.docuram.category_path;
 
         for new_doc in new_docs {
             pb_new.set_message(format!("{}", new_doc.title));
 
-            // Infer correct category path based on file location
-            // If the file is in docuram/organic/, docuram/impl/, docuram/req/, or docuram/manual/,
-            // we need to ensure the category is set to <working_category>/<subdir> and preserve subdirectories
-            let category_path = if let Some(stripped) = new_doc.file_path.strip_prefix("docuram/") {
-                // Extract the directory path (without the filename)
-                let path = std::path::Path::new(stripped);
-                if let Some(parent) = path.parent() {
-                    let parent_str = parent.to_string_lossy();
-                    if parent_str.starts_with("organic") ||
-                       parent_str.starts_with("impl") ||
-                       parent_str.starts_with("req") ||
-                       parent_str.starts_with("manual") {
-                        // Standard docuram directory, prepend working category path
-                        format!("{}/{}", working_category_path, parent_str)
-                    } else {
-                        // Other directory, use working category path
-                        working_category_path.to_string()
-                    }
-                } else {
-                    // File at root of docuram/, use working category path
-                    working_category_path.to_string()
-                }
-            } else {
-                // Not in docuram/ directory, use working category path
-                working_category_path.to_string()
-            };
+            let category_path = new_document_category_path(&new_doc.file_path, working_category_path);
 
             // Get or create category by path
             let category_id = match client.get_category_by_path(&category_path).await {
@@ -431,18 +677,31 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
                 }
             };
 
+            // Resolve metadata: overrides recorded by add/import, then project defaults
+            let overrides = docuram_config.get_local_document_by_path(&new_doc.file_path)
+                .map(|d| DocumentDefaults { doc_type: d.doc_type.clone(), priority: d.priority })
+                .unwrap_or_default();
+            let front_matter = match default_front_matter(&category_path, &new_doc.title, &overrides) {
+                Ok(fm) => fm,
+                Err(e) => {
+                    failed_new_docs.push((new_doc.title.clone(), e.to_string()));
+                    pb_new.inc(1);
+                    continue;
+                }
+            };
+
             // Create document - push pure markdown content
             let doc_create = DocumentCreate {
                 category_id,
-                title: new_doc.title.clone(),
+                title: front_matter.title,
                 content: new_doc.content.clone(),
                 description: None,
-                doc_type: Some("knowledge".to_string()),
-                priority: Some(0),
+                doc_type: Some(front_matter.doc_type),
+                priority: Some(front_matter.priority),
                 is_required: None,
             };
 
-            match client.create_document(doc_create).await {
+            match client.create_document(doc_create, &idempotency_keys[&new_doc.file_path]).await {
                 Ok(created_doc) => {
                     // Calculate checksum for local state
                     let checksum = calculate_checksum(&new_doc.content);
@@ -472,10 +731,19 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
                         is_required: false,
                         // Local state fields
                         local_checksum: Some(checksum),
+                        local_content_checksum: Some(calculate_content_checksum(&new_doc.content)),
                         last_sync: Some(chrono::Utc::now().to_rfc3339()),
                         pending_deletion: false,
+                        provisional: false,
+                        keep_local: false,
+                        local_name: None,
+                        extra: Default::default(),
                     };
 
+                    // Replace the provisional entry (if registered by 'add --register') with the server's
+                    docuram_config.documents.retain(|d| {
+                        !(d.provisional && d.local_path(&layout) == new_doc.file_path)
+                    });
                     docuram_config.documents.push(new_doc_info);
 
                     // Remove from local_documents if it was there
@@ -499,23 +767,16 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
         .context("Failed to save docuram.json")?;
 
     // If we created new documents, update docuram.json from server
-    // But preserve local state fields (local_checksum, last_sync, pending_deletion)
+    // Only the working category is replaced; local state and merged categories are kept
     if created_count > 0 {
-        println!();
-        println!("{}", style("Updating docuram.json from server...").cyan());
-
-        // Save local state before fetching server config
-        // Map: uuid -> (local_checksum, last_sync, pending_deletion)
-        let local_state_backup: std::collections::HashMap<String, (Option<String>, Option<String>, bool)> =
-            docuram_config.all_documents()
-                .map(|d| (d.uuid.clone(), (d.local_checksum.clone(), d.last_sync.clone(), d.pending_deletion)))
-                .collect();
+        crate::outln!();
+        crate::outln!("{}", style("Updating docuram.json from server...").cyan());
 
         // Get category UUID from docuram config
         let category_uuid = match &docuram_config.docuram.category_uuid {
             Some(uuid) => uuid.clone(),
             None => {
-                println!("{}", style("Warning: No category UUID in docuram.json, skipping config update").yellow());
+                crate::outln!("{}", style("Warning: No category UUID in docuram.json, skipping config update").yellow());
                 String::new()
             }
         };
@@ -527,30 +788,22 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
 
             match client.get_docuram_config(&config_url).await {
                 Ok(updated_config) => {
-                    // Save server config first
-                    if let Err(e) = updated_config.save() {
-                        println!("{}", style(format!("Warning: Failed to save updated docuram.json: {}", e)).yellow());
+                    if let Err(e) = apply_server_config(&mut docuram_config, &updated_config) {
+                        crate::outln!("{}", style(format!("Warning: Failed to save updated docuram.json: {}", e)).yellow());
                     } else {
-                        // Reload and restore local state
-                        if let Ok(mut reloaded_config) = DocuramConfig::load() {
-                            restore_local_state(&mut reloaded_config, &local_state_backup);
-                            if let Err(e) = reloaded_config.save() {
-                                println!("{}", style(format!("Warning: Failed to save local state: {}", e)).yellow());
-                            }
-                        }
-                        println!("{}", style("✓ Updated docuram.json").green());
+                        crate::outln!("{}", style("✓ Updated docuram.json").green());
                     }
                 }
                 Err(e) => {
                     let error_msg = e.to_string();
                     if error_msg.contains("not found") || error_msg.contains("Not found") || error_msg.contains("404") {
                         // Category UUID is stale, try to refresh it using category path
-                        println!("{}", style("Category UUID is stale, attempting to refresh...").yellow());
+                        crate::outln!("{}", style("Category UUID is stale, attempting to refresh...").yellow());
 
                         let category_path = &docuram_config.docuram.category_path;
                         match client.get_category_uuid_by_path(category_path).await {
                             Ok(Some(new_uuid)) => {
-                                println!("{}", style(format!("Found new UUID for category: {}", category_path)).dim());
+                                crate::outln!("{}", style(format!("Found new UUID for category: {}", category_path)).dim());
 
                                 // Retry with the new UUID
                                 let new_config_url = format!("{}/api/docuram/categories/{}/generate_config",
@@ -558,82 +811,228 @@ pub async fn execute(documents: Vec<String>, message: Option<String>) -> Result<
 
                                 match client.get_docuram_config(&new_config_url).await {
                                     Ok(updated_config) => {
-                                        // Save server config first
-                                        if let Err(e) = updated_config.save() {
-                                            println!("{}", style(format!("Warning: Failed to save updated docuram.json: {}", e)).yellow());
+                                        if let Err(e) = apply_server_config(&mut docuram_config, &updated_config) {
+                                            crate::outln!("{}", style(format!("Warning: Failed to save updated docuram.json: {}", e)).yellow());
                                         } else {
-                                            // Reload and restore local state
-                                            if let Ok(mut reloaded_config) = DocuramConfig::load() {
-                                                restore_local_state(&mut reloaded_config, &local_state_backup);
-                                                if let Err(e) = reloaded_config.save() {
-                                                    println!("{}", style(format!("Warning: Failed to save local state: {}", e)).yellow());
-                                                }
-                                            }
-                                            println!("{}", style("✓ Updated docuram.json with refreshed category UUID").green());
+                                            crate::outln!("{}", style("✓ Updated docuram.json with refreshed category UUID").green());
                                         }
                                     }
                                     Err(e) => {
-                                        println!("{}", style(format!("Warning: Failed to fetch config with new UUID: {}", e)).yellow());
-                                        println!("{}", style("  Run 'teamturbo init' to re-initialize.").dim());
+                                        crate::outln!("{}", style(format!("Warning: Failed to fetch config with new UUID: {}", e)).yellow());
+                                        crate::outln!("{}", style("  Run 'teamturbo init' to re-initialize.").dim());
                                     }
                                 }
                             }
                             Ok(None) => {
-                                println!("{}", style(format!("Category '{}' not found on server. Please run 'teamturbo init' to re-initialize.", category_path)).yellow());
+                                crate::outln!("{}", style(format!("Category '{}' not found on server. Please run 'teamturbo init' to re-initialize.", category_path)).yellow());
                             }
                             Err(e) => {
-                                println!("{}", style(format!("Failed to lookup category UUID: {}", e)).yellow());
-                                println!("{}", style("  Run 'teamturbo init' to re-initialize.").dim());
+                                crate::outln!("{}", style(format!("Failed to lookup category UUID: {}", e)).yellow());
+                                crate::outln!("{}", style("  Run 'teamturbo init' to re-initialize.").dim());
                             }
                         }
                     } else {
-                        println!("{}", style(format!("Warning: Failed to fetch updated config: {}", e)).yellow());
-                        println!("{}", style("  Run 'teamturbo pull --config' to manually update docuram.json").dim());
+                        crate::outln!("{}", style(format!("Warning: Failed to fetch updated config: {}", e)).yellow());
+                        crate::outln!("{}", style("  Run 'teamturbo pull --config' to manually update docuram.json").dim());
                     }
                 }
             }
         }
     }
 
-    println!();
+    crate::outln!();
 
     // Report results
     if failed_docs.is_empty() && created_count == 0 {
-        println!("{}", style(format!("✓ Successfully pushed {} document(s)", success_count)).green());
+        crate::outln!("{}", style(format!("✓ Successfully pushed {} document(s)", success_count)).green());
     } else {
         if success_count > 0 {
-            println!("{}", style(format!("✓ Updated {} document(s)", success_count)).green());
+            crate::outln!("{}", style(format!("✓ Updated {} document(s)", success_count)).green());
         }
         if created_count > 0 {
-            println!("{}", style(format!("✓ Created {} new document(s)", created_count)).green());
+            crate::outln!("{}", style(format!("✓ Created {} new document(s)", created_count)).green());
         }
         if !failed_docs.is_empty() {
-            println!("{}", style(format!("✗ Failed to update {} document(s):", failed_docs.len())).red());
+            crate::outln!("{}", style(format!("✗ Failed to update {} document(s):", failed_docs.len())).red());
             for (uuid, error) in failed_docs {
-                println!("  - {}: {}", uuid, error);
+                crate::outln!("  - {}: {}", uuid, error);
             }
         }
         if !failed_new_docs.is_empty() {
-            println!("{}", style(format!("✗ Failed to create {} document(s):", failed_new_docs.len())).red());
+            crate::outln!("{}", style(format!("✗ Failed to create {} document(s):", failed_new_docs.len())).red());
             for (title, error) in failed_new_docs {
-                println!("  - {}: {}", title, error);
+                crate::outln!("  - {}: {}", title, error);
             }
         }
     }
+    crate::outln!("{}", style(transfer_summary("Pushed", success_count + created_count, client.bytes_transferred(), started.elapsed())).dim());
 
     Ok(())
 }
 
-/// Restore local state fields to a config after server update
-fn restore_local_state(
-    config: &mut DocuramConfig,
-    backup: &std::collections::HashMap<String, (Option<String>, Option<String>, bool)>,
-) {
-    for doc in config.all_documents_mut() {
-        if let Some((local_checksum, last_sync, pending_deletion)) = backup.get(&doc.uuid) {
-            doc.local_checksum = local_checksum.clone();
-            doc.last_sync = last_sync.clone();
-            doc.pending_deletion = *pending_deletion;
+/// Update docuram.json with the working category's config the server generated after push
+fn apply_server_config(docuram_config: &mut DocuramConfig, server_config: &crate::api::client::DocuramConfig) -> Result<()> {
+    let server_config: DocuramConfig = serde_json::to_value(server_config)
+        .and_then(serde_json::from_value)
+        .context("Failed to read the updated configuration")?;
+    docuram_config.apply_server_config(server_config);
+    docuram_config.save()
+}
+
+/// Category a new document is created in, inferred from its location
+/// Files in docuram/organic/, docuram/impl/, docuram/req/ or docuram/manual/ go to
+/// <working_category>/<subdir> (keeping deeper subdirectories); everything else goes to the working category.
+fn new_document_category_path(file_path: &str, working_category_path: &str) -> String {
+    let Some(stripped) = file_path.strip_prefix("docuram/") else {
+        return working_category_path.to_string();
+    };
+    match std::path::Path::new(stripped).parent() {
+        Some(parent) => {
+            let parent_str = parent.to_string_lossy();
+            if STANDARD_DIRS.iter().any(|dir| parent_str.starts_with(dir)) {
+                format!("{}/{}", working_category_path, parent_str)
+            } else {
+                working_category_path.to_string()
+            }
+        }
+        None => working_category_path.to_string(),
+    }
+}
+
+/// Category matching a new document's directory under docuram/, whether or not push files it there
+fn directory_category_path(file_path: &str, working_category_path: &str) -> String {
+    let parent = file_path.strip_prefix("docuram/")
+        .and_then(|stripped| std::path::Path::new(stripped).parent())
+        .map(|parent| parent.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if parent.is_empty() {
+        working_category_path.to_string()
+    } else {
+        format!("{}/{}", working_category_path, parent)
+    }
+}
+
+/// Filename for a document title (server titles carry the .md extension)
+fn title_filename(title: &str) -> String {
+    let name = sanitize_filename(title.trim());
+    if name.ends_with(".md") {
+        name
+    } else {
+        format!("{}.md", name)
+    }
+}
+
+/// Rename a local file to match the document title
+/// Returns (new local path, new file name), or None when the name already matches.
+/// An existing file at the target name is never overwritten.
+fn rename_to_title(local_path: &str, title: &str) -> Result<Option<(String, String)>> {
+    let new_name = title_filename(title);
+    if new_name == ".md" {
+        anyhow::bail!("Title is empty");
+    }
+
+    let current = std::path::Path::new(local_path);
+    if current.file_name().and_then(|n| n.to_str()) == Some(new_name.as_str()) {
+        return Ok(None);
+    }
+
+    let target = current.with_file_name(&new_name);
+    if target.exists() {
+        anyhow::bail!("{} already exists", target.display());
+    }
+
+    std::fs::rename(current, &target)
+        .with_context(|| format!("Failed to rename {} to {}", local_path, target.display()))?;
+
+    Ok(Some((target.to_string_lossy().to_string(), new_name)))
+}
+
+/// A tracked document found under a new name: (uuid, old local path, new local path)
+type DetectedRename = (String, String, String);
+
+/// Match documents missing at their recorded path to untracked files with identical content
+/// Like git's rename detection, but only within the same directory since directories are
+/// categories on the server. Content shared by several files or documents is ambiguous and skipped.
+fn detect_renames(docuram_config: &DocuramConfig, files: &[NewDocument]) -> Vec<DetectedRename> {
+    let layout = docuram_config.layout();
+    let tracked: HashSet<String> = docuram_config.all_documents()
+        .map(|d| d.local_path(&layout))
+        .collect();
+    let untracked: Vec<&NewDocument> = files.iter()
+        .filter(|f| !tracked.contains(&f.file_path))
+        .collect();
+
+    let missing: Vec<(&DocumentInfo, String)> = docuram_config.documents.iter()
+        .filter(|d| !d.pending_deletion && !d.provisional)
+        .filter_map(|d| {
+            let local_path = d.local_path(&layout);
+            let is_missing = d.local_checksum.is_some() && !std::path::Path::new(&local_path).exists();
+            is_missing.then_some((d, local_path))
+        })
+        .collect();
+
+    let parent = |path: &str| std::path::Path::new(path).parent().map(|p| p.to_path_buf());
+    let mut renames = Vec::new();
+
+    for (doc, old_path) in &missing {
+        let local_checksum = doc.local_checksum.as_deref().unwrap_or_default();
+        let same_content = |content: &str| checksums_match(&calculate_checksum(content), local_checksum);
+
+        let candidates: Vec<&&NewDocument> = untracked.iter()
+            .filter(|f| parent(&f.file_path) == parent(old_path) && same_content(&f.content))
+            .collect();
+        let rivals = missing.iter()
+            .filter(|(other, other_path)| parent(other_path) == parent(old_path)
+                && other.local_checksum.as_deref().map(|cs| checksums_match(cs, local_checksum)).unwrap_or(false))
+            .count();
+
+        if let [file] = candidates.as_slice() {
+            if rivals == 1 {
+                renames.push((doc.uuid.clone(), old_path.clone(), file.file_path.clone()));
+            }
         }
     }
+
+    renames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_to_title_skips_collisions() {
+        let root = std::env::temp_dir().join(format!("teamturbo-rename-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let old = root.join("draft.md");
+        std::fs::write(&old, "a").unwrap();
+        std::fs::write(root.join("taken.md"), "b").unwrap();
+        let old_path = old.to_string_lossy().to_string();
+
+        assert!(rename_to_title(&old_path, "taken.md").is_err());
+        assert!(rename_to_title(&old_path, "draft.md").unwrap().is_none());
+
+        let (new_local, new_name) = rename_to_title(&old_path, "Design: v2").unwrap().unwrap();
+        assert_eq!(new_name, "Design_ v2.md");
+        assert!(std::path::Path::new(&new_local).exists());
+        assert!(!old.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_document_category_path() {
+        assert_eq!(new_document_category_path("docuram/impl/feat/b.md", "Cat"), "Cat/impl/feat");
+        assert_eq!(new_document_category_path("docuram/notes/b.md", "Cat"), "Cat");
+        assert_eq!(new_document_category_path("docuram/b.md", "Cat"), "Cat");
+        assert_eq!(new_document_category_path("other/b.md", "Cat"), "Cat");
+    }
+
+    #[test]
+    fn test_directory_category_path() {
+        assert_eq!(directory_category_path("docuram/impl/feat/b.md", "Cat"), "Cat/impl/feat");
+        assert_eq!(directory_category_path("docuram/notes/b.md", "Cat"), "Cat/notes");
+        assert_eq!(directory_category_path("docuram/b.md", "Cat"), "Cat");
+        assert_eq!(directory_category_path("other/b.md", "Cat"), "Cat");
+    }
 }
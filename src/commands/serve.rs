@@ -0,0 +1,423 @@
+use anyhow::{Context, Result};
+use console::style;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use walkdir::WalkDir;
+
+use crate::config::DocuramConfig;
+use crate::utils::{extract_front_matter, markdown_change_stamp, logger};
+
+/// Default port for `teamturbo serve`
+pub const DEFAULT_PORT: u16 = 4000;
+
+/// Largest request head read before the connection is dropped
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Endpoint polled by pages to detect changes on disk
+const STAMP_PATH: &str = "/__stamp";
+
+/// How often pages check for changes, in milliseconds
+const RELOAD_INTERVAL_MS: u64 = 1000;
+
+/// Serve a local HTML preview of the documents under docuram/
+/// Pages reload automatically when files change. `no_open` skips opening the browser.
+pub async fn execute(port: u16, no_open: bool) -> Result<()> {
+    let docuram_config = DocuramConfig::load()
+        .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+
+    let docuram_dir = PathBuf::from("docuram");
+    if !docuram_dir.is_dir() {
+        anyhow::bail!("docuram directory not found. Run 'teamturbo init' first.");
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).await
+        .with_context(|| format!("Failed to listen on port {}", port))?;
+    let url = format!("http://127.0.0.1:{}/", port);
+
+    crate::outln!("{}", style("Document Preview").cyan().bold());
+    crate::outln!();
+    crate::outln!("Serving {} at {}", style(&docuram_config.project.name).bold(), style(&url).cyan());
+    crate::outln!("{}", style("Press Ctrl+C to stop").dim());
+
+    if !no_open {
+        if let Err(e) = webbrowser::open(&url) {
+            crate::outln!("{} Could not open browser: {}", style("⚠").yellow(), e);
+        }
+    }
+
+    let title = docuram_config.project.name.clone();
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let title = title.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &title).await {
+                logger::debug("serve", &format!("Request failed: {}", e));
+            }
+        });
+    }
+}
+
+/// Answer a single HTTP request
+async fn handle_connection(mut stream: TcpStream, title: &str) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 || buf.len() + n > MAX_REQUEST_BYTES {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut parts = head.lines().next().unwrap_or("").split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+    let path = percent_decode(target.split('?').next().unwrap_or("/"));
+    logger::debug("serve", &format!("{} {}", method, path));
+
+    let docuram_dir = Path::new("docuram");
+    let (status, content_type, body) = if method != "GET" {
+        ("405 Method Not Allowed", "text/plain", "Method not allowed".to_string())
+    } else if path == STAMP_PATH {
+        ("200 OK", "text/plain", markdown_change_stamp(docuram_dir))
+    } else if path == "/" {
+        let nav = render_nav(docuram_dir, None);
+        let main = "<p>Select a document.</p>".to_string();
+        ("200 OK", "text/html; charset=utf-8", render_page(title, &nav, &main))
+    } else if let Some(doc) = path.strip_prefix("/doc/").and_then(safe_relative_path) {
+        match fs::read_to_string(docuram_dir.join(&doc)) {
+            Ok(content) => {
+                let nav = render_nav(docuram_dir, Some(&doc));
+                let main = render_markdown(extract_front_matter(&content).1);
+                ("200 OK", "text/html; charset=utf-8", render_page(title, &nav, &main))
+            }
+            Err(_) => ("404 Not Found", "text/plain", "Document not found".to_string()),
+        }
+    } else {
+        ("404 Not Found", "text/plain", "Not found".to_string())
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        status, content_type, body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Markdown files under docuram/, relative to it (hidden files skipped like push)
+fn document_paths(docuram_dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = WalkDir::new(docuram_dir)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().and_then(|x| x.to_str()) == Some("md"))
+        .filter_map(|e| e.path().strip_prefix(docuram_dir).ok().map(Path::to_path_buf))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Only accept plain relative paths so requests cannot escape docuram/
+fn safe_relative_path(path: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(path);
+    let is_plain = path.components().all(|c| matches!(c, Component::Normal(_)));
+    (is_plain && path.components().next().is_some()).then_some(path)
+}
+
+/// Category navigation: documents grouped by their directory
+fn render_nav(docuram_dir: &Path, current: Option<&Path>) -> String {
+    let mut groups: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for path in document_paths(docuram_dir) {
+        let dir = path.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+        groups.entry(dir).or_default().push(path);
+    }
+
+    let mut html = String::new();
+    for (dir, docs) in groups {
+        let heading = if dir.is_empty() { "docuram" } else { &dir };
+        html.push_str(&format!("<h3>{}</h3>\n<ul>\n", escape_html(heading)));
+        for doc in docs {
+            let name = doc.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let class = if current == Some(doc.as_path()) { " class=\"current\"" } else { "" };
+            html.push_str(&format!("<li><a href=\"/doc/{}\"{}>{}</a></li>\n",
+                percent_encode(&doc.to_string_lossy()), class, escape_html(&name)));
+        }
+        html.push_str("</ul>\n");
+    }
+    html
+}
+
+fn render_page(title: &str, nav: &str, main: &str) -> String {
+    format!(r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ margin: 0; display: flex; font-family: -apple-system, "Segoe UI", sans-serif; line-height: 1.6; }}
+nav {{ width: 280px; padding: 1em; background: #f6f8fa; height: 100vh; overflow-y: auto; box-sizing: border-box; position: sticky; top: 0; }}
+nav h3 {{ font-size: 0.85em; color: #57606a; margin-bottom: 0.2em; }}
+nav ul {{ list-style: none; padding-left: 0.5em; margin-top: 0; }}
+nav a {{ color: #0969da; text-decoration: none; }}
+nav a.current {{ font-weight: bold; }}
+main {{ flex: 1; padding: 1em 2em; max-width: 900px; }}
+pre {{ background: #f6f8fa; padding: 1em; overflow-x: auto; }}
+code {{ background: #f6f8fa; padding: 0.1em 0.3em; }}
+blockquote {{ border-left: 4px solid #d0d7de; margin-left: 0; padding-left: 1em; color: #57606a; }}
+</style>
+</head>
+<body>
+<nav>{nav}</nav>
+<main>{main}</main>
+<script>
+let stamp = null;
+setInterval(async () => {{
+  try {{
+    const current = await (await fetch("{stamp_path}")).text();
+    if (stamp !== null && current !== stamp) location.reload();
+    stamp = current;
+  }} catch (e) {{}}
+}}, {interval});
+</script>
+</body>
+</html>
+"#,
+        title = escape_html(title),
+        nav = nav,
+        main = main,
+        stamp_path = STAMP_PATH,
+        interval = RELOAD_INTERVAL_MS,
+    )
+}
+
+/// Render the common subset of markdown used in documents to HTML
+/// Covers headings, paragraphs, lists, block quotes, rules, fenced code and inline
+/// code/emphasis/links. Anything else is shown as escaped text.
+fn render_markdown(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list: Option<&str> = None;
+    let mut code_block: Option<Vec<&str>> = None;
+
+    let flush_paragraph = |html: &mut String, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", render_inline(&paragraph.join("\n"))));
+            paragraph.clear();
+        }
+    };
+    let close_list = |html: &mut String, list: &mut Option<&str>| {
+        if let Some(tag) = list.take() {
+            html.push_str(&format!("</{}>\n", tag));
+        }
+    };
+
+    for line in markdown.lines() {
+        if let Some(code) = code_block.as_mut() {
+            if line.trim_start().starts_with("```") {
+                html.push_str(&format!("<pre><code>{}</code></pre>\n", escape_html(&code.join("\n"))));
+                code_block = None;
+            } else {
+                code.push(line);
+            }
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut list);
+            code_block = Some(Vec::new());
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut list);
+        } else if let Some(level) = heading_level(trimmed) {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut list);
+            let text = trimmed[level..].trim().trim_end_matches('#').trim();
+            html.push_str(&format!("<h{0}>{1}</h{0}>\n", level, render_inline(text)));
+        } else if ["---", "***", "___"].contains(&trimmed) {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut list);
+            html.push_str("<hr>\n");
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut list);
+            html.push_str(&format!("<blockquote>{}</blockquote>\n", render_inline(quote.trim())));
+        } else if let Some((tag, item)) = list_item(trimmed) {
+            flush_paragraph(&mut html, &mut paragraph);
+            if list != Some(tag) {
+                close_list(&mut html, &mut list);
+                html.push_str(&format!("<{}>\n", tag));
+                list = Some(tag);
+            }
+            html.push_str(&format!("<li>{}</li>\n", render_inline(item)));
+        } else {
+            close_list(&mut html, &mut list);
+            paragraph.push(trimmed);
+        }
+    }
+
+    if let Some(code) = code_block {
+        html.push_str(&format!("<pre><code>{}</code></pre>\n", escape_html(&code.join("\n"))));
+    }
+    flush_paragraph(&mut html, &mut paragraph);
+    close_list(&mut html, &mut list);
+    html
+}
+
+/// Heading level for an ATX heading line (`# Title`)
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let followed_by_space = line[level..].starts_with(' ') || line.len() == level;
+    ((1..=6).contains(&level) && followed_by_space).then_some(level)
+}
+
+/// List tag and item text for `- item`, `* item`, `+ item` or `1. item`
+fn list_item(line: &str) -> Option<(&'static str, &str)> {
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(marker) {
+            return Some(("ul", item));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(item) = line[digits..].strip_prefix(". ") {
+            return Some(("ol", item));
+        }
+    }
+    None
+}
+
+/// Inline markdown: `code`, **bold**, *italic* and [links](url)
+fn render_inline(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+
+    while let Some(pos) = rest.find(['`', '*', '[']) {
+        html.push_str(&escape_html(&rest[..pos]));
+        rest = &rest[pos..];
+
+        let (rendered, consumed) = if let Some(end) = rest[1..].find('`').filter(|_| rest.starts_with('`')) {
+            (format!("<code>{}</code>", escape_html(&rest[1..end + 1])), end + 2)
+        } else if let Some(end) = rest.strip_prefix("**").and_then(|r| r.find("**")) {
+            (format!("<strong>{}</strong>", render_inline(&rest[2..end + 2])), end + 4)
+        } else if let Some(end) = rest.strip_prefix('*').and_then(|r| r.find('*')).filter(|e| *e > 0) {
+            (format!("<em>{}</em>", render_inline(&rest[1..end + 1])), end + 2)
+        } else if let Some((label, url, len)) = parse_link(rest) {
+            if is_safe_link(url) {
+                (format!("<a href=\"{}\">{}</a>", escape_html(url), render_inline(label)), len)
+            } else {
+                (render_inline(label), len)
+            }
+        } else {
+            (escape_html(&rest[..1]), 1)
+        };
+
+        html.push_str(&rendered);
+        rest = &rest[consumed..];
+    }
+
+    html.push_str(&escape_html(rest));
+    html
+}
+
+/// Parse `[label](url)` at the start of `text`, returning the total length consumed
+fn parse_link(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.strip_prefix('[')?.find("](")? + 1;
+    let url_end = text[label_end + 2..].find(')')? + label_end + 2;
+    Some((&text[1..label_end], &text[label_end + 2..url_end], url_end + 1))
+}
+
+/// Whether a link target may be rendered as a link: http, https, mailto, `#` anchors and relative URLs
+/// Anything else (javascript:, data:, ...) could run script on the preview server's origin.
+fn is_safe_link(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside the scheme
+    let url: String = url.chars().filter(|c| !c.is_ascii_whitespace() && !c.is_control()).collect();
+    match url.find([':', '/', '?', '#']) {
+        Some(pos) if url[pos..].starts_with(':') => {
+            matches!(url[..pos].to_ascii_lowercase().as_str(), "http" | "https" | "mailto")
+        }
+        _ => true,
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Percent-encode a path for use in a URL, keeping `/` separators
+fn percent_encode(path: &str) -> String {
+    path.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| path.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown() {
+        let html = render_markdown("# Title\n\nSome *text* with `code` and [a link](http://x).\n\n- one\n- **two**\n\n```\n<b>\n```\n");
+        assert_eq!(html, "<h1>Title</h1>\n\
+            <p>Some <em>text</em> with <code>code</code> and <a href=\"http://x\">a link</a>.</p>\n\
+            <ul>\n<li>one</li>\n<li><strong>two</strong></li>\n</ul>\n\
+            <pre><code>&lt;b&gt;</code></pre>\n");
+    }
+
+    #[test]
+    fn test_unsafe_links_render_as_text() {
+        assert_eq!(render_inline("[x](javascript:alert(1))"), "x)");
+        assert_eq!(render_inline("[x](JavaScript:alert)"), "x");
+        assert_eq!(render_inline("[x](java\tscript:alert)"), "x");
+        assert_eq!(render_inline("[x](data:text/html,hi)"), "x");
+        assert_eq!(render_inline("[x](mailto:a@b.c)"), "<a href=\"mailto:a@b.c\">x</a>");
+        assert_eq!(render_inline("[x](#top)"), "<a href=\"#top\">x</a>");
+        assert_eq!(render_inline("[x](organic/a.md)"), "<a href=\"organic/a.md\">x</a>");
+        assert_eq!(render_inline("[x](https://x/a:b)"), "<a href=\"https://x/a:b\">x</a>");
+    }
+
+    #[test]
+    fn test_percent_round_trip_and_safe_path() {
+        let path = "组/a b.md";
+        assert_eq!(percent_decode(&percent_encode(path)), path);
+        assert!(safe_relative_path("organic/a.md").is_some());
+        assert!(safe_relative_path("../docuram.json").is_none());
+        assert!(safe_relative_path("/etc/passwd").is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use console::{style, Color};
+use std::collections::HashMap;
+
+use crate::api::ApiClient;
+use crate::commands::list::{find_new_local_docs, get_document_status_from_doc};
+use crate::commands::pull::fetch_document_versions;
+use crate::config::{CliConfig, DocuramConfig};
+
+/// Working tree summary counted by `status`
+#[derive(Debug, Default)]
+pub struct StatusSummary {
+    pub modified: Vec<String>,
+    /// Tracked but never synced locally
+    pub untracked: Vec<String>,
+    /// Files (or `add --register` entries) not on the server yet
+    pub new_local: Vec<String>,
+    pub pending_deletion: Vec<String>,
+    /// Tracked but not downloaded
+    pub missing: Vec<String>,
+    /// Local version behind the server (only known with --remote)
+    pub out_of_date: Option<Vec<String>>,
+}
+
+impl StatusSummary {
+    /// Local changes that a push would send
+    pub fn has_local_changes(&self) -> bool {
+        !self.modified.is_empty() || !self.new_local.is_empty() || !self.pending_deletion.is_empty()
+    }
+}
+
+/// Show a summary of the working tree
+/// `remote` also compares local versions with the server.
+/// Returns false when there are local changes, so main can exit non-zero.
+pub async fn execute(remote: bool) -> Result<bool> {
+    crate::outln!("{}", style("Docuram Status").cyan().bold());
+    crate::outln!();
+
+    let docuram_config = DocuramConfig::load_with_migration()
+        .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+    let server_url = docuram_config.server_url().to_string();
+
+    crate::outln!("{}", style(format!("Project: {} ({})", docuram_config.project.name, server_url)).bold());
+    let cli_config = CliConfig::load()?;
+    let auth = cli_config.resolve_auth(&server_url);
+    match &auth {
+        Some(auth) => crate::outln!("Logged in as {}", auth.user_email),
+        None => crate::outln!("{}", style("Not logged in (run 'teamturbo login')").yellow()),
+    }
+    crate::outln!();
+
+    let remote_versions = if remote {
+        let auth = auth.with_context(|| format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
+        let client = ApiClient::from_auth(server_url.clone(), &auth);
+        let docs = fetch_document_versions(&client, &docuram_config).await?;
+        Some(docs.into_iter().map(|doc| (doc.uuid, doc.version)).collect())
+    } else {
+        None
+    };
+
+    let summary = summarize(&docuram_config, remote_versions.as_ref());
+    print_summary(&summary);
+
+    Ok(!summary.has_local_changes())
+}
+
+/// Classify every document in the checkout
+pub fn summarize(docuram_config: &DocuramConfig, remote_versions: Option<&HashMap<String, i64>>) -> StatusSummary {
+    let layout = docuram_config.layout();
+    let mut summary = StatusSummary {
+        out_of_date: remote_versions.map(|_| Vec::new()),
+        ..Default::default()
+    };
+
+    for doc in docuram_config.all_documents() {
+        let path = doc.local_path(&layout);
+        match get_document_status_from_doc(doc, &path).as_str() {
+            "Modified" => summary.modified.push(path.clone()),
+            "Not synced" => summary.untracked.push(path.clone()),
+            "New" => summary.new_local.push(path.clone()),
+            "Pending deletion" => summary.pending_deletion.push(path.clone()),
+            "Not downloaded" => summary.missing.push(path.clone()),
+            _ => {}
+        }
+
+        if let (Some(out_of_date), Some(versions)) = (summary.out_of_date.as_mut(), remote_versions) {
+            if !doc.provisional && versions.get(&doc.uuid).is_some_and(|&version| version > doc.version) {
+                out_of_date.push(path);
+            }
+        }
+    }
+
+    summary.new_local.extend(find_new_local_docs(docuram_config).into_iter().map(|doc| doc.file_path));
+    summary
+}
+
+fn print_summary(summary: &StatusSummary) {
+    let sections = [
+        ("modified", &summary.modified, Color::Yellow),
+        ("new local", &summary.new_local, Color::Cyan),
+        ("pending deletion", &summary.pending_deletion, Color::Red),
+        ("untracked", &summary.untracked, Color::Magenta),
+        ("missing", &summary.missing, Color::White),
+    ];
+
+    for (label, paths, color) in sections {
+        let line = style(format!("{:<17} {}", format!("{}:", label), paths.len()));
+        crate::outln!("{}", if paths.is_empty() { line.dim() } else { line.fg(color) });
+        for path in paths.iter() {
+            crate::item!("    {}", path);
+        }
+    }
+
+    match &summary.out_of_date {
+        Some(paths) => {
+            let line = format!("{:<17} {}", "out of date:", paths.len());
+            crate::outln!("{}", if paths.is_empty() { style(line).dim() } else { style(line).blue() });
+            for path in paths {
+                crate::item!("    {}", path);
+            }
+        }
+        None => crate::outln!("{}", style(format!("{:<17} ? (use --remote to check)", "out of date:")).dim()),
+    }
+
+    crate::outln!();
+    if summary.has_local_changes() {
+        crate::outln!("{}", style("Local changes not pushed (run 'teamturbo push')").yellow());
+    } else {
+        crate::outln!("{}", style("✓ No local changes").green());
+    }
+}
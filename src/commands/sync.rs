@@ -1,27 +1,90 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use console::style;
+use std::collections::HashSet;
 
-use crate::commands::{pull, push};
+use crate::commands::{clean, pull, push};
+use crate::config::DocuramConfig;
+use crate::utils::{parse_size, PathFilter, DEFAULT_MAX_FILE_SIZE};
 
-pub async fn execute(force: bool) -> Result<()> {
-    println!("{}", style("Sync Documents").cyan().bold());
-    println!();
+/// Pull then push
+/// Like pull and push on their own, sync removes local copies of documents deleted on the server
+/// and deletes locally removed documents on the server. With `prune` it mirrors completely: files
+/// those deletions left behind are removed too, and both directions are summarized.
+/// `strategy` decides per document what happens when both the local file and the server changed.
+pub async fn execute(force: bool, strategy: Option<pull::ConflictStrategy>, prune: bool) -> Result<()> {
+    crate::outln!("{}", style("Sync Documents").cyan().bold());
+    crate::outln!();
+
+    let before = server_document_uuids()?;
 
     // First pull updates from server
-    println!("{}", style("Step 1/2: Pulling updates from server...").bold());
-    println!();
+    crate::outln!("{}", style("Step 1/2: Pulling updates from server...").bold());
+    crate::outln!();
 
-    pull::execute(Vec::new(), force).await?;
+    let options = pull::PullOptions {
+        document_types: Vec::new(),
+        force,
+        force_with_lease: false,
+        heal: false,
+        only_new: false,
+        to: None,
+        document_list: None,
+        conflict_strategy: strategy,
+        filter: PathFilter::default(),
+    };
+    pull::execute(Vec::new(), options).await?;
+    let after_pull = server_document_uuids()?;
 
-    println!();
-    println!("{}", style("Step 2/2: Pushing local changes to server...").bold());
-    println!();
+    crate::outln!();
+    crate::outln!("{}", style("Step 2/2: Pushing local changes to server...").bold());
+    crate::outln!();
 
     // Then push local changes
-    push::execute(Vec::new(), Some("Sync: Auto-push after pull".to_string())).await?;
+    let options = push::PushOptions {
+        document_list: None,
+        message: Some("Sync: Auto-push after pull".to_string()),
+        per_document: false,
+        content_only: false,
+        rename_on_title_change: false,
+        max_file_size: parse_size(DEFAULT_MAX_FILE_SIZE).expect("Invalid default file size"),
+        dry_run: false,
+        filter: PathFilter::default(),
+        strict: false,
+    };
+    push::execute(Vec::new(), options).await?;
+
+    if prune {
+        let after_push = server_document_uuids()?;
+
+        crate::outln!();
+        crate::outln!("{}", style("Pruning files left by deleted documents...").bold());
+        let mut docuram_config = DocuramConfig::load()
+            .context("Failed to load docuram.json")?;
+        let orphans = clean::find_orphans(&docuram_config)?;
+        let cleaned = clean::remove_orphans(&mut docuram_config, &orphans)?;
+        if orphans.is_empty() {
+            crate::outln!("  {}", style("No leftover files").dim());
+        }
 
-    println!();
-    println!("{}", style("✓ Sync completed").green().bold());
+        crate::outln!();
+        crate::outln!("{}", style("Deletions mirrored:").bold());
+        crate::outln!("  Deleted on server, removed locally: {}", before.difference(&after_pull).count());
+        crate::outln!("  Removed locally, deleted on server: {}", after_pull.difference(&after_push).count());
+        crate::outln!("  Leftover files removed: {}", cleaned);
+    }
+
+    crate::outln!();
+    crate::outln!("{}", style("✓ Sync completed").green().bold());
 
     Ok(())
 }
+
+/// UUIDs of the documents in docuram.json that exist on the server (provisional ones do not)
+fn server_document_uuids() -> Result<HashSet<String>> {
+    let docuram_config = DocuramConfig::load_with_migration()
+        .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+    Ok(docuram_config.all_documents()
+        .filter(|d| !d.provisional)
+        .map(|d| d.uuid.clone())
+        .collect())
+}
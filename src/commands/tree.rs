@@ -0,0 +1,209 @@
+use anyhow::{Context, Result};
+use console::style;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::api::client::{is_offline, CategoryTree};
+use crate::api::ApiClient;
+use crate::commands::list::{
+    build_tree_structure, get_document_status_from_doc, get_local_version_from_doc,
+    print_tree_node, scan_markdown_files, DocumentSource, ListDocumentInfo, TreeOptions,
+};
+use crate::config::{CliConfig, DocuramConfig};
+
+/// Show the local document hierarchy without contacting the server,
+/// or the server's category hierarchy with `remote`
+pub async fn execute(ascii: bool, depth: Option<usize>, remote: bool) -> Result<()> {
+    if depth == Some(0) {
+        anyhow::bail!("--depth must be at least 1");
+    }
+    if remote {
+        return execute_remote(TreeOptions { ascii, max_depth: depth }).await;
+    }
+
+    // Load docuram config (local only, no server calls)
+    let docuram_config = DocuramConfig::load()
+        .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+
+    let layout = docuram_config.layout();
+
+    let mut tree: HashMap<String, Vec<ListDocumentInfo>> = HashMap::new();
+
+    // Documents tracked in docuram.json
+    for doc in docuram_config.all_documents() {
+        let local_file_path = doc.local_path(&layout);
+        let file_path = Path::new(&local_file_path);
+
+        let title = file_path.file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or(&doc.title)
+            .to_string();
+
+        tree.entry(parent_dir(file_path))
+            .or_default()
+            .push(ListDocumentInfo {
+                title,
+                uuid: doc.uuid.clone(),
+                doc_type: doc.doc_type.clone(),
+                status: get_document_status_from_doc(doc, &local_file_path),
+                local_version: get_local_version_from_doc(doc),
+                remote_version: "-".to_string(),
+                source: DocumentSource::Docuram,
+                is_public: false,
+            });
+    }
+
+    // Public dependencies recorded in docuram.json
+    for public_dep in &docuram_config.public_dependencies {
+        for doc in &public_dep.documents {
+            let file_path = Path::new(&doc.path);
+
+            let title = file_path.file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or(&doc.title)
+                .to_string();
+
+            tree.entry(parent_dir(file_path))
+                .or_default()
+                .push(ListDocumentInfo {
+                    title,
+                    uuid: doc.uuid.clone(),
+                    doc_type: doc.doc_type.clone(),
+                    status: get_document_status_from_doc(doc, &doc.path),
+                    local_version: doc.version.to_string(),
+                    remote_version: "-".to_string(),
+                    source: DocumentSource::Docuram,
+                    is_public: true,
+                });
+        }
+    }
+
+    // New local documents on disk that are not tracked yet
+    let tracked_paths: HashSet<String> = docuram_config
+        .all_documents()
+        .map(|d| d.local_path(&layout))
+        .collect();
+
+    let new_document_filter = docuram_config.new_document_filter();
+
+    for new_doc in scan_markdown_files("docuram").unwrap_or_default() {
+        if tracked_paths.contains(&new_doc.file_path) || !new_document_filter.is_candidate(&new_doc.file_path) {
+            continue;
+        }
+
+        let file_path = Path::new(&new_doc.file_path);
+        tree.entry(parent_dir(file_path))
+            .or_default()
+            .push(ListDocumentInfo {
+                title: new_doc.title,
+                uuid: String::new(),
+                doc_type: "knowledge".to_string(),
+                status: "New".to_string(),
+                local_version: "-".to_string(),
+                remote_version: "-".to_string(),
+                source: DocumentSource::New,
+                is_public: false,
+            });
+    }
+
+    if tree.is_empty() {
+        crate::outln!("{}", style("No documents found").yellow());
+        return Ok(());
+    }
+
+    for docs in tree.values_mut() {
+        docs.sort_by(|a, b| a.title.cmp(&b.title));
+    }
+
+    let tree_structure = build_tree_structure(&tree);
+    let options = TreeOptions { ascii, max_depth: depth };
+
+    crate::outln!("{}", style(&docuram_config.project.name).bold());
+    print_tree_node(&tree_structure, &tree, "", true, 1, options);
+
+    Ok(())
+}
+
+/// Print every category on the server with its document count, including ones not pulled
+async fn execute_remote(options: TreeOptions) -> Result<()> {
+    if is_offline() {
+        anyhow::bail!("tree --remote needs the server; drop --offline or use plain 'teamturbo tree'");
+    }
+
+    let docuram_config = DocuramConfig::load()
+        .context("Failed to load docuram.json. Run 'teamturbo init' first.")?;
+    let server_url = docuram_config.server_url().to_string();
+    let cli_config = CliConfig::load()?;
+    let auth = cli_config.resolve_auth(&server_url)
+        .with_context(|| format!("Not logged in to {}. Run 'teamturbo login' first.", server_url))?;
+    let client = ApiClient::from_auth(server_url.clone(), &auth);
+
+    let categories = client.get_category_tree().await
+        .context("Failed to fetch categories")?;
+    if categories.is_empty() {
+        crate::outln!("{}", style("No categories found").yellow());
+        return Ok(());
+    }
+
+    crate::outln!("{}", style(&server_url).bold());
+    print_category_node(&categories, &docuram_config.docuram.category_path, "", 1, options);
+
+    Ok(())
+}
+
+/// Print categories recursively with the same connectors as `print_tree_node`
+fn print_category_node(categories: &[CategoryTree], working_category_path: &str, prefix: &str, depth: usize, options: TreeOptions) {
+    let (branch, last_branch, pipe, dir_icon) = if options.ascii {
+        ("|--", "`--", "|", "[D]")
+    } else {
+        ("├──", "└──", "│", "📁")
+    };
+
+    let mut categories: Vec<&CategoryTree> = categories.iter().collect();
+    categories.sort_by_key(|category| (category.position, category.name.clone()));
+
+    for (idx, category) in categories.iter().enumerate() {
+        let is_last = idx == categories.len() - 1;
+        let working_marker = if category.path == working_category_path {
+            style(" (working)").green()
+        } else {
+            style("").white()
+        };
+
+        crate::outln!("{}{} {} {} {}{}",
+            prefix,
+            style(if is_last { last_branch } else { branch }).dim(),
+            style(dir_icon).cyan(),
+            style(&category.name).bold().cyan(),
+            style(format!("({} docs)", category.document_count)).dim(),
+            working_marker
+        );
+
+        let children = category.subcategories.as_deref().unwrap_or_default();
+        if children.is_empty() {
+            continue;
+        }
+
+        let child_prefix = if is_last {
+            format!("{}   ", prefix)
+        } else {
+            format!("{}{}  ", prefix, pipe)
+        };
+
+        // Children beyond the depth limit are collapsed
+        if options.max_depth.is_none_or(|max| depth < max) {
+            print_category_node(children, working_category_path, &child_prefix, depth + 1, options);
+        } else {
+            crate::outln!("{}{} {}", child_prefix, style(last_branch).dim(), style("...").dim());
+        }
+    }
+}
+
+/// Directory portion of a document path, used as the tree grouping key
+fn parent_dir(file_path: &Path) -> String {
+    file_path.parent()
+        .and_then(|p| p.to_str())
+        .filter(|p| !p.is_empty())
+        .unwrap_or("Unknown")
+        .to_string()
+}
@@ -3,34 +3,39 @@ use colored::Colorize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::utils::http;
 use crate::config::InstallMetadata;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Execute upgrade command
-pub async fn execute(force: bool) -> Result<()> {
-    println!("{}", "Checking for updates...".cyan());
-
+/// `rollback` restores the binary backed up by an interrupted upgrade instead.
+pub async fn execute(force: bool, rollback: bool) -> Result<()> {
     // Load install metadata
     let metadata = InstallMetadata::load()
         .context("Failed to load installation metadata")?;
 
+    if rollback {
+        return rollback_upgrade(&metadata);
+    }
+
+    crate::outln!("{}", "Checking for updates...".cyan());
+
     // Get current version
     let current_version = VERSION;
-    println!("Current version: teamturbo {}", current_version.green());
+    crate::outln!("Current version: teamturbo {}", current_version.green());
 
     // Fetch remote version
     let version_url = format!("{}/teamturbo-cli/version", metadata.base_url);
-    println!("Fetching version from: {}", version_url);
+    crate::outln!("Fetching version from: {}", version_url);
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()?;
+    let client = http::build_client(http::ClientOptions::default())?;
 
     let response = client
         .get(&version_url)
         .send()
         .await
+        .map_err(http::request_error)
         .with_context(|| format!("Failed to fetch version from {}", version_url))?;
 
     if !response.status().is_success() {
@@ -44,11 +49,11 @@ pub async fn execute(force: bool) -> Result<()> {
         .strip_prefix("teamturbo ")
         .unwrap_or(remote_version_text.trim());
 
-    println!("Latest version: teamturbo {}", remote_version.green());
+    crate::outln!("Latest version: teamturbo {}", remote_version.green());
 
     // Compare versions
     if remote_version == current_version {
-        println!("{}", "You are already using the latest version!".green());
+        crate::outln!("{}", "You are already using the latest version!".green());
         return Ok(());
     }
 
@@ -65,7 +70,7 @@ pub async fn execute(force: bool) -> Result<()> {
     let is_newer = remote_parts > current_parts;
 
     if !is_newer {
-        println!(
+        crate::outln!(
             "{}",
             format!(
                 "Local version ({}) is newer than remote version ({})",
@@ -76,7 +81,7 @@ pub async fn execute(force: bool) -> Result<()> {
         return Ok(());
     }
 
-    println!(
+    crate::outln!(
         "{}",
         format!(
             "New version available: {} -> {}",
@@ -87,26 +92,27 @@ pub async fn execute(force: bool) -> Result<()> {
 
     // Ask for confirmation unless force flag is set
     if !force {
-        println!("\n{}", "Do you want to upgrade? (y/N): ".cyan());
+        crate::outln!("\n{}", "Do you want to upgrade? (y/N): ".cyan());
         let mut input = String::new();
         std::io::stdin().read_line(&mut input)?;
         let input = input.trim().to_lowercase();
 
         if input != "y" && input != "yes" {
-            println!("{}", "Upgrade cancelled.".yellow());
+            crate::outln!("{}", "Upgrade cancelled.".yellow());
             return Ok(());
         }
     } else {
-        println!("{}", "\nForce upgrade mode: skipping confirmation.".yellow());
+        crate::outln!("{}", "\nForce upgrade mode: skipping confirmation.".yellow());
     }
 
-    println!("{}", "Downloading new version...".cyan());
+    crate::outln!("{}", "Downloading new version...".cyan());
 
     // Download new version
     let response = client
         .get(&metadata.download_url)
         .send()
         .await
+        .map_err(http::request_error)
         .with_context(|| format!("Failed to download from {}", metadata.download_url))?;
 
     if !response.status().is_success() {
@@ -126,28 +132,144 @@ pub async fn execute(force: bool) -> Result<()> {
     fs::write(&temp_file, &bytes)
         .with_context(|| format!("Failed to write temp file: {:?}", temp_file))?;
 
-    println!("{}", "Extracting files...".cyan());
+    // Keep the current binaries until the new version is installed and verified
+    let backed_up: Vec<PathBuf> = installed_binaries(&metadata)
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect();
+    for path in &backed_up {
+        backup_binary(path)?;
+    }
+
+    crate::outln!("{}", "Extracting files...".cyan());
 
     // Extract and install based on OS
-    if metadata.os == "Windows" {
-        install_windows(&temp_file, &metadata)?;
+    let installed = if metadata.os == "Windows" {
+        install_windows(&temp_file, &metadata)
     } else {
-        install_unix(&temp_file, &metadata)?;
-    }
+        install_unix(&temp_file, &metadata)
+    };
 
     // Clean up temp file
     let _ = fs::remove_file(&temp_file);
 
-    println!("{}", "\nUpgrade completed successfully!".green());
-    println!(
+    // A failed install may have replaced some binaries already, so it is rolled back like a
+    // new version that does not run
+    let verified = installed.and_then(|_| verify_binary(Path::new(&metadata.install_path), remote_version));
+    if let Err(e) = verified {
+        crate::outln!("{}", format!("Upgrade failed: {:#}", e).red());
+        crate::outln!("{}", "Restoring the previous version...".cyan());
+        for path in &backed_up {
+            restore_binary(path)?;
+        }
+        anyhow::bail!("Upgrade rolled back to teamturbo {}", current_version);
+    }
+
+    // The new version works, so the backups are no longer needed
+    for path in &backed_up {
+        let _ = fs::remove_file(backup_path(path));
+    }
+
+    crate::outln!("{}", "\nUpgrade completed successfully!".green());
+    crate::outln!(
         "{}",
         format!("teamturbo {} -> {}", current_version, remote_version).green()
     );
-    println!("\nRun 'teamturbo --version' to verify the update.");
 
     Ok(())
 }
 
+/// Restore the binaries backed up by an upgrade that did not finish
+/// A completed upgrade removes its backups, so this recovers from an interrupted one.
+fn rollback_upgrade(metadata: &InstallMetadata) -> Result<()> {
+    crate::outln!("{}", "Restoring the previous version...".cyan());
+
+    let binaries = installed_binaries(metadata);
+    if let Some(missing) = binaries.iter().map(|path| backup_path(path)).find(|backup| !backup.exists()) {
+        anyhow::bail!("No backup found at {:?}; nothing to roll back", missing);
+    }
+    for path in &binaries {
+        restore_binary(path)?;
+        crate::outln!("Restored: {}", path.display());
+    }
+
+    crate::outln!("{}", "\nRollback completed successfully!".green());
+    crate::outln!("\nRun 'teamturbo --version' to check the restored version.");
+
+    Ok(())
+}
+
+/// Binaries replaced by an upgrade (tt.exe is a copy on Windows, a symlink elsewhere)
+fn installed_binaries(metadata: &InstallMetadata) -> Vec<PathBuf> {
+    let mut binaries = vec![PathBuf::from(&metadata.install_path)];
+    if metadata.os == "Windows" {
+        binaries.push(tt_path(metadata));
+    }
+    binaries
+}
+
+fn tt_path(metadata: &InstallMetadata) -> PathBuf {
+    metadata
+        .tt_path
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(&metadata.install_dir).join("tt.exe"))
+}
+
+/// Where the binary at `path` is kept during an upgrade (`{path}.bak`)
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Copy the binary to its backup path
+/// The copy is written next to it and renamed into place, so an interrupted backup
+/// never replaces a good one.
+fn backup_binary(path: &Path) -> Result<()> {
+    let backup = backup_path(path);
+    let partial = backup.with_extension("bak.partial");
+    fs::copy(path, &partial)
+        .with_context(|| format!("Failed to back up {:?}", path))?;
+    fs::rename(&partial, &backup)
+        .with_context(|| format!("Failed to back up {:?} to {:?}", path, backup))?;
+    Ok(())
+}
+
+/// Move the backup back over the binary and mark it executable
+fn restore_binary(path: &Path) -> Result<()> {
+    let backup = backup_path(path);
+    fs::rename(&backup, path)
+        .with_context(|| format!("Failed to restore {:?} from {:?}", path, backup))?;
+    set_executable(path)
+}
+
+/// Run the installed binary and check it reports the expected version
+fn verify_binary(path: &Path, expected_version: &str) -> Result<()> {
+    let output = std::process::Command::new(path)
+        .arg("--version")
+        .output()
+        .with_context(|| format!("Failed to run {:?}", path))?;
+    let reported = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || !reported.split_whitespace().any(|word| word == expected_version) {
+        anyhow::bail!("{:?} reported '{}', expected teamturbo {}", path, reported.trim(), expected_version);
+    }
+    Ok(())
+}
+
+fn set_executable(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(path, perms)?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
 fn install_windows(zip_path: &Path, metadata: &InstallMetadata) -> Result<()> {
     use std::io::Read;
     use zip::ZipArchive;
@@ -162,11 +284,7 @@ fn install_windows(zip_path: &Path, metadata: &InstallMetadata) -> Result<()> {
 
     // Get install paths
     let install_path = Path::new(&metadata.install_path);
-    let tt_path_buf = metadata
-        .tt_path
-        .as_ref()
-        .map(|p| PathBuf::from(p))
-        .unwrap_or_else(|| PathBuf::from(&metadata.install_dir).join("tt.exe"));
+    let tt_path_buf = tt_path(metadata);
     let tt_path = tt_path_buf.as_path();
 
     // Write to temporary files first (to avoid file in use errors)
@@ -187,7 +305,7 @@ fn install_windows(zip_path: &Path, metadata: &InstallMetadata) -> Result<()> {
         match fs::rename(&temp_teamturbo_path, install_path) {
             Ok(_) => break,
             Err(e) if attempt < max_attempts => {
-                println!("Waiting for file to be available (attempt {}/{})...", attempt, max_attempts);
+                crate::outln!("Waiting for file to be available (attempt {}/{})...", attempt, max_attempts);
                 std::thread::sleep(std::time::Duration::from_millis(500));
                 if attempt == max_attempts - 1 {
                     return Err(e).with_context(|| format!("Failed to replace {:?}. Please close all terminal windows running teamturbo and try again.", install_path));
@@ -201,7 +319,7 @@ fn install_windows(zip_path: &Path, metadata: &InstallMetadata) -> Result<()> {
     fs::rename(&temp_tt_path, tt_path)
         .with_context(|| format!("Failed to replace {:?}", tt_path))?;
 
-    println!(
+    crate::outln!(
         "Updated: {} and {}",
         install_path.display(),
         tt_path.display()
@@ -227,19 +345,13 @@ fn install_unix(gz_path: &Path, metadata: &InstallMetadata) -> Result<()> {
         .with_context(|| format!("Failed to write to {:?}", temp_new_path))?;
 
     // Make executable
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&temp_new_path)?.permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(&temp_new_path, perms)?;
-    }
+    set_executable(&temp_new_path)?;
 
     // Use rename/move to replace the running binary (this works even if file is in use)
     fs::rename(&temp_new_path, install_path)
         .with_context(|| format!("Failed to replace binary at {:?}", install_path))?;
 
-    println!("Updated: {}", install_path.display());
+    crate::outln!("Updated: {}", install_path.display());
 
     // Note: tt is a symlink, no need to update it
 
@@ -4,8 +4,10 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::collections::HashSet;
 
-use crate::config::DocuramConfig;
-use crate::utils::{logger, calculate_checksum};
+use crate::api::ApiClient;
+use crate::commands::pull::save_downloaded_document;
+use crate::config::{CategoryTree, CliConfig, DocuramConfig};
+use crate::utils::{logger, format_size, is_ignored, calculate_checksum, checksums_match, calculate_content_checksum, matches_ignoring_line_endings, normalize_line_endings, write_file, STANDARD_DIRS};
 
 #[derive(Debug, Clone)]
 struct ValidationIssue {
@@ -19,11 +21,24 @@ enum IssueLevel {
     Warning,
 }
 
-pub async fn execute() -> Result<()> {
-    println!("{}", style("Verifying Docuram Project Structure").cyan().bold());
-    println!();
+/// Where `verify --fix` moves orphaned files
+const QUARANTINE_DIR: &str = ".docuram/quarantine";
+
+/// Stub contents for required files `verify --fix` creates in req/
+const REQ_STUBS: [(&str, &str); 2] = [
+    ("README.md", "# Requirements\n"),
+    ("UPDATED_LOG.md", "# Update Log\n"),
+];
+
+/// Verify the project layout and document integrity
+/// `fix` repairs what is safe (missing directories, req stubs, missing dependencies, orphaned files)
+/// without overwriting existing files; `fix_encoding` rewrites files to LF without BOM first
+pub async fn execute(fix: bool, fix_encoding: bool) -> Result<()> {
+    crate::outln!("{}", style("Verifying Docuram Project Structure").cyan().bold());
+    crate::outln!();
 
     let mut issues: Vec<ValidationIssue> = Vec::new();
+    let mut fixed = 0;
 
     // Check if docuram directory exists
     let docuram_path = Path::new("docuram");
@@ -37,36 +52,86 @@ pub async fn execute() -> Result<()> {
         anyhow::bail!("docuram.json not found. Run 'teamturbo init' first.");
     }
 
-    let docuram_config = DocuramConfig::load()
+    let mut docuram_config = DocuramConfig::load()
         .context("Failed to load docuram.json")?;
 
     logger::debug("verify", "Loaded docuram.json");
 
+    // 0. Normalize line endings before checking, so checksums reflect the rewritten files
+    if fix_encoding {
+        crate::outln!("{}", style("Normalizing line endings...").bold());
+        let changed = fix_line_endings(docuram_path, &mut docuram_config)?;
+        for path in &changed {
+            crate::outln!("  {} Normalized {}", style("→").cyan(), path.display());
+        }
+        if changed.is_empty() {
+            crate::outln!("  {}", style("All files already use LF line endings").dim());
+        } else {
+            docuram_config.save()?;
+        }
+    }
+
     // 1. Verify category path structure
-    println!("{}", style("Checking category path structure...").bold());
+    crate::outln!("{}", style("Checking category path structure...").bold());
     verify_category_path_structure(docuram_path, &docuram_config, &mut issues)?;
 
     // 2. Verify top-level directory structure
-    println!("{}", style("Checking directory structure...").bold());
-    verify_directory_structure(docuram_path, &docuram_config, &mut issues)?;
+    crate::outln!("{}", style("Checking directory structure...").bold());
+    verify_directory_structure(docuram_path, &docuram_config, fix, &mut fixed, &mut issues)?;
 
     // 3. Verify req directory contents
-    println!("{}", style("Checking req directory...").bold());
-    verify_req_directory(docuram_path, &docuram_config, &mut issues)?;
+    crate::outln!("{}", style("Checking req directory...").bold());
+    verify_req_directory(docuram_path, &docuram_config, fix, &mut fixed, &mut issues)?;
 
     // 4. Verify dependencies directory (should only contain pulled documents)
-    println!("{}", style("Checking dependencies directory...").bold());
+    crate::outln!("{}", style("Checking dependencies directory...").bold());
     verify_dependencies_directory(docuram_path, &docuram_config, &mut issues)?;
 
     // 5. Verify document integrity (front matter, checksums)
-    println!("{}", style("Checking document integrity...").bold());
-    verify_document_integrity(docuram_path, &docuram_config, &mut issues)?;
+    crate::outln!("{}", style("Checking document integrity...").bold());
+    // With --verbose, mismatches are compared against the last synced version on the server
+    let client = if logger::is_verbose() { connect(&docuram_config)? } else { None };
+    verify_document_integrity(docuram_path, &docuram_config, client.as_ref(), &mut issues).await?;
 
     // 6. Verify all documents in config exist on disk
-    println!("{}", style("Checking document existence...").bold());
+    // Missing dependencies are read-only server copies, so re-downloading them loses nothing
+    if fix {
+        fixed += restore_missing_dependencies(&mut docuram_config).await?;
+    }
+    crate::outln!("{}", style("Checking document existence...").bold());
     verify_documents_exist(docuram_path, &docuram_config, &mut issues)?;
 
-    println!();
+    // 7. Detect orphaned markdown files: untracked and never uploaded by push
+    crate::outln!("{}", style("Checking for orphaned files...").bold());
+    let orphans = find_orphaned_files(docuram_path, &docuram_config)?;
+
+    if fix && !orphans.is_empty() {
+        for orphan in &orphans {
+            let target = quarantine_file(orphan)?;
+            crate::outln!("  {} Moved {} -> {}", style("→").cyan(), orphan.display(), target.display());
+            fixed += 1;
+        }
+    } else {
+        for orphan in &orphans {
+            issues.push(ValidationIssue {
+                level: IssueLevel::Warning,
+                message: format!(
+                    "File '{}' is not tracked and push will not upload it. Run 'teamturbo verify --fix' to quarantine it.",
+                    orphan.display()
+                ),
+            });
+        }
+    }
+
+    crate::outln!();
+
+    if fix {
+        crate::outln!("{}", style(format!("Fixed {} issue(s)", fixed)).green().bold());
+        if !issues.is_empty() {
+            crate::outln!("{}", style("The issues below could not be fixed safely and need attention.").dim());
+        }
+        crate::outln!();
+    }
 
     // Report results
     let errors: Vec<_> = issues.iter().filter(|i| i.level == IssueLevel::Error).collect();
@@ -76,26 +141,26 @@ pub async fn execute() -> Result<()> {
     let warning_count = warnings.len();
 
     if !errors.is_empty() {
-        println!("{}", style(format!("Found {} error(s):", error_count)).red().bold());
+        crate::outln!("{}", style(format!("Found {} error(s):", error_count)).red().bold());
         for issue in &errors {
-            println!("  {} {}", style("✗").red(), issue.message);
+            crate::outln!("  {} {}", style("✗").red(), issue.message);
         }
-        println!();
+        crate::outln!();
     }
 
     if !warnings.is_empty() {
-        println!("{}", style(format!("Found {} warning(s):", warning_count)).yellow().bold());
+        crate::outln!("{}", style(format!("Found {} warning(s):", warning_count)).yellow().bold());
         for issue in &warnings {
-            println!("  {} {}", style("⚠").yellow(), issue.message);
+            crate::outln!("  {} {}", style("⚠").yellow(), issue.message);
         }
-        println!();
+        crate::outln!();
     }
 
     if issues.is_empty() {
-        println!("{}", style("✓ All checks passed! Docuram structure is valid.").green().bold());
+        crate::outln!("{}", style("✓ All checks passed! Docuram structure is valid.").green().bold());
         Ok(())
     } else if error_count == 0 {
-        println!("{}", style("✓ Verification completed with warnings.").yellow().bold());
+        crate::outln!("{}", style("✓ Verification completed with warnings.").yellow().bold());
         Ok(())
     } else {
         anyhow::bail!("Verification failed with {} error(s)", error_count);
@@ -151,6 +216,8 @@ fn verify_category_path_structure(
 fn verify_directory_structure(
     docuram_path: &Path,
     docuram_config: &DocuramConfig,
+    fix: bool,
+    fixed: &mut usize,
     issues: &mut Vec<ValidationIssue>
 ) -> Result<()> {
     let category_path = &docuram_config.docuram.category_path;
@@ -161,9 +228,12 @@ fn verify_directory_structure(
         return Ok(());
     }
 
-    let allowed_dirs = vec!["dependencies", "impl", "organic", "req"];
+    let allowed_dirs: Vec<&str> = std::iter::once("dependencies").chain(STANDARD_DIRS).collect();
     let allowed_files = vec!["README.md"];
 
+    // Directories that correspond to real server categories are allowed too
+    let known_categories = known_category_dirs(docuram_config);
+
     let entries = fs::read_dir(&base_path)
         .with_context(|| format!("Failed to read directory: {}", base_path.display()))?;
 
@@ -174,7 +244,7 @@ fn verify_directory_structure(
         let path = entry.path();
 
         if path.is_dir() {
-            if !allowed_dirs.contains(&name.as_str()) {
+            if !allowed_dirs.contains(&name.as_str()) && !known_categories.contains(&name) {
                 let relative_path = path.strip_prefix(docuram_path)
                     .unwrap_or(&path);
                 issues.push(ValidationIssue {
@@ -206,6 +276,13 @@ fn verify_directory_structure(
         if !dir_path.exists() {
             let relative_path = dir_path.strip_prefix(docuram_path)
                 .unwrap_or(&dir_path);
+            if fix {
+                fs::create_dir_all(&dir_path)
+                    .with_context(|| format!("Failed to create directory: {}", dir_path.display()))?;
+                crate::outln!("  {} Created directory {}", style("→").cyan(), dir_path.display());
+                *fixed += 1;
+                continue;
+            }
             issues.push(ValidationIssue {
                 level: IssueLevel::Warning,
                 message: format!("Required directory '{}' is missing.", relative_path.display()),
@@ -216,9 +293,34 @@ fn verify_directory_structure(
     Ok(())
 }
 
+/// Top-level subcategory names of the working category, from category_tree and document paths
+fn known_category_dirs(docuram_config: &DocuramConfig) -> HashSet<String> {
+    let prefix = format!("{}/", docuram_config.docuram.category_path);
+    let mut category_paths: Vec<&str> = docuram_config.all_documents()
+        .map(|doc| doc.category_path.as_str())
+        .collect();
+
+    let mut pending: Vec<&CategoryTree> = docuram_config.category_tree.iter().collect();
+    while let Some(category) = pending.pop() {
+        category_paths.push(&category.path);
+        if let Some(subcategories) = &category.subcategories {
+            pending.extend(subcategories.iter());
+        }
+    }
+
+    category_paths.into_iter()
+        .filter_map(|path| path.strip_prefix(&prefix))
+        .filter_map(|relative| relative.split('/').next())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+        .collect()
+}
+
 fn verify_req_directory(
     docuram_path: &Path,
     docuram_config: &DocuramConfig,
+    fix: bool,
+    fixed: &mut usize,
     issues: &mut Vec<ValidationIssue>
 ) -> Result<()> {
     let category_path = &docuram_config.docuram.category_path;
@@ -229,13 +331,18 @@ fn verify_req_directory(
         return Ok(());
     }
 
-    let required_files = vec!["README.md", "UPDATED_LOG.md"];
-
-    for file in &required_files {
+    for (file, stub) in &REQ_STUBS {
         let file_path = req_path.join(file);
         if !file_path.exists() {
             let relative_path = file_path.strip_prefix(docuram_path)
                 .unwrap_or(&file_path);
+            if fix {
+                write_file(&file_path, stub)
+                    .with_context(|| format!("Failed to write {}", file_path.display()))?;
+                crate::outln!("  {} Created stub {}", style("→").cyan(), file_path.display());
+                *fixed += 1;
+                continue;
+            }
             issues.push(ValidationIssue {
                 level: IssueLevel::Error,
                 message: format!("Required file '{}' is missing.", relative_path.display()),
@@ -251,7 +358,7 @@ fn verify_dependencies_directory(
     docuram_config: &DocuramConfig,
     issues: &mut Vec<ValidationIssue>
 ) -> Result<()> {
-    let working_category_path = &docuram_config.docuram.category_path;
+    let layout = docuram_config.layout();
 
     // Dependencies are now at project root "dependencies/" directory
     let dep_path = Path::new("dependencies");
@@ -272,7 +379,7 @@ fn verify_dependencies_directory(
 
     // Get all required document LOCAL paths from config
     let required_paths: HashSet<String> = docuram_config.requires.iter()
-        .map(|doc| doc.local_path(working_category_path))
+        .map(|doc| doc.local_path(&layout))
         .collect();
 
     // Check if any file in dependencies is not in the required list
@@ -293,12 +400,13 @@ fn verify_dependencies_directory(
     Ok(())
 }
 
-fn verify_document_integrity(
+async fn verify_document_integrity(
     _docuram_path: &Path,
     docuram_config: &DocuramConfig,
+    client: Option<&ApiClient>,
     issues: &mut Vec<ValidationIssue>
 ) -> Result<()> {
-    let working_category_path = &docuram_config.docuram.category_path;
+    let layout = docuram_config.layout();
 
     // Combine all documents (working + dependencies)
     let all_docs: Vec<_> = docuram_config.documents.iter()
@@ -307,7 +415,7 @@ fn verify_document_integrity(
 
     for doc in all_docs {
         // Use local_path() to get the correct local file path
-        let local_file_path = doc.local_path(working_category_path);
+        let local_file_path = doc.local_path(&layout);
         let doc_path = Path::new(&local_file_path);
 
         if !doc_path.exists() {
@@ -329,12 +437,29 @@ fn verify_document_integrity(
 
         // Verify checksum
         let calculated_checksum = calculate_checksum(&content);
-        if calculated_checksum != doc.checksum {
+        if !checksums_match(&calculated_checksum, &doc.checksum) {
+            let expected = if doc.checksum.is_empty() { "none" } else { doc.checksum.as_str() };
+            let mut details = format!("expected {}, actual {}", expected, calculated_checksum);
+            if let Ok(metadata) = fs::metadata(doc_path) {
+                details.push_str(&format!(", {}", format_size(metadata.len())));
+                if let Ok(modified) = metadata.modified() {
+                    details.push_str(&format!(", modified {}", chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339()));
+                }
+            }
+            if let Some(client) = client {
+                match client.download_document_version(&doc.uuid, doc.version).await {
+                    Ok(synced) => match first_differing_line(&synced.content.unwrap_or_default(), &content) {
+                        Some(line) => details.push_str(&format!(", first differs from v{} at line {}", doc.version, line)),
+                        None => details.push_str(&format!(", identical to v{}", doc.version)),
+                    },
+                    Err(e) => logger::debug("verify", &format!("Failed to download v{} of {}: {}", doc.version, doc.uuid, e)),
+                }
+            }
             issues.push(ValidationIssue {
                 level: IssueLevel::Warning,
                 message: format!(
-                    "Document '{}' has checksum mismatch. File may have been modified.",
-                    local_file_path
+                    "Document '{}' has checksum mismatch ({}). File may have been modified.",
+                    local_file_path, details
                 ),
             });
         }
@@ -348,11 +473,11 @@ fn verify_documents_exist(
     docuram_config: &DocuramConfig,
     issues: &mut Vec<ValidationIssue>
 ) -> Result<()> {
-    let working_category_path = &docuram_config.docuram.category_path;
+    let layout = docuram_config.layout();
 
     // Check working documents
     for doc in &docuram_config.documents {
-        let local_file_path = doc.local_path(working_category_path);
+        let local_file_path = doc.local_path(&layout);
         let doc_path = Path::new(&local_file_path);
         if !doc_path.exists() {
             issues.push(ValidationIssue {
@@ -364,7 +489,7 @@ fn verify_documents_exist(
 
     // Check dependency documents
     for doc in &docuram_config.requires {
-        let local_file_path = doc.local_path(working_category_path);
+        let local_file_path = doc.local_path(&layout);
         let doc_path = Path::new(&local_file_path);
         if !doc_path.exists() {
             issues.push(ValidationIssue {
@@ -377,6 +502,172 @@ fn verify_documents_exist(
     Ok(())
 }
 
+/// Client for the project's server, or None when not logged in
+fn connect(docuram_config: &DocuramConfig) -> Result<Option<ApiClient>> {
+    let server_url = docuram_config.server_url().to_string();
+    let cli_config = CliConfig::load()?;
+    Ok(cli_config.resolve_auth(&server_url)
+        .map(|auth| ApiClient::from_auth(server_url, &auth)))
+}
+
+/// 1-based number of the first line where `old` and `new` differ, None if they are identical
+/// Line endings are ignored, so CRLF and LF copies of the same text compare equal.
+fn first_differing_line(old: &str, new: &str) -> Option<usize> {
+    let mut old_lines = old.lines();
+    let mut new_lines = new.lines();
+    let mut line = 1;
+    loop {
+        match (old_lines.next(), new_lines.next()) {
+            (None, None) => return None,
+            (a, b) if a != b => return Some(line),
+            _ => line += 1,
+        }
+    }
+}
+
+/// Download dependency documents whose local file is missing
+/// Failures are printed and left for the existence check to report. Returns how many were restored.
+async fn restore_missing_dependencies(docuram_config: &mut DocuramConfig) -> Result<usize> {
+    let layout = docuram_config.layout();
+    let missing: Vec<(String, String)> = docuram_config.requires.iter()
+        .map(|doc| (doc.uuid.clone(), doc.local_path(&layout)))
+        .filter(|(_, local_path)| !Path::new(local_path).exists())
+        .collect();
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    let Some(client) = connect(docuram_config)? else {
+        crate::outln!("  {} Not logged in to {}, skipping {} missing dependency document(s)", style("✗").red(), docuram_config.server_url(), missing.len());
+        return Ok(0);
+    };
+
+    let mut restored = 0;
+    for (uuid, local_path) in &missing {
+        let saved = match client.download_document(uuid).await {
+            Ok(doc) => save_downloaded_document(docuram_config, uuid, doc),
+            Err(e) => Err(e),
+        };
+        match saved {
+            Ok(()) => {
+                crate::outln!("  {} Downloaded {}", style("→").cyan(), local_path);
+                restored += 1;
+            }
+            Err(e) => crate::outln!("  {} Failed to download {}: {}", style("✗").red(), local_path, e),
+        }
+    }
+
+    if restored > 0 {
+        docuram_config.save()?;
+    }
+    Ok(restored)
+}
+
+/// Markdown files under docuram/ that docuram.json does not know and push would never upload
+fn find_orphaned_files(
+    docuram_path: &Path,
+    docuram_config: &DocuramConfig,
+) -> Result<Vec<PathBuf>> {
+    let layout = docuram_config.layout();
+    let working_category_path = &docuram_config.docuram.category_path;
+
+    // Every path that config knows about: tracked documents and local-only metadata,
+    // plus the files the req directory check requires
+    let req_path = docuram_path.join(working_category_path).join("req");
+    let known_paths: HashSet<String> = docuram_config.all_documents()
+        .map(|doc| doc.local_path(&layout))
+        .chain(docuram_config.local_documents.iter().map(|doc| doc.path.clone()))
+        .chain(REQ_STUBS.iter().map(|(file, _)| req_path.join(file).to_string_lossy().to_string()))
+        .collect();
+
+    let new_document_filter = docuram_config.new_document_filter();
+    let mut orphans = Vec::new();
+
+    for file_path in collect_all_files(docuram_path)? {
+        let path_str = file_path.to_string_lossy().to_string();
+
+        // Only markdown files are considered; hidden and .docuramignore'd files (including
+        // add templates) are skipped like push does
+        let is_hidden = file_path.file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.starts_with('.'))
+            .unwrap_or(false);
+        if is_hidden || is_ignored(&file_path) || file_path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+
+        // Files push uploads as new documents are user documents, wherever they are
+        if !known_paths.contains(&path_str) && !new_document_filter.is_candidate(&path_str) {
+            orphans.push(file_path);
+        }
+    }
+
+    orphans.sort();
+    Ok(orphans)
+}
+
+/// Rewrite markdown files under docuram/ to LF line endings without BOM
+/// Tracked documents that were unmodified keep matching their stored local checksum.
+fn fix_line_endings(docuram_path: &Path, docuram_config: &mut DocuramConfig) -> Result<Vec<PathBuf>> {
+    let layout = docuram_config.layout();
+    let mut changed = Vec::new();
+
+    for file_path in collect_all_files(docuram_path)? {
+        let is_hidden = file_path.file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.starts_with('.'))
+            .unwrap_or(false);
+        if is_hidden || file_path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+
+        let content = fs::read_to_string(&file_path)
+            .with_context(|| format!("Failed to read {}", file_path.display()))?;
+        let Some(normalized) = normalize_line_endings(&content) else {
+            continue;
+        };
+
+        write_file(&file_path, &normalized)
+            .with_context(|| format!("Failed to write {}", file_path.display()))?;
+
+        let path_str = file_path.to_string_lossy().to_string();
+        let original_checksum = calculate_checksum(&content);
+        let tracked = docuram_config.documents.iter_mut()
+            .chain(docuram_config.requires.iter_mut())
+            .find(|doc| doc.local_path(&layout) == path_str);
+
+        if let Some(doc) = tracked {
+            let unmodified = doc.local_checksum.as_deref()
+                .map(|stored| checksums_match(stored, &original_checksum) || matches_ignoring_line_endings(&normalized, stored))
+                .unwrap_or(false);
+            if unmodified {
+                doc.local_checksum = Some(calculate_checksum(&normalized));
+                doc.local_content_checksum = Some(calculate_content_checksum(&normalized));
+            }
+        }
+
+        changed.push(file_path);
+    }
+
+    changed.sort();
+    Ok(changed)
+}
+
+/// Move a file into the quarantine directory, preserving its relative path
+fn quarantine_file(file_path: &Path) -> Result<PathBuf> {
+    let target = Path::new(QUARANTINE_DIR).join(file_path);
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::rename(file_path, &target)
+        .with_context(|| format!("Failed to move {} to {}", file_path.display(), target.display()))?;
+
+    Ok(target)
+}
+
 fn collect_all_files(dir: &Path) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
 
@@ -399,3 +690,17 @@ fn collect_all_files(dir: &Path) -> Result<Vec<PathBuf>> {
 
     Ok(files)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_differing_line() {
+        assert_eq!(first_differing_line("a\nb\nc\n", "a\nb\nc\n"), None);
+        assert_eq!(first_differing_line("a\r\nb\r\n", "a\nb\n"), None);
+        assert_eq!(first_differing_line("a\nb\nc\n", "a\nx\nc\n"), Some(2));
+        assert_eq!(first_differing_line("a\nb\n", "a\nb\nc\n"), Some(3));
+        assert_eq!(first_differing_line("", "a"), Some(1));
+    }
+}
@@ -1,39 +1,95 @@
 use anyhow::Result;
 use console::style;
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use crate::config::CliConfig;
 use crate::api::ApiClient;
+use crate::api::client::VerifyResponse;
 
-pub async fn execute() -> Result<()> {
-    println!("{}", style("TeamTurbo CLI Status").cyan().bold());
-    println!();
+/// One server in `whoami --json` output
+#[derive(Serialize)]
+pub struct WhoamiJsonEntry {
+    pub server: String,
+    /// Display name
+    pub user: Option<String>,
+    pub user_id: Option<i64>,
+    /// Absent when the token could not be verified
+    pub account: Option<String>,
+    /// "active", "expired" or "error"
+    pub status: &'static str,
+    pub expires_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
 
+/// Show the logged-in user on each server
+/// `json` prints one entry per server as a JSON array and nothing else.
+/// Returns false when any token is expired or fails verification, so main can exit non-zero.
+pub async fn execute(json: bool) -> Result<bool> {
     // Load config
     let config = CliConfig::load()?;
 
+    if json {
+        let mut entries = Vec::new();
+        for (server_url, auth_config) in config.auth.iter() {
+            let client = ApiClient::new(server_url.clone(), auth_config.access_token.clone());
+            let verified = client.verify().await;
+            let status = token_status(&verified, &auth_config.expires_at);
+            entries.push(match verified {
+                Ok(verify_response) => WhoamiJsonEntry {
+                    server: server_url.clone(),
+                    user: Some(verify_response.user.display_name_or_account().to_string()),
+                    user_id: Some(verify_response.user.id),
+                    account: Some(verify_response.user.account),
+                    status,
+                    expires_at: Some(verify_response.expires_at),
+                    error: None,
+                },
+                Err(e) => WhoamiJsonEntry {
+                    server: server_url.clone(),
+                    // Last known identity from login
+                    user: Some(auth_config.user_name.clone()),
+                    user_id: Some(auth_config.user_id),
+                    account: None,
+                    status,
+                    expires_at: Some(auth_config.expires_at.clone()),
+                    error: Some(e.to_string()),
+                },
+            });
+        }
+        crate::outln!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(entries.iter().all(|entry| entry.status == "active"));
+    }
+
+    crate::outln!("{}", style("TeamTurbo CLI Status").cyan().bold());
+    crate::outln!();
+
     // Check if there are any saved auth configs
     if config.auth.is_empty() {
-        println!("{}", style("Not logged in").yellow());
-        println!();
-        println!("{}", style("Run 'teamturbo login' to authenticate").dim());
-        return Ok(());
+        crate::outln!("{}", style("Not logged in").yellow());
+        crate::outln!();
+        crate::outln!("{}", style("Run 'teamturbo login' to authenticate").dim());
+        return Ok(true);
     }
 
     // Verify each server
+    let mut all_active = true;
     for (server_url, auth_config) in config.auth.iter() {
-        println!("{}", style(format!("Server: {}", server_url)).bold());
+        crate::outln!("{}", style(format!("Server: {}", server_url)).bold());
 
         let client = ApiClient::new(server_url.clone(), auth_config.access_token.clone());
 
-        match client.verify().await {
+        let verified = client.verify().await;
+        all_active &= token_status(&verified, &auth_config.expires_at) == "active";
+        match verified {
             Ok(verify_response) => {
-                println!("  {}: {}", style("Status").dim(), style("✓ Active").green());
-                println!("  {}: {} ({})",
+                crate::outln!("  {}: {}", style("Status").dim(), style("✓ Active").green());
+                crate::outln!("  {}: {} ({})",
                     style("User").dim(),
                     verify_response.user.display_name_or_account(),
                     verify_response.user.account
                 );
-                println!("  {}: {}",
+                crate::outln!("  {}: {}",
                     style("User ID").dim(),
                     verify_response.user.id
                 );
@@ -48,45 +104,77 @@ pub async fn execute() -> Result<()> {
                         let days = duration.num_days();
 
                         if days > 7 {
-                            println!("  {}: {} ({} days)",
+                            crate::outln!("  {}: {} ({} days)",
                                 style("Expires").dim(),
                                 verify_response.expires_at,
                                 days
                             );
                         } else if days > 0 {
-                            println!("  {}: {} ({} days)",
+                            crate::outln!("  {}: {} ({} days)",
                                 style("Expires").dim(),
                                 style(&verify_response.expires_at).yellow(),
                                 style(days).yellow()
                             );
                         } else {
                             let hours = duration.num_hours();
-                            println!("  {}: {} ({} hours)",
+                            crate::outln!("  {}: {} ({} hours)",
                                 style("Expires").dim(),
                                 style(&verify_response.expires_at).red(),
                                 style(hours).red()
                             );
                         }
                     } else {
-                        println!("  {}: {}",
+                        crate::outln!("  {}: {}",
                             style("Expires").dim(),
                             style("Expired").red()
                         );
                     }
                 } else {
-                    println!("  {}: {}",
+                    crate::outln!("  {}: {}",
                         style("Expires").dim(),
                         verify_response.expires_at
                     );
                 }
             }
             Err(e) => {
-                println!("  {}: {}", style("Status").dim(), style(format!("✗ {}", e)).red());
+                crate::outln!("  {}: {}", style("Status").dim(), style(format!("✗ {}", e)).red());
             }
         }
 
-        println!();
+        // Capabilities recorded at login
+        match &auth_config.capabilities {
+            Some(capabilities) => {
+                crate::outln!("  {}: {}",
+                    style("Server version").dim(),
+                    capabilities.version.as_deref().unwrap_or("unknown")
+                );
+                if !capabilities.features.is_empty() {
+                    crate::outln!("  {}: {}", style("Features").dim(), capabilities.features.join(", "));
+                }
+            }
+            None => {
+                crate::outln!("  {}: {}",
+                    style("Capabilities").dim(),
+                    style("unknown (run 'teamturbo login' to refresh)").dim()
+                );
+            }
+        }
+
+        crate::outln!();
     }
 
-    Ok(())
+    Ok(all_active)
+}
+
+/// "active", "expired" (per the server or the saved expiry) or "error"
+fn token_status(verified: &Result<VerifyResponse>, saved_expires_at: &str) -> &'static str {
+    let expired = |expires_at: &str| {
+        DateTime::parse_from_rfc3339(expires_at).is_ok_and(|expires_at| expires_at.with_timezone(&Utc) <= Utc::now())
+    };
+    match verified {
+        Ok(verify_response) if expired(&verify_response.expires_at) => "expired",
+        Ok(_) => "active",
+        Err(_) if expired(saved_expires_at) => "expired",
+        Err(_) => "error",
+    }
 }
@@ -1,12 +1,32 @@
 use anyhow::{Result, Context};
+use fs2::FileExt;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::sync::OnceLock;
 use crate::auth::AuthConfig;
+use crate::utils::logger;
+
+/// Access token used instead of the saved login (for CI)
+pub const TOKEN_ENV_VAR: &str = "TEAMTURBO_TOKEN";
+
+/// Restricts TEAMTURBO_TOKEN to this server
+pub const SERVER_ENV_VAR: &str = "TEAMTURBO_SERVER";
 
 /// Global CLI configuration
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct CliConfig {
+    /// Override for the User-Agent header sent with every request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+
+    /// Server used by 'login' without --domain and for relative 'init --config-url' values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_server: Option<String>,
+
     #[serde(flatten)]
     pub auth: std::collections::HashMap<String, AuthConfig>,
 }
@@ -45,15 +65,68 @@ impl CliConfig {
         let content = toml::to_string_pretty(self)
             .context("Failed to serialize config")?;
 
-        fs::write(&path, content)
+        crate::utils::write_file_atomic(&path, &content)
             .with_context(|| format!("Failed to write config file: {:?}", path))?;
 
         Ok(())
     }
 
     /// Get auth config for a server
+    /// Falls back to comparing normalized URLs (case, trailing slash, default port)
     pub fn get_auth(&self, server_url: &str) -> Option<&AuthConfig> {
-        self.auth.get(server_url)
+        self.auth.get(server_url).or_else(|| {
+            let wanted = normalize_server_url(server_url);
+            self.auth.iter()
+                .find(|(url, _)| normalize_server_url(url) == wanted)
+                .map(|(_, auth)| auth)
+        })
+    }
+
+    /// Credentials for a server: TEAMTURBO_TOKEN when set, otherwise the saved login
+    /// With TEAMTURBO_SERVER the environment token only applies to that server. It is never
+    /// refreshed or saved.
+    pub fn resolve_auth(&self, server_url: &str) -> Option<Cow<'_, AuthConfig>> {
+        let env_token = std::env::var(TOKEN_ENV_VAR).ok().filter(|token| !token.trim().is_empty());
+        let env_server = std::env::var(SERVER_ENV_VAR).ok().filter(|server| !server.trim().is_empty());
+        match env_token {
+            Some(token) if env_server.is_none_or(|server| normalize_server_url(&server) == normalize_server_url(server_url)) => {
+                Some(Cow::Owned(AuthConfig {
+                    access_token: token.trim().to_string(),
+                    token_type: "Bearer".to_string(),
+                    // No expiry disables refreshing
+                    expires_at: String::new(),
+                    user_id: 0,
+                    user_name: format!("${}", TOKEN_ENV_VAR),
+                    user_email: format!("${}", TOKEN_ENV_VAR),
+                    capabilities: None,
+                }))
+            }
+            _ => self.get_auth(server_url)
+                // Under --server, fall back to the login for the checkout's own server
+                .or_else(|| {
+                    let overridden = OVERRIDDEN_SERVER.get()
+                        .filter(|_| server_override().map(normalize_server_url) == Some(normalize_server_url(server_url)))?;
+                    self.get_auth(overridden)
+                })
+                .map(Cow::Borrowed),
+        }
+    }
+
+    /// Get mutable auth config for a server (same lookup as `get_auth`)
+    pub fn get_auth_mut(&mut self, server_url: &str) -> Option<&mut AuthConfig> {
+        let wanted = normalize_server_url(server_url);
+        let key = self.auth.keys()
+            .find(|url| url.as_str() == server_url)
+            .or_else(|| self.auth.keys().find(|url| normalize_server_url(url) == wanted))?
+            .clone();
+        self.auth.get_mut(&key)
+    }
+
+    /// Servers with stored credentials, sorted
+    pub fn servers(&self) -> Vec<&str> {
+        let mut servers: Vec<&str> = self.auth.keys().map(|s| s.as_str()).collect();
+        servers.sort();
+        servers
     }
 
     /// Set auth config for a server
@@ -67,6 +140,213 @@ impl CliConfig {
     }
 }
 
+/// Host of a URL, or the whole string if it does not parse
+fn url_host(server_url: &str) -> String {
+    url::Url::parse(server_url.trim()).ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| server_url.trim().to_string())
+}
+
+/// Normalize a server URL for comparison
+/// Lowercases scheme and host, drops default ports and trailing slashes.
+pub fn normalize_server_url(server_url: &str) -> String {
+    let trimmed = server_url.trim().trim_end_matches('/');
+    match url::Url::parse(trimmed) {
+        Ok(url) => {
+            let host = url.host_str().unwrap_or_default();
+            let mut normalized = match url.port() {
+                Some(port) => format!("{}://{}:{}", url.scheme(), host, port),
+                None => format!("{}://{}", url.scheme(), host),
+            };
+            normalized.push_str(url.path().trim_end_matches('/'));
+            normalized
+        }
+        Err(_) => trimmed.to_lowercase(),
+    }
+}
+
+/// Project-level CLI settings (.docuram/config.toml)
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ProjectConfig {
+    /// Defaults applied to documents created from this project
+    #[serde(default)]
+    pub defaults: DocumentDefaults,
+}
+
+/// Default metadata for newly created documents
+/// Also used for per-document overrides recorded by `add`/`import`
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct DocumentDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i64>,
+}
+
+impl DocumentDefaults {
+    /// Check if no value is set
+    pub fn is_empty(&self) -> bool {
+        self.doc_type.is_none() && self.priority.is_none()
+    }
+}
+
+impl ProjectConfig {
+    /// Get project config path: .docuram/config.toml
+    pub fn config_path() -> PathBuf {
+        PathBuf::from(".docuram").join("config.toml")
+    }
+
+    /// Load project config, falling back to defaults if the file is absent
+    pub fn load() -> Result<Self> {
+        let path = Self::config_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read project config: {:?}", path))?;
+
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse project config: {:?}", path))
+    }
+}
+
+/// Advisory lock on .docuram/lock, held while a command modifies the project
+/// Released when dropped; the OS also releases it if the process dies.
+pub struct ProjectLock {
+    _file: fs::File,
+}
+
+impl ProjectLock {
+    /// Get lock file path: .docuram/lock
+    pub fn path() -> PathBuf {
+        PathBuf::from(".docuram").join("lock")
+    }
+
+    /// Take the lock, failing at once if another teamturbo command holds it
+    pub fn acquire() -> Result<Self> {
+        Self::acquire_at(&Self::path())
+    }
+
+    fn acquire_at(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file: {}", path.display()))?;
+
+        if let Err(e) = file.try_lock_exclusive() {
+            if e.kind() != fs2::lock_contended_error().kind() {
+                return Err(e).with_context(|| format!("Failed to lock {}", path.display()));
+            }
+            let holder = fs::read_to_string(path).ok()
+                .map(|pid| pid.trim().to_string())
+                .filter(|pid| !pid.is_empty())
+                .map(|pid| format!(" (pid {})", pid))
+                .unwrap_or_default();
+            anyhow::bail!(
+                "Another teamturbo operation is in progress in this project{}. Wait for it to finish and try again.",
+                holder
+            );
+        }
+
+        // Record who holds the lock for the error above; purely informational
+        let _ = file.set_len(0).and_then(|_| write!(file, "{}", std::process::id()));
+        logger::debug("lock", &format!("Acquired {}", path.display()));
+
+        Ok(ProjectLock { _file: file })
+    }
+}
+
+/// Backend set by the global --server flag, used instead of project.url (for testing)
+static SERVER_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// project.url of the checkout whose server is overridden; its login is the fallback
+static OVERRIDDEN_SERVER: OnceLock<String> = OnceLock::new();
+
+/// Use `server_url` instead of the checkout's project.url for this invocation
+pub fn set_server_override(server_url: &str) {
+    let _ = SERVER_OVERRIDE.set(server_url.trim().trim_end_matches('/').to_string());
+}
+
+pub fn server_override() -> Option<&'static str> {
+    SERVER_OVERRIDE.get().map(String::as_str)
+}
+
+/// Directory the CLI was started from, before moving to the project root
+static INVOCATION_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Nearest directory at or above `start` that contains docuram.json
+pub fn find_project_root(start: &Path) -> Option<PathBuf> {
+    start.ancestors()
+        .find(|dir| dir.join("docuram.json").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Move to the project root so docuram.json, .docuram/ and document paths resolve
+/// `project_dir` (--project-dir) wins; otherwise with `discover` the nearest parent
+/// containing docuram.json is used, like git. `create` makes a missing `project_dir` (init).
+pub fn enter_project_dir(project_dir: Option<&str>, discover: bool, create: bool) -> Result<()> {
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    let _ = INVOCATION_DIR.set(cwd.clone());
+
+    let root = match project_dir {
+        Some(dir) => {
+            if create {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create project directory: {}", dir))?;
+            }
+            fs::canonicalize(dir)
+                .with_context(|| format!("Project directory not found: {}", dir))?
+        }
+        None if discover => match find_project_root(&cwd) {
+            Some(root) => root,
+            None => return Ok(()),
+        },
+        None => return Ok(()),
+    };
+
+    if root != cwd {
+        logger::debug("project", &format!("Using project at {}", root.display()));
+        std::env::set_current_dir(&root)
+            .with_context(|| format!("Failed to enter project directory: {}", root.display()))?;
+    }
+    Ok(())
+}
+
+/// Re-anchor a path given on the command line (relative to where the CLI was started)
+/// to the project root. Paths outside the project become absolute; `-` (stdin) is kept.
+pub fn user_path(path: &str) -> String {
+    let (Some(invocation_dir), Ok(cwd)) = (INVOCATION_DIR.get(), std::env::current_dir()) else {
+        return path.to_string();
+    };
+    if path == "-" || Path::new(path).is_absolute() || *invocation_dir == cwd {
+        return path.to_string();
+    }
+
+    let full = invocation_dir.join(path);
+    match full.strip_prefix(&cwd) {
+        Ok(relative) => relative.to_string_lossy().to_string(),
+        Err(_) => full.to_string_lossy().to_string(),
+    }
+}
+
+/// `user_path` for arguments that may also be a UUID, slug or URL: only re-anchored
+/// when the path exists relative to where the CLI was started
+pub fn user_path_if_exists(arg: &str) -> String {
+    match INVOCATION_DIR.get() {
+        Some(invocation_dir) if invocation_dir.join(arg).exists() => user_path(arg),
+        _ => arg.to_string(),
+    }
+}
+
 /// Docuram configuration (docuram.json)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DocuramConfig {
@@ -86,6 +366,40 @@ pub struct DocuramConfig {
     /// Public dependencies from docuram.teamturbo.io
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub public_dependencies: Vec<PublicDependency>,
+
+    /// Additional categories added with `init --merge`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_categories: Vec<MergedCategory>,
+
+    /// Documents dropped because they were deleted on the server, with their last local path
+    /// Files left at these paths are removed by `clean` and never created again by push.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_documents: Vec<RemovedDocument>,
+
+    /// Fields not modeled here, kept so rewriting docuram.json does not drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A category added to the checkout with `init --merge`
+/// Its documents are tracked in `documents`/`requires` next to the working category's
+/// and stored under docuram/<category_path>/.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MergedCategory {
+    pub category_uuid: Option<String>,
+    pub category_path: String,
+    /// Config URL the category was merged from
+    pub config_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_tree: Option<CategoryTree>,
+}
+
+/// A document deleted on the server and dropped from docuram.json
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemovedDocument {
+    pub uuid: String,
+    /// Local file path the document was last tracked at
+    pub path: String,
 }
 
 /// Local document not yet pushed to server
@@ -99,6 +413,93 @@ pub struct LocalOnlyDocument {
     pub checksum: String,
     /// Creation timestamp (ISO 8601 format)
     pub created_at: String,
+    /// Document type override (applied on push)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_type: Option<String>,
+    /// Priority override (applied on push)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i64>,
+    /// Idempotency key sent when push creates this document
+    /// Kept until the document is created so a retried create is not duplicated by the server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// Unpublished with `delete --server-only`: the file is kept but push does not create it again
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unpublished: bool,
+    /// Recorded by add, import or push for a file the next push creates
+    /// Entries without it are files kept locally on purpose, which push never creates.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pending: bool,
+}
+
+impl LocalOnlyDocument {
+    /// Create an entry for a freshly written local file
+    pub fn new(path: String, content: &str, overrides: &DocumentDefaults) -> Self {
+        // Title matches what push sends to the server (filename with .md)
+        let stem = std::path::Path::new(&path)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Untitled")
+            .to_string();
+
+        Self {
+            title: format!("{}.md", stem),
+            path,
+            checksum: crate::utils::calculate_checksum(content),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            doc_type: overrides.doc_type.clone(),
+            priority: overrides.priority,
+            idempotency_key: None,
+            unpublished: false,
+            pending: true,
+        }
+    }
+}
+
+/// Where a checkout stores its documents: the working category and the categories merged into it
+/// Built by `DocuramConfig::layout` and passed to `DocumentInfo::local_path`.
+#[derive(Debug, Clone, Default)]
+pub struct LocalLayout {
+    pub working_category_path: String,
+    pub merged_category_paths: Vec<String>,
+}
+
+impl LocalLayout {
+    /// Layout of a checkout without merged categories
+    pub fn new(working_category_path: &str) -> Self {
+        Self { working_category_path: working_category_path.to_string(), merged_category_paths: Vec::new() }
+    }
+
+    /// Whether `category_path` is a merged category or one of its subcategories
+    pub fn is_merged(&self, category_path: &str) -> bool {
+        self.merged_category_paths.iter().any(|merged| {
+            category_path == merged || category_path.starts_with(&format!("{}/", merged))
+        })
+    }
+}
+
+/// Which untracked files under docuram/ push creates as new documents
+/// Built by `DocuramConfig::new_document_filter`, shared by push, list/status and verify.
+pub struct NewDocumentFilter {
+    /// Local paths that are never new: tracked documents and local documents kept off the server
+    excluded: HashSet<String>,
+    /// Last local paths of documents deleted on the server
+    removed: HashSet<String>,
+}
+
+impl NewDocumentFilter {
+    /// Whether push would create the file at `file_path` (relative to the project root)
+    pub fn is_candidate(&self, file_path: &str) -> bool {
+        // dependencies/ (at project root) holds read-only documents
+        !file_path.starts_with("dependencies/")
+            && !self.excluded.contains(file_path)
+            && !self.is_left_by_server_deletion(file_path)
+    }
+
+    /// Whether an untracked file is the leftover copy of a document deleted on the server
+    pub fn is_left_by_server_deletion(&self, file_path: &str) -> bool {
+        !self.excluded.contains(file_path) && self.removed.contains(file_path)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -108,6 +509,9 @@ pub struct ProjectInfo {
     pub description: Option<String>,
     pub url: String,
     pub created_at: String,
+    /// Fields not modeled here, kept so rewriting docuram.json does not drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -121,6 +525,9 @@ pub struct DocuramInfo {
     pub category_path: String,
     pub task_id: Option<i64>,
     pub task_name: Option<String>,
+    /// Fields not modeled here, kept so rewriting docuram.json does not drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 // Custom deserializer to support both old format (required/optional) and new format (array)
@@ -181,6 +588,10 @@ pub struct DocumentInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub local_checksum: Option<String>,
 
+    /// Checksum of the body without front matter (used by --content-only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_content_checksum: Option<String>,
+
     /// Last sync timestamp (ISO 8601 format)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_sync: Option<String>,
@@ -188,9 +599,45 @@ pub struct DocumentInfo {
     /// Mark document as pending deletion (will be deleted from server on next push)
     #[serde(default)]
     pub pending_deletion: bool,
+
+    /// Registered locally by `add --register` and not pushed yet
+    /// The uuid is generated client-side; push creates the document on the server and
+    /// replaces this entry with the server's uuid. Pull leaves provisional entries alone.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub provisional: bool,
+
+    /// Set by `delete --server-only`: keep the local file when push deletes the document
+    /// from the server (the file becomes a local-only document again)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub keep_local: bool,
+
+    /// File name used locally instead of the one in `path`, set by `push --rename-on-title-change`
+    /// Kept out of `path` so refreshing from the server and pulling do not undo the rename.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_name: Option<String>,
+
+    /// Fields not modeled here, kept so rewriting docuram.json does not drop them
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl DocumentInfo {
+    /// Compare file content with the last synced state (None if never synced)
+    /// `content_only` ignores front matter when a body checksum was recorded at sync time.
+    /// Line ending differences alone do not count as modifications.
+    pub fn differs_from_synced(&self, content: &str, content_only: bool) -> Option<bool> {
+        let local_checksum = self.local_checksum.as_ref()?;
+
+        if content_only {
+            if let Some(content_checksum) = &self.local_content_checksum {
+                let body = crate::utils::extract_front_matter(content).1;
+                return Some(!crate::utils::content_matches_checksum(body, content_checksum));
+            }
+        }
+
+        Some(!crate::utils::content_matches_checksum(content, local_checksum))
+    }
+
     /// Generate category remote URL from project URL
     pub fn category_remote_url(&self, project_url: &str) -> String {
         format!("{}/wiki/{}", project_url, self.category_uuid)
@@ -206,7 +653,20 @@ impl DocumentInfo {
     /// Preserves the subdirectory structure within each type directory
     /// For example: docuram/organic/subdir/doc.md, docuram/impl/feature/doc.md
     /// Dependencies are placed in dependencies/ (at project root) with their category structure
-    pub fn local_path(&self, working_category_path: &str) -> String {
+    /// Documents of merged categories go under docuram/<category_path>/
+    /// A `local_name` replaces the file name from the server path.
+    pub fn local_path(&self, layout: &LocalLayout) -> String {
+        let path = self.server_local_path(layout);
+        match (&self.local_name, path.rsplit_once('/')) {
+            (Some(name), Some((dir, _))) => format!("{}/{}", dir, name),
+            (Some(name), None) => name.clone(),
+            (None, _) => path,
+        }
+    }
+
+    fn server_local_path(&self, layout: &LocalLayout) -> String {
+        let working_category_path = layout.working_category_path.as_str();
+
         // Extract the relative path after "docuram/" from the original path
         let path_without_docuram = self.path.strip_prefix("docuram/").unwrap_or(&self.path);
 
@@ -243,6 +703,9 @@ impl DocumentInfo {
                     .unwrap_or("");
 
                 format!("docuram/{}/{}", subdir_path, relative_path)
+            } else if layout.is_merged(&self.category_path) {
+                // Document from a category merged with `init --merge`, namespaced by its path
+                format!("docuram/{}/{}", self.category_path, relative_path)
             } else {
                 // Document is directly in working category, use doc_type mapping
                 let subdir = match self.doc_type.as_str() {
@@ -352,8 +815,16 @@ impl DocuramConfig {
         let content = fs::read_to_string(&path)
             .context("Failed to read docuram.json")?;
 
-        serde_json::from_str(&content)
-            .context("Failed to parse docuram.json")
+        let config: Self = serde_json::from_str(&content)
+            .context("Failed to parse docuram.json")?;
+
+        if let Some(server) = server_override() {
+            if OVERRIDDEN_SERVER.set(config.project.url.clone()).is_ok() && url_host(server) != url_host(&config.project.url) {
+                eprintln!("{} Using {} instead of {} from docuram.json (--server is meant for testing)",
+                    console::style("⚠").yellow(), server, config.project.url);
+            }
+        }
+        Ok(config)
     }
 
     /// Save to docuram.json
@@ -363,15 +834,186 @@ impl DocuramConfig {
         let content = serde_json::to_string_pretty(self)
             .context("Failed to serialize docuram config")?;
 
-        fs::write(&path, content)
+        crate::utils::write_file_atomic(&path, &content)
             .context("Failed to write docuram.json")?;
 
         Ok(())
     }
 
-    /// Get server URL
+    /// Get server URL (the --server override when given)
     pub fn server_url(&self) -> &str {
-        &self.project.url
+        server_override().unwrap_or(&self.project.url)
+    }
+
+    /// Local layout of this checkout, for `DocumentInfo::local_path`
+    pub fn layout(&self) -> LocalLayout {
+        LocalLayout {
+            merged_category_paths: self.merged_categories.iter().map(|c| c.category_path.clone()).collect(),
+            ..LocalLayout::new(&self.docuram.category_path)
+        }
+    }
+
+    /// UUIDs of the working category and merged categories, used to fetch remote versions
+    pub fn category_uuids(&self) -> Vec<String> {
+        self.docuram.category_uuid.iter()
+            .chain(self.merged_categories.iter().filter_map(|c| c.category_uuid.as_ref()))
+            .cloned()
+            .collect()
+    }
+
+    /// Merge another category's config into this checkout (`init --merge`)
+    /// Documents already tracked are skipped. If any new document would land on a local path
+    /// that is already in use, nothing is merged and the conflicts are listed.
+    /// Returns the UUIDs of the documents added.
+    pub fn merge_category(&mut self, other: DocuramConfig, config_url: &str) -> Result<Vec<String>> {
+        let working = self.docuram.category_path.clone();
+        let other_path = other.docuram.category_path.clone();
+        if other_path == working || self.merged_categories.iter().any(|c| c.category_path == other_path) {
+            anyhow::bail!("Category '{}' is already part of this checkout", other_path);
+        }
+        let mut layout = self.layout();
+        layout.merged_category_paths.push(other_path.clone());
+
+        let known_uuids: std::collections::HashSet<String> = self.all_documents()
+            .map(|d| d.uuid.clone())
+            .collect();
+        let mut taken: std::collections::HashMap<String, String> = self.all_documents()
+            .map(|d| (d.local_path(&layout), d.title.clone()))
+            .collect();
+
+        let mut added = Vec::new();
+        let mut conflicts = Vec::new();
+        let mut new_documents = Vec::new();
+        let mut new_requires = Vec::new();
+
+        let incoming = other.documents.into_iter().map(|d| (d, false))
+            .chain(other.requires.into_iter().map(|d| (d, true)));
+        for (doc, is_require) in incoming {
+            if known_uuids.contains(&doc.uuid) || added.contains(&doc.uuid) {
+                continue;
+            }
+
+            let path = doc.local_path(&layout);
+            if let Some(existing) = taken.get(&path) {
+                conflicts.push(format!("{} ('{}' is already stored there)", path, existing));
+                continue;
+            }
+
+            taken.insert(path, doc.title.clone());
+            added.push(doc.uuid.clone());
+            if is_require {
+                new_requires.push(doc);
+            } else {
+                new_documents.push(doc);
+            }
+        }
+
+        if !conflicts.is_empty() {
+            anyhow::bail!(
+                "Cannot merge '{}': {} document(s) conflict with existing paths:\n  {}",
+                other_path,
+                conflicts.len(),
+                conflicts.join("\n  ")
+            );
+        }
+
+        self.documents.extend(new_documents);
+        self.requires.extend(new_requires);
+        for dependency in other.dependencies {
+            if !self.dependencies.iter().any(|d| d.category_id == dependency.category_id) {
+                self.dependencies.push(dependency);
+            }
+        }
+        self.merged_categories.push(MergedCategory {
+            category_uuid: other.docuram.category_uuid,
+            category_path: other_path,
+            config_url: config_url.to_string(),
+            category_tree: other.category_tree,
+        });
+
+        Ok(added)
+    }
+
+    /// Take the working category from a config the server generated again (after push created documents)
+    /// Only the working category's documents, requires and category tree are replaced, and they keep
+    /// their local sync state. Merged categories and their documents, documents not created yet,
+    /// local and removed documents, public dependencies and fields not modeled here are kept.
+    pub fn apply_server_config(&mut self, server: DocuramConfig) {
+        let layout = self.layout();
+        let keep = |d: &DocumentInfo| d.provisional || layout.is_merged(&d.category_path);
+        let (kept_documents, previous_documents): (Vec<_>, Vec<_>) = std::mem::take(&mut self.documents)
+            .into_iter()
+            .partition(|d| keep(d));
+        let (kept_requires, previous_requires): (Vec<_>, Vec<_>) = std::mem::take(&mut self.requires)
+            .into_iter()
+            .partition(|d| keep(d));
+
+        let previous: std::collections::HashMap<String, DocumentInfo> = previous_documents.into_iter()
+            .chain(previous_requires)
+            .map(|d| (d.uuid.clone(), d))
+            .collect();
+        let kept_uuids: HashSet<String> = kept_documents.iter()
+            .chain(kept_requires.iter())
+            .map(|d| d.uuid.clone())
+            .collect();
+        let restore = |mut doc: DocumentInfo| {
+            if let Some(old) = previous.get(&doc.uuid) {
+                doc.local_checksum = old.local_checksum.clone();
+                doc.local_content_checksum = old.local_content_checksum.clone();
+                doc.last_sync = old.last_sync.clone();
+                doc.pending_deletion = old.pending_deletion;
+                doc.keep_local = old.keep_local;
+                doc.local_name = old.local_name.clone();
+            }
+            doc
+        };
+
+        self.documents = server.documents.into_iter()
+            .filter(|d| !kept_uuids.contains(&d.uuid))
+            .map(restore)
+            .chain(kept_documents)
+            .collect();
+        self.requires = server.requires.into_iter()
+            .filter(|d| !kept_uuids.contains(&d.uuid))
+            .map(restore)
+            .chain(kept_requires)
+            .collect();
+
+        // Dependencies added by merged categories are not in the working category's config
+        let local_dependencies: Vec<_> = std::mem::take(&mut self.dependencies).into_iter()
+            .filter(|dep| !server.dependencies.iter().any(|d| d.category_id == dep.category_id))
+            .collect();
+        self.dependencies = server.dependencies;
+        self.dependencies.extend(local_dependencies);
+        self.category_tree = server.category_tree;
+
+        // Fields the server sends win; local fields it does not know about stay
+        let mut project = server.project;
+        keep_missing_fields(&mut project.extra, std::mem::take(&mut self.project.extra));
+        self.project = project;
+        let mut docuram = server.docuram;
+        keep_missing_fields(&mut docuram.extra, std::mem::take(&mut self.docuram.extra));
+        self.docuram = docuram;
+        let mut extra = server.extra;
+        keep_missing_fields(&mut extra, std::mem::take(&mut self.extra));
+        self.extra = extra;
+    }
+
+    /// Resolve document selectors (UUIDs or local paths) to UUIDs
+    /// Selectors that match no tracked path are kept as given.
+    pub fn resolve_document_selectors(&self, selectors: Vec<String>) -> Vec<String> {
+        let layout = self.layout();
+        selectors
+            .into_iter()
+            .map(|selector| {
+                let path = selector.replace('\\', "/");
+                let path = path.strip_prefix("./").unwrap_or(&path);
+                self.all_documents()
+                    .find(|doc| doc.local_path(&layout) == path || doc.path == path)
+                    .map(|doc| doc.uuid.clone())
+                    .unwrap_or(selector)
+            })
+            .collect()
     }
 
     /// Get all documents (documents + requires) as an iterator
@@ -414,13 +1056,66 @@ impl DocuramConfig {
         self.local_documents.iter().find(|d| d.path == path)
     }
 
+    /// Idempotency key for creating the document at `path`, generated on first use
+    /// The key is stored in local_documents, so save the config before sending the create request
+    pub fn idempotency_key_for(&mut self, path: &str, content: &str) -> String {
+        if self.get_local_document_by_path(path).is_none() {
+            self.local_documents.push(LocalOnlyDocument::new(path.to_string(), content, &DocumentDefaults::default()));
+        }
+
+        let doc = self.local_documents.iter_mut()
+            .find(|d| d.path == path)
+            .expect("local document was just added");
+        doc.idempotency_key
+            .get_or_insert_with(|| uuid::Uuid::new_v4().to_string())
+            .clone()
+    }
+
     /// Add a local-only document
     pub fn add_local_document(&mut self, doc: LocalOnlyDocument) {
-        // Remove existing if any
+        // Remove existing if any; a new file at a deleted document's path is a document again
         self.local_documents.retain(|d| d.path != doc.path);
+        self.removed_documents.retain(|d| d.path != doc.path);
         self.local_documents.push(doc);
     }
 
+    /// Filter for the untracked files push creates as new documents
+    /// Provisional entries (from 'add --register') are created by push, so they count as untracked.
+    pub fn new_document_filter(&self) -> NewDocumentFilter {
+        let layout = self.layout();
+        let excluded = self.all_documents()
+            .filter(|d| !d.provisional)
+            .map(|d| d.local_path(&layout))
+            .chain(self.local_documents.iter().filter(|d| !d.pending).map(|d| d.path.clone()))
+            .collect();
+        let removed = self.removed_documents.iter().map(|d| d.path.clone()).collect();
+        NewDocumentFilter { excluded, removed }
+    }
+
+    /// Drop a document deleted on the server
+    /// When its local file is still there, the path is remembered so `clean` can remove it.
+    pub fn forget_server_deleted(&mut self, uuid: &str) -> bool {
+        let Some(doc) = self.get_document_by_uuid(uuid) else {
+            return false;
+        };
+        let path = doc.local_path(&self.layout());
+        self.removed_documents.retain(|d| d.path != path);
+        if Path::new(&path).exists() {
+            self.removed_documents.push(RemovedDocument { uuid: uuid.to_string(), path });
+        }
+        self.remove_document_by_uuid(uuid)
+    }
+
+    /// Drop a document deleted from the server and keep its file as an unpublished local document
+    pub fn unpublish_document(&mut self, uuid: &str, local_path: &str, content: &str) {
+        self.remove_document_by_uuid(uuid);
+        self.add_local_document(LocalOnlyDocument {
+            unpublished: true,
+            pending: false,
+            ..LocalOnlyDocument::new(local_path.to_string(), content, &DocumentDefaults::default())
+        });
+    }
+
     /// Remove a local-only document by path
     pub fn remove_local_document(&mut self, path: &str) -> Option<LocalOnlyDocument> {
         let idx = self.local_documents.iter().position(|d| d.path == path)?;
@@ -455,59 +1150,540 @@ impl DocuramConfig {
     }
 
     /// Load from docuram.json with migration from state.json
+    /// Sync state is only filled in memory; files are moved and state.json removed by
+    /// `reconcile_state_file`, which pull and push run under the project lock.
     pub fn load_with_migration() -> Result<Self> {
         let mut config = Self::load()?;
+        let entries = read_state_entries(&state_file_path(Path::new("")));
+        config.migrate_state_entries(&entries);
+        Ok(config)
+    }
+
+    /// Move files recorded at older locations in the legacy state.json to their docuram.json
+    /// path, then save docuram.json and remove state.json
+    pub fn reconcile_state_file(&mut self) -> Result<()> {
+        if self.reconcile_state_in(Path::new(""))? {
+            self.save()?;
+        }
+        Ok(())
+    }
 
-        // Check if state.json exists and migrate data
-        let state_path = PathBuf::from(".docuram").join("state.json");
-        if state_path.exists() {
-            if let Ok(content) = fs::read_to_string(&state_path) {
-                if let Ok(state) = serde_json::from_str::<serde_json::Value>(&content) {
-                    if let Some(docs) = state.get("documents").and_then(|d| d.as_object()) {
-                        let mut migrated_count = 0;
-
-                        for (_path, doc_value) in docs {
-                            if let (Some(uuid), Some(checksum), Some(last_sync)) = (
-                                doc_value.get("uuid").and_then(|v| v.as_str()),
-                                doc_value.get("checksum").and_then(|v| v.as_str()),
-                                doc_value.get("last_sync").and_then(|v| v.as_str()),
-                            ) {
-                                // Find matching document in config and update local state
-                                if let Some(doc) = config.get_document_by_uuid_mut(uuid) {
-                                    if doc.local_checksum.is_none() {
-                                        doc.local_checksum = Some(checksum.to_string());
-                                        doc.last_sync = Some(last_sync.to_string());
-                                        doc.pending_deletion = doc_value.get("pending_deletion")
-                                            .and_then(|v| v.as_bool())
-                                            .unwrap_or(false);
-                                        migrated_count += 1;
-                                    }
-                                }
-                            }
-                        }
-
-                        if migrated_count > 0 {
-                            // Save migrated config
-                            config.save()?;
-
-                            // Remove old state.json
-                            let _ = fs::remove_file(&state_path);
-
-                            // Try to remove .docuram directory if empty
-                            let docuram_dir = PathBuf::from(".docuram");
-                            if let Ok(mut entries) = fs::read_dir(&docuram_dir) {
-                                if entries.next().is_none() {
-                                    let _ = fs::remove_dir(&docuram_dir);
-                                }
-                            }
-
-                            println!("Migrated {} document(s) from state.json to docuram.json", migrated_count);
-                        }
-                    }
+    /// Reconcile the state.json under `root`; false when there is none
+    fn reconcile_state_in(&mut self, root: &Path) -> Result<bool> {
+        let state_path = state_file_path(root);
+        if !state_path.exists() {
+            return Ok(false);
+        }
+
+        let entries = read_state_entries(&state_path);
+        let layout = self.layout();
+        for entry in &entries {
+            // state.json is keyed by the old local path; docuram.json wins
+            if let Some(doc) = self.get_document_by_uuid(&entry.uuid) {
+                let config_path = root.join(doc.local_path(&layout));
+                reconcile_document_path(&root.join(&entry.path), &config_path)?;
+            }
+        }
+        let migrated_count = self.migrate_state_entries(&entries);
+
+        fs::remove_file(&state_path)
+            .with_context(|| format!("Failed to remove {}", state_path.display()))?;
+
+        // stderr keeps machine-readable stdout intact
+        eprintln!("Migrated {} document(s) from state.json to docuram.json", migrated_count);
+        Ok(true)
+    }
+
+    /// Fill in sync state of documents that have none; returns how many were updated
+    fn migrate_state_entries(&mut self, entries: &[StateEntry]) -> usize {
+        let mut migrated_count = 0;
+        for entry in entries {
+            if let Some(doc) = self.get_document_by_uuid_mut(&entry.uuid) {
+                if doc.local_checksum.is_none() {
+                    doc.local_checksum = Some(entry.checksum.clone());
+                    doc.last_sync = Some(entry.last_sync.clone());
+                    doc.pending_deletion = entry.pending_deletion;
+                    migrated_count += 1;
                 }
             }
         }
+        migrated_count
+    }
+}
 
-        Ok(config)
+/// A document entry of the legacy .docuram/state.json
+struct StateEntry {
+    path: String,
+    uuid: String,
+    checksum: String,
+    last_sync: String,
+    pending_deletion: bool,
+}
+
+fn state_file_path(root: &Path) -> PathBuf {
+    root.join(".docuram").join("state.json")
+}
+
+/// Read the document entries of a state.json (none when missing or unreadable)
+fn read_state_entries(state_path: &Path) -> Vec<StateEntry> {
+    let Some(state) = fs::read_to_string(state_path).ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok()) else {
+        return Vec::new();
+    };
+    let Some(docs) = state.get("documents").and_then(|d| d.as_object()) else {
+        return Vec::new();
+    };
+
+    docs.iter()
+        .filter_map(|(path, doc_value)| Some(StateEntry {
+            path: path.clone(),
+            uuid: doc_value.get("uuid")?.as_str()?.to_string(),
+            checksum: doc_value.get("checksum")?.as_str()?.to_string(),
+            last_sync: doc_value.get("last_sync")?.as_str()?.to_string(),
+            pending_deletion: doc_value.get("pending_deletion").and_then(|v| v.as_bool()).unwrap_or(false),
+        }))
+        .collect()
+}
+
+/// Result of reconciling a document's file with the path recorded in docuram.json
+#[derive(Debug, PartialEq)]
+pub enum PathReconciliation {
+    /// Paths agree, or there is no stale file to move
+    Unchanged,
+    /// The file was moved from the stale path to the configured path
+    Moved,
+    /// Files exist at both paths; left in place for the user to resolve
+    Conflict,
+}
+
+/// Add the fields of `local` that `server` does not have
+fn keep_missing_fields(server: &mut serde_json::Map<String, serde_json::Value>, local: serde_json::Map<String, serde_json::Value>) {
+    for (key, value) in local {
+        server.entry(key).or_insert(value);
+    }
+}
+
+/// Move a document's file from a stale local path to the path derived from docuram.json
+/// Used when the server moved a document (or state.json recorded an older location),
+/// so push/diff/list do not see the old file as a new document and create a duplicate.
+pub fn reconcile_document_path(stale_path: &Path, config_path: &Path) -> Result<PathReconciliation> {
+    if stale_path == config_path || !stale_path.exists() {
+        return Ok(PathReconciliation::Unchanged);
+    }
+
+    if config_path.exists() {
+        eprintln!("Warning: document exists at both {} and {}; keeping both, please remove the stale copy",
+            stale_path.display(), config_path.display());
+        return Ok(PathReconciliation::Conflict);
+    }
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::rename(stale_path, config_path)
+        .with_context(|| format!("Failed to move {} to {}", stale_path.display(), config_path.display()))?;
+
+    eprintln!("Moved document {} -> {}", stale_path.display(), config_path.display());
+
+    Ok(PathReconciliation::Moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_lock_is_exclusive() {
+        let root = std::env::temp_dir().join(format!("teamturbo-lock-{}", uuid::Uuid::new_v4()));
+        let path = root.join(".docuram/lock");
+
+        let lock = ProjectLock::acquire_at(&path).unwrap();
+        let err = ProjectLock::acquire_at(&path).err().unwrap();
+        assert!(err.to_string().contains("Another teamturbo operation is in progress"), "{}", err);
+        assert!(err.to_string().contains(&std::process::id().to_string()));
+
+        drop(lock);
+        assert!(ProjectLock::acquire_at(&path).is_ok());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    fn document(path: &str, category_path: &str) -> DocumentInfo {
+        DocumentInfo {
+            id: 1,
+            uuid: "doc-1".to_string(),
+            title: "a.md".to_string(),
+            category_id: 1,
+            category_name: "organic".to_string(),
+            category_path: category_path.to_string(),
+            category_uuid: "cat-1".to_string(),
+            doc_type: "knowledge".to_string(),
+            version: 1,
+            path: path.to_string(),
+            checksum: String::new(),
+            is_required: false,
+            local_checksum: None,
+            local_content_checksum: None,
+            last_sync: None,
+            pending_deletion: false,
+            provisional: false,
+            keep_local: false,
+            local_name: None,
+            extra: Default::default(),
+        }
+    }
+
+    fn checkout(category_path: &str, documents: Vec<DocumentInfo>) -> DocuramConfig {
+        DocuramConfig {
+            project: ProjectInfo {
+                id: 1,
+                name: "Demo".to_string(),
+                description: None,
+                url: "http://127.0.0.1:9".to_string(),
+                created_at: String::new(),
+                extra: Default::default(),
+            },
+            docuram: DocuramInfo {
+                version: "1.0".to_string(),
+                category_id: 1,
+                category_name: category_path.to_string(),
+                category_uuid: Some(format!("cat-{}", category_path)),
+                category_slug: None,
+                category_path: category_path.to_string(),
+                task_id: None,
+                task_name: None,
+                extra: Default::default(),
+            },
+            documents,
+            requires: Vec::new(),
+            dependencies: Vec::new(),
+            category_tree: None,
+            local_documents: Vec::new(),
+            public_dependencies: Vec::new(),
+            merged_categories: Vec::new(),
+            removed_documents: Vec::new(),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_merge_category_namespaces_and_detects_conflicts() {
+        let mut config = checkout("Cat", vec![document("docuram/Cat/organic/a.md", "Cat/organic")]);
+
+        let mut other_doc = document("docuram/Other/organic/a.md", "Other/organic");
+        other_doc.uuid = "doc-2".to_string();
+        let added = config.merge_category(checkout("Other", vec![other_doc.clone()]), "url").unwrap();
+        assert_eq!(added, vec!["doc-2".to_string()]);
+        assert_eq!(other_doc.local_path(&config.layout()), "docuram/Other/organic/a.md");
+        assert_eq!(config.category_uuids(), vec!["cat-Cat".to_string(), "cat-Other".to_string()]);
+
+        // Merging the same category twice is refused
+        assert!(config.merge_category(checkout("Other", Vec::new()), "url").is_err());
+
+        // A different document landing on an already tracked path is a conflict
+        let mut clash = document("docuram/Other/organic/a.md", "Other/organic");
+        clash.uuid = "doc-3".to_string();
+        let mut third = checkout("Third", vec![clash]);
+        third.docuram.category_path = "Other/organic".to_string();
+        let err = config.merge_category(third, "url").unwrap_err().to_string();
+        assert!(err.contains("conflict"), "{}", err);
+        assert_eq!(config.documents.len(), 2);
+    }
+
+    #[test]
+    fn test_server_config_refresh_keeps_merged_categories() {
+        let mut working_doc = document("docuram/Cat/organic/a.md", "Cat/organic");
+        working_doc.local_checksum = Some("local".to_string());
+        let mut config = checkout("Cat", vec![working_doc]);
+        config.extra.insert("workspace".to_string(), serde_json::json!("team"));
+        let mut merged_doc = document("docuram/Other/b.md", "Other");
+        merged_doc.uuid = "doc-2".to_string();
+        config.merge_category(checkout("Other", vec![merged_doc]), "url").unwrap();
+        let merged_path = config.documents[1].local_path(&config.layout());
+
+        // After push created doc-3, the server regenerates the working category's config
+        let mut updated = document("docuram/Cat/organic/a.md", "Cat/organic");
+        updated.version = 2;
+        let mut created = document("docuram/Cat/organic/c.md", "Cat/organic");
+        created.uuid = "doc-3".to_string();
+        config.apply_server_config(checkout("Cat", vec![updated, created]));
+
+        assert_eq!(config.merged_categories.len(), 1);
+        let uuids: Vec<&str> = config.documents.iter().map(|d| d.uuid.as_str()).collect();
+        assert_eq!(uuids, vec!["doc-1", "doc-3", "doc-2"]);
+        let working = config.get_document_by_uuid("doc-1").unwrap();
+        assert_eq!((working.version, working.local_checksum.as_deref()), (2, Some("local")));
+        assert_eq!(config.extra["workspace"], "team");
+
+        // The merged document's file is still tracked, so push does not create it again
+        assert_eq!(config.get_document_by_uuid("doc-2").unwrap().local_path(&config.layout()), merged_path);
+        assert!(!config.new_document_filter().is_candidate(&merged_path));
+    }
+
+    #[test]
+    fn test_idempotency_key_survives_config_refresh() {
+        let mut config = checkout("Cat", vec![document("docuram/Cat/organic/a.md", "Cat/organic")]);
+        config.idempotency_key_for("docuram/organic/b.md", "# B\n");
+        let timed_out_key = config.idempotency_key_for("docuram/organic/c.md", "# C\n");
+
+        // b.md is created, c.md times out; the refresh must not lose c.md's key
+        config.local_documents.retain(|d| d.path != "docuram/organic/b.md");
+        let mut created = document("docuram/Cat/organic/b.md", "Cat/organic");
+        created.uuid = "doc-2".to_string();
+        let server = checkout("Cat", vec![document("docuram/Cat/organic/a.md", "Cat/organic"), created]);
+        config.apply_server_config(server);
+
+        assert_eq!(config.idempotency_key_for("docuram/organic/c.md", "# C\n"), timed_out_key);
+    }
+
+    #[test]
+    fn test_only_merged_categories_are_namespaced() {
+        // A document moved to another category on the server keeps the doc_type layout
+        let mut doc = document("docuram/Other/organic/a.md", "Other/organic");
+        doc.doc_type = "design".to_string();
+        let config = checkout("Cat", vec![doc.clone()]);
+        assert_eq!(doc.local_path(&config.layout()), "docuram/impl/a.md");
+
+        let mut merged = config.clone();
+        merged.merge_category(checkout("Other", Vec::new()), "url").unwrap();
+        assert_eq!(doc.local_path(&merged.layout()), "docuram/Other/organic/a.md");
+    }
+
+    #[test]
+    fn test_find_project_root_walks_up() {
+        let root = std::env::temp_dir().join(format!("teamturbo-root-{}", uuid::Uuid::new_v4()));
+        let nested = root.join("docuram").join("organic");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_project_root(&nested), None);
+
+        fs::write(root.join("docuram.json"), "{}").unwrap();
+        assert_eq!(find_project_root(&nested), Some(root.clone()));
+        assert_eq!(find_project_root(&root), Some(root.clone()));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_server_only_delete_is_not_pushed_again() {
+        let mut config = checkout("Cat", vec![document("docuram/Cat/organic/a.md", "Cat/organic")]);
+        let local_path = config.documents[0].local_path(&LocalLayout::new("Cat"));
+        let untracked = "docuram/organic/b.md";
+
+        // delete --server-only
+        config.mark_for_deletion("doc-1");
+        config.get_document_by_uuid_mut("doc-1").unwrap().keep_local = true;
+
+        // First push deletes the document on the server and keeps the file
+        config.unpublish_document("doc-1", &local_path, "# A\n");
+        assert!(!config.new_document_filter().is_candidate(&local_path));
+
+        // Second push starts from the saved docuram.json
+        let config: DocuramConfig = serde_json::from_str(&serde_json::to_string_pretty(&config).unwrap()).unwrap();
+        let filter = config.new_document_filter();
+        assert!(config.documents.is_empty());
+        assert!(!filter.is_candidate(&local_path));
+        assert!(filter.is_candidate(untracked));
+    }
+
+    #[test]
+    fn test_kept_local_documents_are_not_new() {
+        // An entry written without `pending` (kept locally on purpose) is never pushed
+        let mut value = serde_json::to_value(checkout("Cat", Vec::new())).unwrap();
+        value["local_documents"] = serde_json::json!([{
+            "path": "docuram/organic/kept.md",
+            "title": "kept.md",
+            "checksum": "x",
+            "created_at": "2024-01-01T00:00:00Z"
+        }]);
+        let mut config: DocuramConfig = serde_json::from_value(value).unwrap();
+        assert!(!config.new_document_filter().is_candidate("docuram/organic/kept.md"));
+
+        // Files registered by add/import are created by the next push
+        config.add_local_document(LocalOnlyDocument::new("docuram/organic/new.md".to_string(), "# N\n", &DocumentDefaults::default()));
+        assert!(config.new_document_filter().is_candidate("docuram/organic/new.md"));
+    }
+
+    #[test]
+    fn test_unpublished_document_survives_config_refresh() {
+        let mut config = checkout("Cat", vec![document("docuram/Cat/organic/a.md", "Cat/organic")]);
+        let local_path = config.documents[0].local_path(&config.layout());
+
+        // One push unpublishes a.md (delete --server-only) and creates b.md
+        config.unpublish_document("doc-1", &local_path, "# A\n");
+        let mut created = document("docuram/Cat/organic/b.md", "Cat/organic");
+        created.uuid = "doc-2".to_string();
+        config.apply_server_config(checkout("Cat", vec![created]));
+
+        assert!(!config.new_document_filter().is_candidate(&local_path));
+    }
+
+    #[test]
+    fn test_server_deleted_leftover_is_not_new() {
+        let mut config = checkout("Cat", Vec::new());
+        let path = "docuram/organic/a.md";
+        config.removed_documents.push(RemovedDocument { uuid: "doc-1".to_string(), path: path.to_string() });
+
+        let filter = config.new_document_filter();
+        assert!(filter.is_left_by_server_deletion(path));
+        assert!(!filter.is_candidate(path));
+
+        // Registering a new file at that path (add/import) makes it a document again
+        config.add_local_document(LocalOnlyDocument::new(path.to_string(), "# A\n", &DocumentDefaults::default()));
+        assert!(config.new_document_filter().is_candidate(path));
+    }
+
+    #[test]
+    fn test_server_deleted_leftover_survives_config_refresh() {
+        let mut config = checkout("Cat", Vec::new());
+        let path = "docuram/organic/a.md";
+        config.removed_documents.push(RemovedDocument { uuid: "doc-1".to_string(), path: path.to_string() });
+
+        // A push that created a document refreshes docuram.json from the server
+        let mut created = document("docuram/Cat/organic/b.md", "Cat/organic");
+        created.uuid = "doc-2".to_string();
+        config.apply_server_config(checkout("Cat", vec![created]));
+
+        assert_eq!(config.removed_documents.len(), 1);
+        assert!(!config.new_document_filter().is_candidate(path));
+    }
+
+    #[test]
+    fn test_title_rename_survives_config_refresh() {
+        let mut doc = document("docuram/Cat/organic/draft.md", "Cat/organic");
+        doc.local_name = Some("Design.md".to_string());
+        let mut config = checkout("Cat", vec![doc]);
+        assert_eq!(config.documents[0].local_path(&config.layout()), "docuram/organic/Design.md");
+
+        // The server still has the old path
+        config.apply_server_config(checkout("Cat", vec![document("docuram/Cat/organic/draft.md", "Cat/organic")]));
+        assert_eq!(config.documents[0].local_path(&config.layout()), "docuram/organic/Design.md");
+    }
+
+    #[test]
+    fn test_docuram_config_keeps_unknown_fields() {
+        let mut value = serde_json::to_value(checkout("Cat", vec![document("docuram/Cat/organic/a.md", "Cat/organic")])).unwrap();
+        value["workspace_id"] = serde_json::json!(42);
+        value["project"]["owner"] = serde_json::json!("team");
+        value["docuram"]["branch"] = serde_json::json!("main");
+        value["documents"][0]["tags"] = serde_json::json!(["a", "b"]);
+
+        // Same (de)serialization as load() and save()
+        let config: DocuramConfig = serde_json::from_str(&value.to_string()).unwrap();
+        let saved: serde_json::Value = serde_json::from_str(&serde_json::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(saved["workspace_id"], 42);
+        assert_eq!(saved["project"]["owner"], "team");
+        assert_eq!(saved["docuram"]["branch"], "main");
+        assert_eq!(saved["documents"][0]["tags"], serde_json::json!(["a", "b"]));
+        assert_eq!(saved, value);
+    }
+
+    #[test]
+    fn test_cli_config_user_agent_alongside_servers() {
+        let content = r#"
+user_agent = "corp-proxy/1.0"
+
+["https://example.com"]
+access_token = "t"
+token_type = "Bearer"
+expires_at = "2030-01-01T00:00:00Z"
+user_id = 1
+user_name = "u"
+user_email = "u@example.com"
+"#;
+        let config: CliConfig = toml::from_str(content).unwrap();
+        assert_eq!(config.user_agent.as_deref(), Some("corp-proxy/1.0"));
+        assert_eq!(config.servers(), vec!["https://example.com"]);
+
+        let saved: CliConfig = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(saved.user_agent.as_deref(), Some("corp-proxy/1.0"));
+        assert!(saved.get_auth("https://example.com").is_some());
+
+        let mut config = saved;
+        config.get_auth_mut("HTTPS://example.com/").unwrap().access_token = "refreshed".to_string();
+        assert_eq!(config.get_auth("https://example.com").unwrap().access_token, "refreshed");
+    }
+
+    #[test]
+    fn test_normalize_server_url() {
+        assert_eq!(normalize_server_url("HTTPS://Example.com:443/"), "https://example.com");
+        assert_eq!(normalize_server_url("http://127.0.0.1:3000"), "http://127.0.0.1:3000");
+        assert_ne!(normalize_server_url("http://example.com"), normalize_server_url("https://example.com"));
+    }
+
+    #[test]
+    fn test_reconcile_after_server_move() {
+        let root = std::env::temp_dir().join(format!("teamturbo-reconcile-{}", uuid::Uuid::new_v4()));
+
+        // Pulled document, then moved on the server from organic/ to impl/feat/
+        let mut doc = document("docuram/Cat/organic/a.md", "Cat/organic");
+        let old_path = root.join(doc.local_path(&LocalLayout::new("Cat")));
+        fs::create_dir_all(old_path.parent().unwrap()).unwrap();
+        fs::write(&old_path, "content").unwrap();
+
+        doc.path = "docuram/Cat/impl/feat/a.md".to_string();
+        doc.category_path = "Cat/impl/feat".to_string();
+        let new_path = root.join(doc.local_path(&LocalLayout::new("Cat")));
+        assert_ne!(old_path, new_path);
+
+        // Next pull migrates the file; a second pass is a no-op
+        assert_eq!(reconcile_document_path(&old_path, &new_path).unwrap(), PathReconciliation::Moved);
+        assert_eq!(reconcile_document_path(&old_path, &new_path).unwrap(), PathReconciliation::Unchanged);
+
+        // Push would only see the tracked file, so no duplicate is created
+        let markdown_files: Vec<_> = walkdir::WalkDir::new(&root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_file())
+            .map(|e| e.path().to_path_buf())
+            .collect();
+        assert_eq!(markdown_files, vec![new_path.clone()]);
+        assert_eq!(fs::read_to_string(&new_path).unwrap(), "content");
+
+        // A file recreated at the old location is reported, not overwritten
+        fs::write(&old_path, "other").unwrap();
+        assert_eq!(reconcile_document_path(&old_path, &new_path).unwrap(), PathReconciliation::Conflict);
+        assert_eq!(fs::read_to_string(&new_path).unwrap(), "content");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_pull_then_push_after_server_move_with_stale_state() {
+        let root = std::env::temp_dir().join(format!("teamturbo-state-{}", uuid::Uuid::new_v4()));
+
+        // docuram.json has the server's new path, state.json and the file still the old one
+        let old_path = "docuram/organic/a.md";
+        fs::create_dir_all(root.join("docuram/organic")).unwrap();
+        fs::write(root.join(old_path), "content").unwrap();
+        fs::create_dir_all(root.join(".docuram")).unwrap();
+        let state = serde_json::json!({
+            "documents": { old_path: { "uuid": "doc-1", "checksum": "abc", "last_sync": "2024-01-01T00:00:00Z" } }
+        });
+        fs::write(state_file_path(&root), state.to_string()).unwrap();
+        let mut config = checkout("Cat", vec![document("docuram/Cat/impl/feat/a.md", "Cat/impl/feat")]);
+        let new_path = config.documents[0].local_path(&config.layout());
+
+        // Pull reconciles: the file moves, sync state is migrated and state.json is gone
+        assert!(config.reconcile_state_in(&root).unwrap());
+        assert_eq!(fs::read_to_string(root.join(&new_path)).unwrap(), "content");
+        assert!(!root.join(old_path).exists());
+        assert!(!state_file_path(&root).exists());
+        assert_eq!(config.documents[0].local_checksum.as_deref(), Some("abc"));
+
+        // Push finds no new document, and a second pull has nothing left to do
+        let filter = config.new_document_filter();
+        let new_documents: Vec<String> = walkdir::WalkDir::new(root.join("docuram"))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_file())
+            .map(|e| e.path().strip_prefix(&root).unwrap().to_string_lossy().to_string())
+            .filter(|path| filter.is_candidate(path))
+            .collect();
+        assert!(new_documents.is_empty(), "{:?}", new_documents);
+        assert!(!config.reconcile_state_in(&root).unwrap());
+
+        fs::remove_dir_all(&root).unwrap();
     }
 }
@@ -16,15 +16,75 @@ struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// With --verbose, also log raw HTTP response bodies (may contain tokens and document content)
+    #[arg(long, global = true, requires = "verbose")]
+    verbose_bodies: bool,
+
+    /// Append HTTP and debug logs to this file, with timestamps, instead of the terminal (debug lines need --verbose)
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<String>,
+
+    /// Only print final summaries (no per-document lines or progress bars)
+    #[arg(long, global = true)]
+    summary_only: bool,
+
+    /// Disable animated progress bars (automatic when stdout is not a terminal)
+    #[arg(long, global = true)]
+    plain: bool,
+
+    /// When to color output: auto (terminals only, off with NO_COLOR), always or never
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value = "auto")]
+    color: utils::logger::ColorMode,
+
+    /// Disable colored output (same as --color never)
+    #[arg(long, global = true, conflicts_with = "color")]
+    no_color: bool,
+
+    /// Do not follow HTTP redirects
+    #[arg(long, global = true)]
+    no_follow_redirects: bool,
+
+    /// Maximum number of same-origin HTTP redirects to follow
+    #[arg(long, global = true, value_name = "N", default_value_t = api::client::DEFAULT_MAX_REDIRECTS)]
+    max_redirects: usize,
+
+    /// Timeout in seconds for each server request (default 60, or TEAMTURBO_TIMEOUT; 0 disables)
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Number of documents downloaded at the same time by init and pull
+    #[arg(long, global = true, value_name = "N", default_value_t = api::client::DEFAULT_DOWNLOAD_CONCURRENCY)]
+    concurrency: usize,
+
+    /// Do not contact the server in list and diff; show local state only
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Do not retry requests after connection errors or 502/503/504 responses
+    #[arg(long, global = true)]
+    no_retry: bool,
+
+    /// HTTP(S) or SOCKS (socks5://, socks5h://) proxy for all requests (default: HTTPS_PROXY, HTTP_PROXY or ALL_PROXY; NO_PROXY hosts bypass it)
+    #[arg(long, global = true, value_name = "URL")]
+    proxy: Option<String>,
+
+    /// Talk to this backend instead of the server in docuram.json, e.g. staging (for testing; docuram.json is not changed)
+    #[arg(long, global = true, value_name = "URL")]
+    server: Option<String>,
+
+    /// Docuram project to work in (default: the nearest directory containing docuram.json)
+    #[arg(long, global = true, value_name = "DIR")]
+    project_dir: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Login to TeamTurbo
+    /// Login to TeamTurbo (or set TEAMTURBO_TOKEN, optionally with TEAMTURBO_SERVER, for CI)
     Login {
-        /// Server domain (subdomain or full URL, e.g., 'example' or 'https://example.com')
+        /// Server domain (subdomain or full URL, e.g., 'example' or 'https://example.com'); defaults to the default_server setting
         #[arg(short, long)]
         domain: Option<String>,
         /// Force browser authorization mode
@@ -33,14 +93,21 @@ enum Commands {
         /// Force manual token input mode
         #[arg(long)]
         manual: bool,
+        /// Log in with an existing CLI token instead of authorizing ('-' reads it from stdin)
+        #[arg(long, value_name = "TOKEN", conflicts_with_all = ["browser", "manual"])]
+        token: Option<String>,
     },
     /// Logout from TeamTurbo
     Logout,
-    /// Show current login status
-    Whoami,
+    /// Show current login status (exits with 1 when a token is expired or invalid)
+    Whoami {
+        /// Print one entry per server as a JSON array (for scripts)
+        #[arg(long)]
+        json: bool,
+    },
     /// Initialize docuram project
     Init {
-        /// Config URL to download from
+        /// Config URL to download from (a path like /docuram/categories/1/generate_config uses the default_server setting)
         #[arg(long)]
         config_url: Option<String>,
         /// Force overwrite existing files
@@ -49,36 +116,175 @@ enum Commands {
         /// Skip downloading documents
         #[arg(long)]
         no_download: bool,
+        /// Do not create .docuram/.gitignore
+        #[arg(long)]
+        no_gitignore: bool,
+        /// Add the category to the existing checkout instead of overwriting it
+        #[arg(long, requires = "config_url", conflicts_with = "force")]
+        merge: bool,
+        /// Create the project in this directory (created if missing) instead of the current one
+        #[arg(long, value_name = "DIR", conflicts_with = "project_dir")]
+        output_dir: Option<String>,
     },
     /// Pull document updates from server
     Pull {
-        /// Specific documents to pull (by slug)
+        /// Specific documents to pull (by UUID or path)
         documents: Vec<String>,
+        /// Read UUIDs or paths to pull from a file, one per line ('-' for stdin)
+        #[arg(long, value_name = "FILE")]
+        document_list: Option<String>,
+        /// Only pull documents of this type (can be repeated)
+        #[arg(long = "document-type", value_name = "TYPE")]
+        document_types: Vec<String>,
         /// Force overwrite local changes
         #[arg(short, long)]
         force: bool,
+        /// Overwrite local changes only if the remote version is newer than the last synced version
+        #[arg(long, conflicts_with = "force")]
+        force_with_lease: bool,
+        /// Three-way merge remote changes into locally modified documents, marking overlapping edits
+        #[arg(long, conflicts_with_all = ["force", "force_with_lease", "to"])]
+        merge: bool,
+        /// Treat files that differ only in line endings as unmodified and update their stored checksum
+        #[arg(long)]
+        heal: bool,
+        /// Only download documents that are new on the server; existing ones are not checked
+        #[arg(long, conflicts_with = "to")]
+        only_new: bool,
+        /// Write documents into this directory instead of the working tree (docuram.json is not modified)
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["force", "force_with_lease"])]
+        to: Option<String>,
+        /// Only process documents whose local path matches this glob (e.g. 'organic/**'; repeatable)
+        #[arg(long, value_name = "GLOB")]
+        include: Vec<String>,
+        /// Skip documents whose local path matches this glob (repeatable, applied after --include)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
     },
     /// Push new documents to server
     Push {
-        /// Specific documents to push (by path)
+        /// Specific documents to push (by UUID or path)
         documents: Vec<String>,
+        /// Read UUIDs or paths to push from a file, one per line ('-' for stdin), e.g. from 'diff --name-only'
+        #[arg(long, value_name = "FILE")]
+        document_list: Option<String>,
         /// Commit message
         #[arg(short, long)]
         message: Option<String>,
+        /// Read the commit message from a file ('-' for stdin)
+        #[arg(long, value_name = "FILE", conflicts_with = "message")]
+        message_file: Option<String>,
+        /// Prompt for a separate commit message for each modified document (blank uses --message)
+        #[arg(long)]
+        per_document: bool,
+        /// Ignore front matter when detecting modified documents
+        #[arg(long)]
+        content_only: bool,
+        /// Rename local files to match updated document titles
+        #[arg(long)]
+        rename_on_title_change: bool,
+        /// Skip files larger than this (e.g. 5MB, 512KB)
+        #[arg(long, value_name = "SIZE", default_value = utils::DEFAULT_MAX_FILE_SIZE, value_parser = utils::parse_size)]
+        max_file_size: u64,
+        /// Show what would be deleted, updated and created without changing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Only process documents whose local path matches this glob (e.g. 'organic/**'; repeatable)
+        #[arg(long, value_name = "GLOB")]
+        include: Vec<String>,
+        /// Skip documents whose local path matches this glob (repeatable, applied after --include)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
+        /// Fail instead of warning when a new document would not be created in its directory's category
+        #[arg(long)]
+        strict: bool,
     },
     /// Sync documents (pull then push)
     Sync {
         /// Force overwrite conflicts
         #[arg(short, long)]
         force: bool,
+        /// Resolve documents changed on both sides: keep local (ours), take remote (theirs), write both with merge markers (manual), or three-way merge (merge)
+        #[arg(long, value_enum, conflicts_with = "force")]
+        strategy: Option<commands::pull::ConflictStrategy>,
+        /// Mirror deletions completely: also remove files left behind by deleted documents and summarize deletions in both directions
+        #[arg(long)]
+        prune: bool,
+    },
+    /// Summarize local changes (exits with 1 when there is something to push)
+    Status {
+        /// Also check the server for newer versions
+        #[arg(long)]
+        remote: bool,
     },
     /// Show diff between local and remote
     Diff {
-        /// Specific document to diff (by slug)
+        /// Specific document to diff (by slug, or a file path with --from)
         document: Option<String>,
+        /// Ignore front matter when detecting modified documents
+        #[arg(long)]
+        content_only: bool,
+        /// Show a unified diff against an earlier copy (a file, or a directory mirroring the project such as .docuram/quarantine)
+        #[arg(long, value_name = "PATH", requires = "document")]
+        from: Option<String>,
+        /// Only print the paths of modified documents, one per line (for push --document-list)
+        #[arg(long, conflicts_with = "from")]
+        name_only: bool,
+        /// Only print insertion/deletion counts for modified documents
+        #[arg(long, conflicts_with_all = ["from", "name_only"])]
+        stat: bool,
+        /// Compare the document against this server version instead of the latest
+        #[arg(long, value_name = "N", requires = "document", conflicts_with_all = ["from", "name_only"])]
+        version: Option<i64>,
     },
     /// List all documents with version information
-    List,
+    List {
+        /// Show every category from the category tree, even if empty
+        #[arg(long, conflicts_with = "hide_empty")]
+        include_empty_categories: bool,
+        /// Hide the standard directories (organic, impl, req, manual) when empty
+        #[arg(long)]
+        hide_empty: bool,
+        /// Redraw the tree whenever local documents change
+        #[arg(long)]
+        watch: bool,
+        /// Print the documents as a JSON array (for scripts)
+        #[arg(long, conflicts_with_all = ["watch", "include_empty_categories", "hide_empty"])]
+        json: bool,
+        /// Only show documents synced since then: a duration (30m, 12h, 7d, 2w) or a date (2024-01-01)
+        #[arg(long, value_name = "TIME", value_parser = utils::parse_since)]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        /// With --since, show documents not synced in that window instead (never synced ones included)
+        #[arg(long, requires = "since")]
+        stale: bool,
+    },
+    /// Show the local document tree (offline, no server calls), or the server's category tree with --remote
+    Tree {
+        /// Use plain ASCII characters instead of box-drawing characters and emoji
+        #[arg(long)]
+        ascii: bool,
+        /// Maximum directory depth to display
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Show every category on the server with document counts, including ones not pulled
+        #[arg(long)]
+        remote: bool,
+    },
+    /// Print a JSON manifest of all tracked documents
+    Manifest {
+        /// Refresh document versions from the server
+        #[arg(long)]
+        remote: bool,
+    },
+    /// Bundle the local copies of all tracked documents into one file
+    Export {
+        /// Bundle layout
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: commands::export::ExportFormat,
+        /// File to write the bundle to
+        #[arg(short, long, value_name = "FILE")]
+        output: String,
+    },
     /// Import documents from a git repository or local directory
     Import {
         /// Paths to import (files or directories). If provided, converts in-place.
@@ -89,6 +295,30 @@ enum Commands {
         /// Target category path - use with --from for remote import
         #[arg(long)]
         to: Option<String>,
+        /// Document type used when the documents are pushed (overrides project default)
+        #[arg(long)]
+        doc_type: Option<String>,
+        /// Priority used when the documents are pushed (overrides project default)
+        #[arg(long)]
+        priority: Option<i64>,
+        /// Keep at most N source directory levels as subcategories; deeper levels are folded into the filename
+        #[arg(long, value_name = "N", requires = "from")]
+        flatten_depth: Option<usize>,
+        /// Import each directory containing an index file (index.md or README.md) as one document
+        #[arg(long, requires = "to")]
+        dir_as_category: bool,
+        /// Index file name used by --dir-as-category (can be repeated)
+        #[arg(long, value_name = "NAME", requires = "dir_as_category")]
+        index_file: Vec<String>,
+        /// Skip files larger than this (e.g. 5MB, 512KB)
+        #[arg(long, value_name = "SIZE", default_value = utils::DEFAULT_MAX_FILE_SIZE, value_parser = utils::parse_size)]
+        max_file_size: u64,
+        /// Create the target category directory if it does not exist
+        #[arg(short, long)]
+        parents: bool,
+        /// Also import these formats (comma-separated): txt as is, rst converted to markdown
+        #[arg(long, value_name = "EXT", value_enum, value_delimiter = ',', requires = "from")]
+        include_ext: Vec<commands::import::SourceFormat>,
     },
     /// Delete documents or directories
     Delete {
@@ -97,33 +327,124 @@ enum Commands {
         /// Force deletion without confirmation
         #[arg(short, long)]
         force: bool,
+        /// Delete from the server on next push but keep the local files
+        #[arg(long, conflicts_with = "local_only")]
+        server_only: bool,
+        /// Delete local files only, never from the server
+        #[arg(long)]
+        local_only: bool,
+        /// List what would be deleted without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move a document to another category
+    Move {
+        /// Local path of the document (e.g. docuram/organic/notes.md)
+        path: String,
+        /// Destination category, relative to the working category (e.g. impl/feature)
+        category: String,
+    },
+    /// Remove local files that docuram.json no longer references (dependency files and leftovers of server-deleted documents)
+    Clean {
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
     },
     /// Send feedback to document authors or category creators
     Feedback {
-        /// Document or category UUIDs
-        #[arg(required = true)]
+        /// Document or category UUIDs (more than 10 are sent in batches, up to 100)
+        #[arg(required_unless_present = "category")]
         targets: Vec<String>,
+        /// Also send to every document in this category (relative to the working category or a full path; counts towards the 100-target limit)
+        #[arg(long, value_name = "PATH")]
+        category: Option<String>,
         /// Feedback message content
         #[arg(short, long)]
         message: String,
+        /// Thread ID to reply to (attach the message to an existing discussion)
+        #[arg(long, value_name = "THREAD_ID")]
+        reply_to: Option<String>,
     },
     /// Upgrade teamturbo CLI to the latest version
     Upgrade {
         /// Force upgrade without confirmation
         #[arg(short, long)]
         force: bool,
+        /// Restore the previous version from the backup an interrupted upgrade left behind
+        #[arg(long, conflicts_with = "force")]
+        rollback: bool,
     },
     /// Add a new organic document (req or bug)
     Add {
-        /// Document type: 'req' for requirement or 'bug' for bug report
-        #[arg(value_name = "TYPE")]
-        doc_type: String,
+        /// Document type; the header comes from docuram/templates/<TYPE>.md when present
+        #[arg(value_name = "TYPE", value_enum, ignore_case = true)]
+        doc_type: commands::add::DocType,
         /// Document title (optional)
         #[arg(short, long)]
         title: Option<String>,
+        /// Server document type used when pushed (overrides project default)
+        #[arg(long = "doc-type")]
+        server_doc_type: Option<String>,
+        /// Priority used when pushed (overrides project default)
+        #[arg(long)]
+        priority: Option<i64>,
+        /// Track the document in docuram.json now (provisional UUID, replaced on push)
+        #[arg(long)]
+        register: bool,
+        /// Append the contents of this file after the header (e.g. a crash log)
+        #[arg(long, value_name = "PATH", conflicts_with = "from_stdin")]
+        from_file: Option<String>,
+        /// Append content read from stdin after the header
+        #[arg(long)]
+        from_stdin: bool,
+        /// Subcategory of the working category to file the document under, e.g. impl/feature (default: organic)
+        #[arg(long, value_name = "PATH")]
+        category: Option<String>,
+        /// Create the category directory if it does not exist
+        #[arg(short, long)]
+        parents: bool,
+        /// Open the new document in $VISUAL or $EDITOR and wait for it to close
+        #[arg(long)]
+        open: bool,
+    },
+    /// Preview documents as HTML in the browser (reloads on change)
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = commands::serve::DEFAULT_PORT)]
+        port: u16,
+        /// Do not open the browser
+        #[arg(long)]
+        no_open: bool,
     },
     /// Verify docuram project structure and document integrity
-    Verify,
+    Verify {
+        /// Repair what is safe: create missing directories and req/ stubs, re-download missing dependencies, move orphaned files to .docuram/quarantine/
+        #[arg(long)]
+        fix: bool,
+        /// Rewrite markdown files to LF line endings without BOM
+        #[arg(long)]
+        fix_encoding: bool,
+    },
+    /// Inspect CLI configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the effective configuration and where each value comes from
+    Dump,
+    /// Set a value in ~/.teamturbo-cli/config.toml (default_server, user_agent)
+    Set {
+        key: String,
+        value: String,
+    },
+    /// Remove a value from ~/.teamturbo-cli/config.toml
+    Unset {
+        key: String,
+    },
 }
 
 #[tokio::main]
@@ -132,61 +453,192 @@ async fn main() -> Result<()> {
 
     // Initialize verbose mode
     utils::logger::init(cli.verbose);
+    utils::logger::set_verbose_bodies(cli.verbose_bodies);
+    utils::logger::set_summary_only(cli.summary_only);
+    utils::logger::set_plain(cli.plain);
+    utils::logger::set_color(if cli.no_color { utils::logger::ColorMode::Never } else { cli.color });
+    if let Some(log_file) = &cli.log_file {
+        utils::logger::set_log_file(log_file)?;
+    }
+
+    // Configure redirect handling for all HTTP clients
+    api::client::set_max_redirects(if cli.no_follow_redirects { 0 } else { cli.max_redirects });
+    let timeout = match cli.timeout {
+        Some(secs) => secs,
+        None => api::client::timeout_from_env()?.unwrap_or(api::client::DEFAULT_REQUEST_TIMEOUT_SECS),
+    };
+    api::client::set_request_timeout(timeout);
+    api::client::set_offline(cli.offline);
+    api::client::set_retry_enabled(!cli.no_retry);
+    api::client::set_download_concurrency(cli.concurrency);
+    api::client::set_proxy(cli.proxy.clone())?;
+    if let Some(server) = &cli.server {
+        config::set_server_override(server);
+    }
+
+    // Paths given on the command line stay relative to where the CLI was started (config::user_path)
+    match &cli.command {
+        Commands::Login { .. } | Commands::Logout | Commands::Whoami { .. } | Commands::Upgrade { .. } => {}
+        // init creates a project in the current directory unless --project-dir says otherwise
+        Commands::Init { output_dir, .. } => {
+            config::enter_project_dir(output_dir.as_deref().or(cli.project_dir.as_deref()), false, true)?
+        }
+        _ => config::enter_project_dir(cli.project_dir.as_deref(), true, false)?,
+    }
+
+    // Commands that modify docuram.json or documents hold the project lock until they return
+    let project_lock = match &cli.command {
+        Commands::Init { .. } | Commands::Pull { .. } | Commands::Push { .. } | Commands::Sync { .. }
+        | Commands::Delete { .. } | Commands::Add { .. } | Commands::Import { .. } | Commands::Move { .. }
+        | Commands::Clean { .. } => Some(config::ProjectLock::acquire()?),
+        Commands::Verify { fix, fix_encoding } if *fix || *fix_encoding => Some(config::ProjectLock::acquire()?),
+        _ => None,
+    };
 
     match cli.command {
-        Commands::Login { domain, browser, manual } => {
-            commands::login::execute(domain, browser, manual).await?;
+        Commands::Login { domain, browser, manual, token } => {
+            commands::login::execute(domain, browser, manual, token).await?;
         }
         Commands::Logout => {
             commands::logout::execute().await?;
         }
-        Commands::Whoami => {
-            commands::whoami::execute().await?;
+        Commands::Whoami { json } => {
+            if !commands::whoami::execute(json).await? {
+                std::process::exit(1);
+            }
         }
-        Commands::Init { config_url, force, no_download } => {
-            commands::init::execute(config_url, force, no_download).await?;
+        Commands::Init { config_url, force, no_download, no_gitignore, merge, .. } => {
+            commands::init::execute(config_url, force, no_download, no_gitignore, merge).await?;
         }
-        Commands::Pull { documents, force } => {
-            commands::pull::execute(documents, force).await?;
+        Commands::Pull { documents, document_list, document_types, force, force_with_lease, merge, heal, only_new, to, include, exclude } => {
+            let options = commands::pull::PullOptions {
+                document_types,
+                force,
+                force_with_lease,
+                heal,
+                only_new,
+                to: to.as_deref().map(config::user_path),
+                document_list: document_list.as_deref().map(config::user_path),
+                conflict_strategy: merge.then_some(commands::pull::ConflictStrategy::Merge),
+                filter: utils::PathFilter::new(&include, &exclude)?,
+            };
+            let documents = documents.iter().map(|d| config::user_path_if_exists(d)).collect();
+            commands::pull::execute(documents, options).await?;
         }
-        Commands::Push { documents, message } => {
-            commands::push::execute(documents, message).await?;
+        Commands::Push { documents, document_list, message, message_file, per_document, content_only, rename_on_title_change, max_file_size, dry_run, include, exclude, strict } => {
+            if message_file.as_deref() == Some("-") && document_list.as_deref() == Some("-") {
+                anyhow::bail!("--message-file and --document-list cannot both read from stdin");
+            }
+            let message = match message_file {
+                Some(path) => Some(utils::read_message(&config::user_path(&path))?),
+                None => message,
+            };
+            let options = commands::push::PushOptions {
+                document_list: document_list.as_deref().map(config::user_path),
+                message,
+                per_document,
+                content_only,
+                rename_on_title_change,
+                max_file_size,
+                dry_run,
+                filter: utils::PathFilter::new(&include, &exclude)?,
+                strict,
+            };
+            let documents = documents.iter().map(|d| config::user_path_if_exists(d)).collect();
+            commands::push::execute(documents, options).await?;
         }
-        Commands::Sync { force } => {
-            commands::sync::execute(force).await?;
+        Commands::Sync { force, strategy, prune } => {
+            commands::sync::execute(force, strategy, prune).await?;
         }
-        Commands::Diff { document } => {
-            commands::diff::execute(document).await?;
+        Commands::Status { remote } => {
+            if !commands::status::execute(remote).await? {
+                std::process::exit(1);
+            }
         }
-        Commands::List => {
-            commands::list::execute().await?;
+        Commands::Diff { document, content_only, from, name_only, stat, version } => {
+            let document = document.as_deref().map(config::user_path_if_exists);
+            let from = from.as_deref().map(config::user_path);
+            commands::diff::execute(document, content_only, from, name_only, stat, version).await?;
         }
-        Commands::Import { paths, from, to } => {
-            commands::import::execute(paths, from, to).await?;
+        Commands::List { include_empty_categories, hide_empty, watch, json, since, stale } => {
+            let sync_filter = since.map(|since| commands::list::SyncFilter { since, stale });
+            commands::list::execute(include_empty_categories, hide_empty, watch, json, sync_filter).await?;
         }
-        Commands::Delete { paths, force } => {
-            commands::delete::execute(paths, force, cli.verbose).await?;
+        Commands::Tree { ascii, depth, remote } => {
+            commands::tree::execute(ascii, depth, remote).await?;
         }
-        Commands::Feedback { targets, message } => {
-            commands::feedback::execute(targets, message, cli.verbose).await?;
+        Commands::Manifest { remote } => {
+            commands::manifest::execute(remote).await?;
         }
-        Commands::Upgrade { force } => {
-            commands::upgrade::execute(force).await?;
+        Commands::Export { format, output } => {
+            commands::export::execute(format, config::user_path(&output))?;
         }
-        Commands::Add { doc_type, title } => {
-            let dtype = match doc_type.to_lowercase().as_str() {
-                "req" => commands::add::DocType::Req,
-                "bug" => commands::add::DocType::Bug,
-                _ => {
-                    eprintln!("Error: Invalid document type '{}'. Use 'req' or 'bug'.", doc_type);
-                    std::process::exit(1);
+        Commands::Import { paths, from, to, doc_type, priority, flatten_depth, dir_as_category, index_file, max_file_size, parents, include_ext } => {
+            let overrides = config::DocumentDefaults { doc_type, priority };
+            let index_files = dir_as_category.then(|| {
+                if index_file.is_empty() {
+                    vec!["index.md".to_string(), "README.md".to_string()]
+                } else {
+                    index_file
                 }
+            });
+            let options = commands::import::ImportOptions {
+                from: from.as_deref().map(config::user_path_if_exists),
+                to,
+                overrides,
+                flatten_depth,
+                index_files,
+                max_file_size,
+                parents,
+                include_ext,
+            };
+            let paths = paths.iter().map(|p| config::user_path(p)).collect();
+            commands::import::execute(paths, options).await?;
+        }
+        Commands::Delete { paths, force, server_only, local_only, dry_run } => {
+            let paths = paths.iter().map(|p| config::user_path(p)).collect();
+            commands::delete::execute(paths, force, server_only, local_only, dry_run, cli.verbose).await?;
+        }
+        Commands::Move { path, category } => {
+            commands::mv::execute(config::user_path(&path), category).await?;
+        }
+        Commands::Clean { force } => {
+            commands::clean::execute(force)?;
+        }
+        Commands::Feedback { targets, category, message, reply_to } => {
+            commands::feedback::execute(targets, category, message, reply_to, cli.verbose).await?;
+        }
+        Commands::Upgrade { force, rollback } => {
+            commands::upgrade::execute(force, rollback).await?;
+        }
+        Commands::Add { doc_type, title, server_doc_type, priority, register, from_file, from_stdin, category, parents, open } => {
+            let options = commands::add::AddOptions {
+                title,
+                overrides: config::DocumentDefaults { doc_type: server_doc_type, priority },
+                register,
+                from_file: from_file.as_deref().map(config::user_path),
+                from_stdin,
+                category,
+                parents,
             };
-            commands::add::execute(dtype, title).await?;
+            let file_path = commands::add::execute(doc_type, options).await?;
+            if open {
+                // Other commands may run while the document is being edited
+                drop(project_lock);
+                commands::add::open_in_editor(&file_path);
+            }
+        }
+        Commands::Serve { port, no_open } => {
+            commands::serve::execute(port, no_open).await?;
         }
-        Commands::Verify => {
-            commands::verify::execute().await?;
+        Commands::Verify { fix, fix_encoding } => {
+            commands::verify::execute(fix, fix_encoding).await?;
         }
+        Commands::Config { action } => match action {
+            ConfigAction::Dump => commands::config::dump().await?,
+            ConfigAction::Set { key, value } => commands::config::set(&key, Some(value))?,
+            ConfigAction::Unset { key } => commands::config::set(&key, None)?,
+        },
     }
 
     Ok(())
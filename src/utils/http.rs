@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use console::style;
+use reqwest::{redirect, Client};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use crate::utils::logger;
+
+/// Default maximum number of same-origin redirects to follow
+pub const DEFAULT_MAX_REDIRECTS: usize = 5;
+
+static MAX_REDIRECTS: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_REDIRECTS);
+
+/// Default request timeout in seconds
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 60;
+
+/// Environment variable that overrides the default timeout (--timeout takes precedence)
+pub const TIMEOUT_ENV_VAR: &str = "TEAMTURBO_TIMEOUT";
+
+/// Request timeout in seconds (0 means no timeout)
+static REQUEST_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_REQUEST_TIMEOUT_SECS);
+
+/// Set the timeout applied to every request (0 disables the timeout)
+pub fn set_request_timeout(secs: u64) {
+    REQUEST_TIMEOUT_SECS.store(secs, Ordering::Relaxed);
+}
+
+/// Timeout in seconds from TEAMTURBO_TIMEOUT, if set
+pub fn timeout_from_env() -> Result<Option<u64>> {
+    match std::env::var(TIMEOUT_ENV_VAR) {
+        Ok(value) if !value.trim().is_empty() => {
+            let secs = value.trim().parse()
+                .with_context(|| format!("Invalid {} value '{}' (expected seconds)", TIMEOUT_ENV_VAR, value))?;
+            Ok(Some(secs))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub fn request_timeout() -> Option<Duration> {
+    match REQUEST_TIMEOUT_SECS.load(Ordering::Relaxed) {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// Set the maximum number of redirects to follow (0 disables redirects)
+pub fn set_max_redirects(max: usize) {
+    MAX_REDIRECTS.store(max, Ordering::Relaxed);
+}
+
+pub fn max_redirects() -> usize {
+    MAX_REDIRECTS.load(Ordering::Relaxed)
+}
+
+/// Turn a failed send into an error, spelling out timeouts
+pub fn request_error(e: reqwest::Error) -> anyhow::Error {
+    match request_timeout() {
+        Some(timeout) if e.is_timeout() => anyhow::anyhow!("request timed out after {}s", timeout.as_secs()),
+        _ => e.into(),
+    }
+}
+
+/// Proxy environment variables, in order of precedence
+pub const PROXY_ENV_VARS: [&str; 6] = ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"];
+
+/// Proxy every request goes through, set once at startup by `set_proxy`
+static PROXY: OnceLock<Option<String>> = OnceLock::new();
+
+/// First proxy URL set in the environment
+pub fn proxy_from_env() -> Option<String> {
+    PROXY_ENV_VARS.iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+}
+
+/// Route all requests through `proxy` (--proxy), falling back to the proxy environment variables
+/// Hosts listed in NO_PROXY are still reached directly.
+pub fn set_proxy(proxy: Option<String>) -> Result<()> {
+    let proxy = proxy.or_else(proxy_from_env);
+    if let Some(url) = &proxy {
+        // Fail at startup rather than on the first request
+        reqwest::Proxy::all(url.as_str())
+            .with_context(|| format!("Invalid proxy URL '{}'", url))?;
+    }
+    let _ = PROXY.set(proxy);
+    Ok(())
+}
+
+pub fn proxy() -> Option<&'static str> {
+    PROXY.get().and_then(|proxy| proxy.as_deref())
+}
+
+/// Per-client settings on top of the shared User-Agent, timeout and proxy
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ClientOptions {
+    /// Follow only same-origin redirects, up to --max-redirects (see `redirect_policy`);
+    /// otherwise reqwest's default policy applies (release downloads redirect to a CDN)
+    pub same_origin_redirects: bool,
+    /// Accept self-signed certificates
+    pub accept_invalid_certs: bool,
+}
+
+/// Build an HTTP client; every client in the CLI is created here
+pub fn build_client(options: ClientOptions) -> reqwest::Result<Client> {
+    let mut builder = http_client_builder();
+    if options.same_origin_redirects {
+        builder = builder.redirect(redirect_policy());
+    }
+    if options.accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    builder.build()
+}
+
+/// Client builder with the shared User-Agent, request timeout and proxy
+fn http_client_builder() -> reqwest::ClientBuilder {
+    let mut builder = Client::builder().user_agent(user_agent());
+    if let Some(timeout) = request_timeout() {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(url) = proxy() {
+        let proxy = reqwest::Proxy::all(url).expect("proxy URL validated by set_proxy")
+            .no_proxy(reqwest::NoProxy::from_env());
+        builder = builder.proxy(proxy);
+    }
+    builder
+}
+
+static USER_AGENT: OnceLock<String> = OnceLock::new();
+
+/// Default User-Agent, e.g. "teamturbo-cli/0.1.0 (linux; x86_64)"
+pub fn default_user_agent() -> String {
+    format!("teamturbo-cli/{} ({}; {})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+/// User-Agent sent with every request
+/// `user_agent` in ~/.teamturbo-cli/config.toml overrides the default (e.g. for proxies that key on it)
+pub fn user_agent() -> &'static str {
+    USER_AGENT.get_or_init(|| {
+        crate::config::CliConfig::load()
+            .ok()
+            .and_then(|config| config.user_agent)
+            .filter(|ua| !ua.trim().is_empty())
+            .unwrap_or_else(default_user_agent)
+    })
+}
+
+/// Redirect policy shared by all authenticated clients
+/// Only same-origin redirects are followed, so the Authorization header never leaks to another host.
+/// Cross-origin redirects (e.g. http -> https, or a different host) are reported and not followed.
+fn redirect_policy() -> redirect::Policy {
+    let max = MAX_REDIRECTS.load(Ordering::Relaxed);
+
+    redirect::Policy::custom(move |attempt| {
+        let from = attempt.previous().last().cloned();
+        let to = attempt.url().clone();
+
+        if max == 0 {
+            eprintln!("{} Redirect to {} not followed (redirects disabled)",
+                style("⚠").yellow(), to);
+            return attempt.stop();
+        }
+
+        if attempt.previous().len() > max {
+            return attempt.error(format!("Too many redirects (maximum {})", max));
+        }
+
+        if let Some(from) = from {
+            if from.origin() != to.origin() {
+                eprintln!("{} Server redirected {} to a different origin: {}",
+                    style("⚠").yellow(), from, to);
+                eprintln!("{}", style("  Not following to protect credentials. Check the configured server URL.").dim());
+                return attempt.stop();
+            }
+        }
+
+        logger::debug("redirect", &format!("Following redirect to {}", to));
+        attempt.follow()
+    })
+}
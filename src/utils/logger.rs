@@ -1,7 +1,23 @@
-use console::style;
+use anyhow::{Context, Result};
+use chrono::Local;
+use console::{style, Term};
+use indicatif::ProgressBar;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
 
 static VERBOSE: AtomicBool = AtomicBool::new(false);
+static SUMMARY_ONLY: AtomicBool = AtomicBool::new(false);
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static VERBOSE_BODIES: AtomicBool = AtomicBool::new(false);
+static LOG_FILE: OnceLock<LogFile> = OnceLock::new();
+
+/// Destination of --log-file
+struct LogFile {
+    path: String,
+    file: Mutex<File>,
+}
 
 /// Initialize logger with verbose mode
 pub fn init(verbose: bool) {
@@ -13,11 +29,149 @@ pub fn is_verbose() -> bool {
     VERBOSE.load(Ordering::Relaxed)
 }
 
+/// Include raw HTTP response bodies in verbose logs (--verbose-bodies)
+pub fn set_verbose_bodies(enabled: bool) {
+    VERBOSE_BODIES.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if response bodies may be logged; they can contain tokens and document content
+pub fn is_verbose_bodies() -> bool {
+    is_verbose() && VERBOSE_BODIES.load(Ordering::Relaxed)
+}
+
+/// Enable summary-only mode (suppress per-item lines and progress bars)
+pub fn set_summary_only(summary_only: bool) {
+    SUMMARY_ONLY.store(summary_only, Ordering::Relaxed);
+}
+
+/// Check if summary-only mode is enabled
+pub fn is_summary_only() -> bool {
+    SUMMARY_ONLY.load(Ordering::Relaxed)
+}
+
+/// When to emit ANSI colors (--color)
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ColorMode {
+    /// Only on a terminal, and not when NO_COLOR is set
+    Auto,
+    Always,
+    Never,
+}
+
+/// Turn colors on or off for console and colored output
+/// Auto decides per stream, so `teamturbo pull > log` keeps colored errors on the terminal.
+pub fn set_color(mode: ColorMode) {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let (stdout, stderr) = match mode {
+        ColorMode::Always => (true, true),
+        ColorMode::Never => (false, false),
+        ColorMode::Auto if no_color_env => (false, false),
+        ColorMode::Auto => (Term::stdout().is_term(), Term::stderr().is_term()),
+    };
+    console::set_colors_enabled(stdout);
+    console::set_colors_enabled_stderr(stderr);
+    colored::control::set_override(stdout);
+}
+
+/// Force plain output (no animated progress bars)
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Check if plain output was requested with --plain
+pub fn is_plain_forced() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Check if plain output is in effect: forced, or stdout is not a terminal (CI, pipes)
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed) || !Term::stdout().is_term()
+}
+
+/// Create a progress bar, hidden in summary-only or plain mode
+pub fn progress_bar(len: u64) -> ProgressBar {
+    if is_summary_only() || is_plain() {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new(len)
+    }
+}
+
+/// Print a per-item line, suppressed in summary-only mode
+#[macro_export]
+macro_rules! item {
+    ($($arg:tt)*) => {
+        if !$crate::utils::logger::is_summary_only() {
+            $crate::outln!($($arg)*);
+        }
+    };
+}
+
+/// Severity of a diagnostic line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    /// HTTP traffic: always written to --log-file, shown on the terminal with --verbose
+    Info,
+    /// Internal details: only with --verbose, whatever the sink
+    Debug,
+}
+
+impl Level {
+    fn enabled(self) -> bool {
+        match self {
+            Level::Info => is_verbose() || LOG_FILE.get().is_some(),
+            Level::Debug => is_verbose(),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        }
+    }
+}
+
+/// Send diagnostics to `path` (appended, one timestamped line each) instead of stderr
+pub fn set_log_file(path: &str) -> Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file: {}", path))?;
+    let _ = LOG_FILE.set(LogFile { path: path.to_string(), file: Mutex::new(file) });
+    Ok(())
+}
+
+/// Path given with --log-file, if any
+pub fn log_file() -> Option<&'static str> {
+    LOG_FILE.get().map(|log_file| log_file.path.as_str())
+}
+
+/// Write a diagnostic line to the log file, or to stderr when there is none
+/// `styled` is what the terminal shows; the file gets `plain` behind a timestamp and level.
+pub fn log(level: Level, styled: impl std::fmt::Display, plain: &str) {
+    if !level.enabled() {
+        return;
+    }
+    match LOG_FILE.get() {
+        Some(log_file) => {
+            let mut file = log_file.file.lock().unwrap_or_else(|e| e.into_inner());
+            for line in plain.lines() {
+                let _ = writeln!(file, "{} {:<5} {}", Local::now().to_rfc3339(), level.label(), line);
+            }
+        }
+        None => eprintln!("{}", styled),
+    }
+}
+
 /// Print verbose log message
 pub fn verbose(message: &str) {
-    if is_verbose() {
-        eprintln!("{} {}", style("[VERBOSE]").dim(), style(message).dim());
-    }
+    log(
+        Level::Debug,
+        format!("{} {}", style("[VERBOSE]").dim(), style(message).dim()),
+        message,
+    );
 }
 
 /// Print verbose log with formatted arguments
@@ -25,51 +179,42 @@ pub fn verbose(message: &str) {
 macro_rules! verbose {
     ($($arg:tt)*) => {
         if $crate::utils::logger::is_verbose() {
-            eprintln!("{} {}", console::style("[VERBOSE]").dim(), console::style(format!($($arg)*)).dim());
+            $crate::utils::logger::verbose(&format!($($arg)*));
         }
     };
 }
 
 /// Print HTTP request details
 pub fn http_request(method: &str, url: &str) {
-    if is_verbose() {
-        eprintln!(
-            "{} {} {}",
-            style("[HTTP]").cyan().dim(),
-            style(method).bold().dim(),
-            style(url).dim()
-        );
-    }
+    log(
+        Level::Info,
+        format!("{} {} {}", style("[HTTP]").cyan().dim(), style(method).bold().dim(), style(url).dim()),
+        &format!("[HTTP] {} {}", method, url),
+    );
 }
 
 /// Print HTTP response details
 pub fn http_response(status: u16, url: &str) {
-    if is_verbose() {
-        let status_str = if status >= 200 && status < 300 {
-            style(status).green().dim()
-        } else if status >= 400 {
-            style(status).red().dim()
-        } else {
-            style(status).yellow().dim()
-        };
-
-        eprintln!(
-            "{} {} {}",
-            style("[HTTP]").cyan().dim(),
-            status_str,
-            style(url).dim()
-        );
-    }
+    let status_str = if (200..300).contains(&status) {
+        style(status).green().dim()
+    } else if status >= 400 {
+        style(status).red().dim()
+    } else {
+        style(status).yellow().dim()
+    };
+
+    log(
+        Level::Info,
+        format!("{} {} {}", style("[HTTP]").cyan().dim(), status_str, style(url).dim()),
+        &format!("[HTTP] {} {}", status, url),
+    );
 }
 
 /// Print debug information about operation
 pub fn debug(context: &str, message: &str) {
-    if is_verbose() {
-        eprintln!(
-            "{} [{}] {}",
-            style("[DEBUG]").blue().dim(),
-            style(context).yellow().dim(),
-            style(message).dim()
-        );
-    }
+    log(
+        Level::Debug,
+        format!("{} [{}] {}", style("[DEBUG]").blue().dim(), style(context).yellow().dim(), style(message).dim()),
+        &format!("[{}] {}", context, message),
+    );
 }
@@ -1,9 +1,151 @@
+pub mod http;
 pub mod logger;
+pub mod output;
+pub mod rst;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use sha2::{Digest, Sha256};
 use std::fs;
+use std::io::Write;
 use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::config::{DocumentDefaults, DocuramConfig, ProjectConfig};
+
+/// Built-in document type for new documents
+pub const DEFAULT_DOC_TYPE: &str = "knowledge";
+
+/// Built-in priority for new documents
+pub const DEFAULT_PRIORITY: i64 = 0;
+
+/// Metadata sent to the server when a local document is first created
+/// (documents themselves stay pure markdown)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontMatter {
+    pub category: String,
+    pub title: String,
+    pub doc_type: String,
+    pub priority: i64,
+}
+
+/// Build metadata for a new document
+/// Precedence: explicit overrides, then .docuram/config.toml defaults, then built-in defaults
+pub fn default_front_matter(category: &str, title: &str, overrides: &DocumentDefaults) -> Result<FrontMatter> {
+    let project_config = ProjectConfig::load()?;
+    Ok(resolve_front_matter(category, title, overrides, &project_config.defaults))
+}
+
+fn resolve_front_matter(
+    category: &str,
+    title: &str,
+    overrides: &DocumentDefaults,
+    defaults: &DocumentDefaults,
+) -> FrontMatter {
+    FrontMatter {
+        category: category.to_string(),
+        title: title.to_string(),
+        doc_type: overrides.doc_type.clone()
+            .or_else(|| defaults.doc_type.clone())
+            .unwrap_or_else(|| DEFAULT_DOC_TYPE.to_string()),
+        priority: overrides.priority
+            .or(defaults.priority)
+            .unwrap_or(DEFAULT_PRIORITY),
+    }
+}
+
+/// Ignore file (gitignore syntax) at the project root, applied when scanning for documents
+pub const IGNORE_FILE: &str = ".docuramignore";
+
+/// Standard subdirectories of docuram/: organic (user-maintained req/bug documents), req
+/// (extended requirements), impl (implementation documents) and manual (user guides)
+/// New documents in them are created in the matching subcategory of the working category.
+pub const STANDARD_DIRS: [&str; 4] = ["organic", "req", "impl", "manual"];
+
+/// Per-type header templates for `add`; never pushed as documents
+pub const TEMPLATES_DIR: &str = "docuram/templates";
+
+static IGNORE_RULES: OnceLock<Gitignore> = OnceLock::new();
+
+/// Load .docuramignore from `root`; without the file only the templates directory is ignored
+/// Invalid lines are reported and skipped.
+pub fn load_ignore_rules(root: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    let _ = builder.add_line(None, &format!("/{}/", TEMPLATES_DIR));
+    let path = root.join(IGNORE_FILE);
+    if path.is_file() {
+        if let Some(e) = builder.add(&path) {
+            eprintln!("{} {}: {}", console::style("⚠").yellow(), IGNORE_FILE, e);
+        }
+    }
+    builder.build().unwrap_or_else(|e| {
+        eprintln!("{} {}: {}", console::style("⚠").yellow(), IGNORE_FILE, e);
+        Gitignore::empty()
+    })
+}
+
+/// Whether .docuramignore excludes a file
+/// `path` is relative to the project root (or absolute inside it); files outside the
+/// project are never ignored. The rules are loaded once per command.
+pub fn is_ignored(path: &Path) -> bool {
+    let rules = IGNORE_RULES.get_or_init(|| {
+        load_ignore_rules(&std::env::current_dir().unwrap_or_default())
+    });
+    matches_ignore_rules(rules, path)
+}
+
+fn matches_ignore_rules(rules: &Gitignore, path: &Path) -> bool {
+    let path = path.strip_prefix(rules.path()).unwrap_or(path);
+    let path = path.strip_prefix(".").unwrap_or(path);
+    !path.has_root() && rules.matched_path_or_any_parents(path, false).is_ignore()
+}
+
+/// `--include`/`--exclude` glob filter over local document paths
+/// A path is selected when it matches any include (or there are none) and no exclude.
+/// Patterns match the path inside docuram/ (`organic/**`) or the full local path
+/// (`docuram/organic/**`); `*` stays within one directory, `**` crosses directories.
+#[derive(Debug, Default)]
+pub struct PathFilter {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl PathFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: build_glob_set(include)?,
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+
+    /// Whether any patterns were given
+    pub fn is_active(&self) -> bool {
+        self.include.is_some() || self.exclude.is_some()
+    }
+
+    pub fn matches(&self, local_path: &str) -> bool {
+        let local_path = local_path.trim_start_matches("./");
+        let inner_path = local_path.strip_prefix("docuram/").unwrap_or(local_path);
+        let matched = |set: &GlobSet| set.is_match(local_path) || set.is_match(inner_path);
+        self.include.as_ref().is_none_or(matched) && !self.exclude.as_ref().is_some_and(matched)
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern.trim_start_matches("./"))
+            .literal_separator(true)
+            .build()
+            .with_context(|| format!("Invalid glob pattern: {}", pattern))?;
+        builder.add(glob);
+    }
+    Ok(Some(builder.build()?))
+}
 
 /// Calculate SHA-256 checksum of file content
 /// Returns checksum in format: "sha256:hexstring"
@@ -13,6 +155,53 @@ pub fn calculate_checksum(content: &str) -> String {
     format!("sha256:{:x}", hasher.finalize())
 }
 
+/// Split leading YAML front matter (`---` ... `---`) from the document body
+/// Returns (front matter, body); documents without front matter return the whole content as body.
+pub fn extract_front_matter(content: &str) -> (Option<&str>, &str) {
+    let rest = match content.strip_prefix("---\n").or_else(|| content.strip_prefix("---\r\n")) {
+        Some(rest) => rest,
+        None => return (None, content),
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let body = &rest[offset + line.len()..];
+            return (Some(&rest[..offset]), body);
+        }
+        offset += line.len();
+    }
+
+    (None, content)
+}
+
+/// Calculate SHA256 checksum of the document body only (front matter excluded)
+pub fn calculate_content_checksum(content: &str) -> String {
+    calculate_checksum(extract_front_matter(content).1)
+}
+
+/// Check whether `content` matches `checksum` once line endings are normalized
+/// Covers checkouts that crossed OS boundaries (LF <-> CRLF) without real edits.
+pub fn matches_ignoring_line_endings(content: &str, checksum: &str) -> bool {
+    let lf = content.replace("\r\n", "\n");
+    let crlf = lf.replace('\n', "\r\n");
+    checksums_match(&calculate_checksum(&lf), checksum) || checksums_match(&calculate_checksum(&crlf), checksum)
+}
+
+/// Check whether `content` matches `checksum`, treating CRLF and LF copies as identical
+/// Use this for modification checks; checksums themselves stay over the raw bytes, like the server's.
+pub fn content_matches_checksum(content: &str, checksum: &str) -> bool {
+    checksums_match(&calculate_checksum(content), checksum) || matches_ignoring_line_endings(content, checksum)
+}
+
+/// Content with a leading BOM removed and CRLF/CR line endings converted to LF
+/// Returns None when the content is already normalized
+pub fn normalize_line_endings(content: &str) -> Option<String> {
+    let without_bom = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let normalized = without_bom.replace("\r\n", "\n").replace('\r', "\n");
+    (normalized != content).then_some(normalized)
+}
+
 /// Normalize checksum format to ensure it has the "sha256:" prefix
 pub fn normalize_checksum(checksum: &str) -> String {
     if checksum.starts_with("sha256:") {
@@ -22,12 +211,66 @@ pub fn normalize_checksum(checksum: &str) -> String {
     }
 }
 
+/// Compare two checksums, accepting both "sha256:<hex>" and bare hex forms
+pub fn checksums_match(a: &str, b: &str) -> bool {
+    normalize_checksum(a).eq_ignore_ascii_case(&normalize_checksum(b))
+}
+
 /// Read file content as string
 pub fn read_file<P: AsRef<Path>>(path: P) -> Result<String> {
     let content = fs::read_to_string(path.as_ref())?;
     Ok(content)
 }
 
+/// Read a `--document-list` selection: one UUID or path per line, `-` reads stdin
+/// Blank lines and lines starting with '#' are ignored.
+pub fn read_document_list(source: &str) -> Result<Vec<String>> {
+    let content = read_source(source, "document list")?;
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Read a change summary from a file, or stdin when `source` is `-`
+/// Surrounding blank lines are dropped; an empty message is an error.
+pub fn read_message(source: &str) -> Result<String> {
+    let message = read_source(source, "message")?.trim().to_string();
+    if message.is_empty() {
+        anyhow::bail!("Message in {} is empty", if source == "-" { "stdin" } else { source });
+    }
+    Ok(message)
+}
+
+fn read_source(source: &str, what: &str) -> Result<String> {
+    if source == "-" {
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)
+            .with_context(|| format!("Failed to read {} from stdin", what))?;
+        Ok(content)
+    } else {
+        fs::read_to_string(source)
+            .with_context(|| format!("Failed to read {}: {}", what, source))
+    }
+}
+
+/// Combine positional document selectors with an optional `--document-list` and resolve paths to UUIDs
+/// Returns None when a list was given but selects nothing, so callers stop instead of
+/// treating the empty selection as "all documents".
+pub fn select_documents(config: &DocuramConfig, mut documents: Vec<String>, document_list: Option<&str>) -> Result<Option<Vec<String>>> {
+    if let Some(source) = document_list {
+        let listed = read_document_list(source)?;
+        if listed.is_empty() && documents.is_empty() {
+            return Ok(None);
+        }
+        documents.extend(listed);
+    }
+    Ok(Some(config.resolve_document_selectors(documents)))
+}
+
 /// Write content to file
 pub fn write_file<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
     // Create parent directories if they don't exist
@@ -38,6 +281,37 @@ pub fn write_file<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
     Ok(())
 }
 
+/// Replace `path` with `content` so readers see either the old or the new file, never a partial one
+/// The content goes to a temporary file in the same directory, is flushed to disk and renamed over
+/// `path`; an existing file's permissions are kept.
+pub fn write_file_atomic<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
+    let path = path.as_ref();
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let file_name = path.file_name()
+        .with_context(|| format!("Not a file path: {}", path.display()))?
+        .to_string_lossy();
+    let temp_path = dir.join(format!(".{}.tmp-{}", file_name, std::process::id()));
+
+    let result = (|| -> Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result.with_context(|| format!("Failed to write {}", path.display()))
+}
+
 /// Check if file exists and has matching checksum
 pub fn verify_checksum<P: AsRef<Path>>(path: P, expected_checksum: &str) -> Result<bool> {
     if !path.as_ref().exists() {
@@ -46,7 +320,65 @@ pub fn verify_checksum<P: AsRef<Path>>(path: P, expected_checksum: &str) -> Resu
 
     let content = read_file(path)?;
     let actual_checksum = calculate_checksum(&content);
-    Ok(actual_checksum == expected_checksum)
+    Ok(checksums_match(&actual_checksum, expected_checksum))
+}
+
+/// Sanitize filename to remove invalid characters
+pub fn sanitize_filename(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Normalize category path by removing ./docuram/ or docuram/ prefix and trailing slashes
+pub fn normalize_category_path(path: &str) -> String {
+    let trimmed = path.trim();
+
+    let mut result = trimmed;
+
+    // Remove ./docuram/ prefix
+    if let Some(stripped) = result.strip_prefix("./docuram/") {
+        result = stripped;
+    }
+    // Remove docuram/ prefix
+    else if let Some(stripped) = result.strip_prefix("docuram/") {
+        result = stripped;
+    }
+    // Remove ./ prefix
+    else if let Some(stripped) = result.strip_prefix("./") {
+        result = stripped;
+    }
+
+    // Remove trailing slashes
+    result.trim_end_matches('/').to_string()
+}
+
+/// Make sure a local category directory exists
+/// With `parents` missing directories are created like `mkdir -p`; otherwise the first missing one is reported.
+/// Returns true when the directory was created.
+pub fn ensure_category_dir(dir: &Path, parents: bool) -> Result<bool> {
+    if dir.is_dir() {
+        return Ok(false);
+    }
+
+    if !parents {
+        let missing = dir.ancestors()
+            .filter(|a| !a.as_os_str().is_empty() && !a.exists())
+            .last()
+            .unwrap_or(dir);
+        anyhow::bail!(
+            "Directory '{}' does not exist (missing: {}). Use --parents to create it.",
+            dir.display(),
+            missing.display()
+        );
+    }
+
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    Ok(true)
 }
 
 /// Format file size in human-readable format
@@ -65,3 +397,266 @@ pub fn format_size(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
+
+/// Default limit for files read by import and push
+pub const DEFAULT_MAX_FILE_SIZE: &str = "5MB";
+
+/// Parse a size such as "5MB", "512KB", "1GB" or "1048576" (bytes)
+pub fn parse_size(value: &str) -> std::result::Result<u64, String> {
+    let value = value.trim();
+    let upper = value.to_uppercase();
+    let (number, multiplier) = if let Some(n) = upper.strip_suffix("GB") {
+        (n, 1024 * 1024 * 1024)
+    } else if let Some(n) = upper.strip_suffix("MB") {
+        (n, 1024 * 1024)
+    } else if let Some(n) = upper.strip_suffix("KB") {
+        (n, 1024)
+    } else if let Some(n) = upper.strip_suffix('B') {
+        (n, 1)
+    } else {
+        (upper.as_str(), 1)
+    };
+
+    let n = number.trim().parse::<u64>()
+        .map_err(|_| format!("invalid size '{}' (examples: 5MB, 512KB, 1048576)", value))?;
+    n.checked_mul(multiplier).ok_or_else(|| format!("size too large: {}", value))
+}
+
+/// Parse a point in time: a duration back from now ("30m", "12h", "7d", "2w"),
+/// a date ("2024-01-01", midnight UTC) or an RFC 3339 timestamp
+pub fn parse_since(value: &str) -> std::result::Result<chrono::DateTime<chrono::Utc>, String> {
+    let value = value.trim();
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&chrono::Utc));
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
+    }
+
+    let invalid = || format!("invalid time '{}' (examples: 7d, 12h, 2w, 2024-01-01)", value);
+    let unit = value.chars().last().ok_or_else(invalid)?;
+    let number: i64 = value[..value.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    let duration = match unit {
+        'm' => chrono::TimeDelta::try_minutes(number),
+        'h' => chrono::TimeDelta::try_hours(number),
+        'd' => chrono::TimeDelta::try_days(number),
+        'w' => chrono::TimeDelta::try_weeks(number),
+        _ => return Err(invalid()),
+    };
+    // Durations too large for chrono, or reaching before its earliest date, are invalid
+    duration.and_then(|duration| chrono::Utc::now().checked_sub_signed(duration))
+        .ok_or_else(invalid)
+}
+
+/// Size of a file when it is larger than `max_size` bytes (None if within the limit or unreadable)
+pub fn exceeds_size<P: AsRef<Path>>(path: P, max_size: u64) -> Option<u64> {
+    fs::metadata(path).ok()
+        .map(|m| m.len())
+        .filter(|len| *len > max_size)
+}
+
+/// Value that changes whenever a markdown file under `dir` is added, removed, renamed or modified
+/// Hidden files are skipped like push does. Used by commands that poll for changes.
+pub fn markdown_change_stamp(dir: &Path) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut entries: Vec<_> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().and_then(|x| x.to_str()) == Some("md"))
+        .map(|e| (e.path().to_path_buf(), e.metadata().ok().and_then(|m| m.modified().ok())))
+        .collect();
+    entries.sort();
+    entries.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+/// Closing line for a transfer, e.g. "Pulled 12 documents (4.20 MB) in 3.1s"
+pub fn transfer_summary(verb: &str, count: usize, bytes: u64, elapsed: std::time::Duration) -> String {
+    format!("{} {} document{} ({}) in {:.1}s",
+        verb,
+        count,
+        if count == 1 { "" } else { "s" },
+        format_size(bytes),
+        elapsed.as_secs_f64()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_file_atomic() {
+        let root = std::env::temp_dir().join(format!("teamturbo-atomic-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("docuram.json");
+
+        fs::write(&path, r#"{"old": true}"#).unwrap();
+        write_file_atomic(&path, r#"{"new": true}"#).unwrap();
+
+        let saved: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["new"], true);
+        let leftovers: Vec<_> = fs::read_dir(&root).unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name != "docuram.json")
+            .collect();
+        assert!(leftovers.is_empty(), "temporary files left behind: {:?}", leftovers);
+
+        // A failed write leaves the original file untouched
+        assert!(write_file_atomic(root.join("missing/docuram.json"), "{}").is_err());
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let root = std::env::temp_dir().join(format!("teamturbo-ignore-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        assert!(!matches_ignore_rules(&load_ignore_rules(&root), Path::new("docuram/a.md")));
+
+        fs::write(root.join(IGNORE_FILE), "drafts/\n*.tmpl.md\n!keep.tmpl.md\n/notes.md\n").unwrap();
+        let rules = load_ignore_rules(&root);
+        assert!(matches_ignore_rules(&rules, Path::new("docuram/drafts/idea.md")));
+        assert!(matches_ignore_rules(&rules, Path::new("docuram/organic/page.tmpl.md")));
+        assert!(!matches_ignore_rules(&rules, Path::new("docuram/organic/keep.tmpl.md")));
+        assert!(matches_ignore_rules(&rules, Path::new("notes.md")));
+        assert!(!matches_ignore_rules(&rules, Path::new("docuram/notes.md")));
+        assert!(matches_ignore_rules(&rules, &root.join("docuram/drafts/idea.md")));
+        assert!(!matches_ignore_rules(&rules, Path::new("/elsewhere/drafts/idea.md")));
+        assert!(matches_ignore_rules(&rules, Path::new("docuram/templates/req.md")));
+        assert!(!matches_ignore_rules(&rules, Path::new("docuram/organic/templates/req.md")));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_path_filter() {
+        let patterns = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(PathFilter::default().matches("docuram/organic/a.md"));
+
+        let filter = PathFilter::new(&patterns(&["organic/**", "docuram/impl/*.md"]), &patterns(&["**/draft-*"])).unwrap();
+        assert!(filter.matches("docuram/organic/a.md"));
+        assert!(filter.matches("docuram/organic/sub/b.md"));
+        assert!(filter.matches("docuram/impl/c.md"));
+        assert!(!filter.matches("docuram/impl/feat/c.md"));
+        assert!(!filter.matches("docuram/organic/draft-x.md"));
+
+        let filter = PathFilter::new(&[], &patterns(&["impl/**"])).unwrap();
+        assert!(filter.matches("docuram/organic/a.md"));
+        assert!(!filter.matches("docuram/impl/feat/c.md"));
+
+        assert!(PathFilter::new(&patterns(&["organic/[a"]), &[]).is_err());
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        assert_eq!(normalize_line_endings("\u{feff}a\r\nb\rc"), Some("a\nb\nc".to_string()));
+        assert_eq!(normalize_line_endings("a\nb\n"), None);
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("5MB"), Ok(5 * 1024 * 1024));
+        assert_eq!(parse_size("512kb"), Ok(512 * 1024));
+        assert_eq!(parse_size("100"), Ok(100));
+        assert!(parse_size("lots").is_err());
+        assert_eq!(parse_size("99999999999GB"), Err("size too large: 99999999999GB".to_string()));
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(parse_since("2024-01-01").unwrap().to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(parse_since("2024-01-01T12:00:00+02:00").unwrap().to_rfc3339(), "2024-01-01T10:00:00+00:00");
+        let week_ago = chrono::Utc::now() - chrono::Duration::days(7);
+        assert!((parse_since("7d").unwrap() - week_ago).num_seconds().abs() < 5);
+        assert!(parse_since("7y").is_err());
+        assert!(parse_since("d").is_err());
+        assert!(parse_since("").is_err());
+        assert!(parse_since("9999999999999999d").is_err());
+        assert!(parse_since("999999999d").is_err());
+    }
+
+    #[test]
+    fn test_transfer_summary() {
+        let line = transfer_summary("Pulled", 3, 2048, std::time::Duration::from_millis(3100));
+        assert_eq!(line, "Pulled 3 documents (2.00 KB) in 3.1s");
+    }
+
+    #[test]
+    fn test_extract_front_matter() {
+        let (front_matter, body) = extract_front_matter("---\ntitle: A\n---\n# Body\n");
+        assert_eq!(front_matter, Some("title: A\n"));
+        assert_eq!(body, "# Body\n");
+
+        assert_eq!(extract_front_matter("# Body\n"), (None, "# Body\n"));
+        assert_eq!(extract_front_matter("---\nunterminated\n"), (None, "---\nunterminated\n"));
+        assert_eq!(
+            calculate_content_checksum("---\ntitle: B\n---\n# Body\n"),
+            calculate_content_checksum("---\ntitle: A\n---\n# Body\n")
+        );
+    }
+
+    #[test]
+    fn test_content_matches_checksum() {
+        let lf_checksum = calculate_checksum("# Title\nbody\n");
+        assert!(content_matches_checksum("# Title\nbody\n", &lf_checksum));
+        assert!(content_matches_checksum("# Title\r\nbody\r\n", &lf_checksum));
+        assert!(content_matches_checksum("# Title\nbody\n", &calculate_checksum("# Title\r\nbody\r\n")));
+        assert!(!content_matches_checksum("# Title\r\nother\r\n", &lf_checksum));
+    }
+
+    #[test]
+    fn test_matches_ignoring_line_endings() {
+        let lf_checksum = calculate_checksum("a\nb\n");
+        assert!(matches_ignoring_line_endings("a\r\nb\r\n", &lf_checksum));
+        assert!(matches_ignoring_line_endings("a\nb\n", &calculate_checksum("a\r\nb\r\n")));
+        assert!(!matches_ignoring_line_endings("a\r\nc\r\n", &lf_checksum));
+    }
+
+    #[test]
+    fn test_checksums_match_mixed_formats() {
+        let prefixed = calculate_checksum("a\n");
+        let bare = prefixed.strip_prefix("sha256:").unwrap();
+        assert!(checksums_match(&prefixed, bare));
+        assert!(checksums_match(bare, &prefixed));
+        assert!(checksums_match(&prefixed, &bare.to_uppercase()));
+        assert!(!checksums_match(&prefixed, &calculate_checksum("b\n")));
+        assert!(matches_ignoring_line_endings("a\r\n", bare));
+    }
+
+    #[test]
+    fn test_resolve_front_matter_builtin_defaults() {
+        let fm = resolve_front_matter("Cat/organic", "a.md", &DocumentDefaults::default(), &DocumentDefaults::default());
+        assert_eq!(fm.doc_type, "knowledge");
+        assert_eq!(fm.priority, 0);
+        assert_eq!(fm.category, "Cat/organic");
+    }
+
+    #[test]
+    fn test_resolve_front_matter_precedence() {
+        let defaults = DocumentDefaults { doc_type: Some("requirement".to_string()), priority: Some(2) };
+        let overrides = DocumentDefaults { doc_type: Some("bug".to_string()), priority: None };
+        let fm = resolve_front_matter("Cat", "a.md", &overrides, &defaults);
+        assert_eq!(fm.doc_type, "bug");
+        assert_eq!(fm.priority, 2);
+    }
+
+    #[test]
+    fn test_ensure_category_dir() {
+        let root = std::env::temp_dir().join(format!("teamturbo-parents-{}", uuid::Uuid::new_v4()));
+        let target = root.join("a/b");
+
+        let err = ensure_category_dir(&target, false).unwrap_err().to_string();
+        assert!(err.contains(&format!("missing: {}", root.display())));
+        assert!(!root.exists());
+
+        assert!(ensure_category_dir(&target, true).unwrap());
+        assert!(!ensure_category_dir(&target, false).unwrap());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
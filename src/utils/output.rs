@@ -0,0 +1,80 @@
+use std::cell::RefCell;
+use std::io::Write;
+
+thread_local! {
+    /// When set, command output on this thread is collected here instead of printed
+    static CAPTURE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Write command output to stdout, or to the capture buffer if one is active
+pub fn write(text: &str) {
+    let captured = CAPTURE.with(|capture| {
+        if let Some(buffer) = capture.borrow_mut().as_mut() {
+            buffer.push_str(text);
+            true
+        } else {
+            false
+        }
+    });
+
+    if !captured {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(text.as_bytes());
+        let _ = stdout.flush();
+    }
+}
+
+/// Write a line of command output
+pub fn write_line(line: &str) {
+    write(&format!("{}\n", line));
+}
+
+/// Run `f` and return everything it wrote through the output macros on this thread
+/// Captures nest: the previous buffer is restored afterwards.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let previous = CAPTURE.with(|capture| capture.borrow_mut().replace(String::new()));
+    let result = f();
+    let output = CAPTURE.with(|capture| {
+        let mut capture = capture.borrow_mut();
+        let output = capture.take().unwrap_or_default();
+        *capture = previous;
+        output
+    });
+    (result, output)
+}
+
+/// Print a line of command output (capturable replacement for `println!`)
+#[macro_export]
+macro_rules! outln {
+    () => {
+        $crate::utils::output::write_line("")
+    };
+    ($($arg:tt)*) => {
+        $crate::utils::output::write_line(&format!($($arg)*))
+    };
+}
+
+/// Print command output without a newline (capturable replacement for `print!`)
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::utils::output::write(&format!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_collects_and_nests() {
+        let ((), outer) = capture(|| {
+            crate::outln!("first");
+            let ((), inner) = capture(|| crate::out!("inner {}", 1));
+            assert_eq!(inner, "inner 1");
+            crate::outln!();
+            crate::item!("item");
+        });
+        assert_eq!(outer, "first\n\nitem\n");
+    }
+}
@@ -0,0 +1,276 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Characters reStructuredText accepts for section over/underlines
+const ADORNMENT_CHARS: &str = "=-`:'\"~^_*+#<>";
+
+/// Convert reStructuredText to markdown
+/// Handles section headings, bullet and enumerated lists, literal and code blocks, inline
+/// literals and hyperlinks. Returns None when the document uses anything else (directives,
+/// tables, roles, footnotes), which would be lost or garbled by a partial conversion.
+pub fn to_markdown(content: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut out: Vec<String> = Vec::new();
+    // Heading styles in order of first use; the position is the heading level
+    let mut styles: Vec<(char, bool)> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if is_unsupported(line) {
+            return None;
+        }
+
+        // Heading with overline and underline
+        if let Some(c) = adornment(line) {
+            if i + 2 < lines.len() && !lines[i + 1].trim().is_empty() && adornment(lines[i + 2]) == Some(c) {
+                out.push(heading(&mut styles, (c, true), lines[i + 1].trim()));
+                i += 3;
+                continue;
+            }
+            // A lone adornment line is a transition
+            out.push("---".to_string());
+            i += 1;
+            continue;
+        }
+
+        // Heading with underline only
+        if !trimmed.is_empty() && !line.starts_with(char::is_whitespace) && i + 1 < lines.len() {
+            if let Some(c) = adornment(lines[i + 1]) {
+                if lines[i + 1].trim_end().chars().count() >= trimmed.chars().count() {
+                    out.push(heading(&mut styles, (c, false), trimmed));
+                    i += 2;
+                    continue;
+                }
+            }
+        }
+
+        // Explicit markup: only code blocks are understood
+        if let Some(directive) = line.strip_prefix("..") {
+            let directive = directive.trim();
+            let language = directive.strip_prefix("code-block::")
+                .or_else(|| directive.strip_prefix("code::"))?
+                .trim();
+            let (block, next) = indented_block(&lines, i + 1, true);
+            push_fenced(&mut out, language, &block);
+            i = next;
+            continue;
+        }
+
+        // Paragraph ending in "::" introduces a literal block
+        if let Some(text) = trimmed.strip_suffix("::") {
+            if !text.is_empty() {
+                let text = line.trim_end().strip_suffix("::").unwrap_or(line);
+                let colon = if text.ends_with(char::is_whitespace) { "" } else { ":" };
+                out.push(format!("{}{}", inline(text.trim_end()), colon));
+            }
+            let (block, next) = indented_block(&lines, i + 1, false);
+            push_fenced(&mut out, "", &block);
+            i = next;
+            continue;
+        }
+
+        out.push(list_item(line).unwrap_or_else(|| inline(line)));
+        i += 1;
+    }
+
+    let mut markdown = out.join("\n");
+    markdown.push('\n');
+    Some(markdown)
+}
+
+/// Wrap `content` in a fenced code block tagged `language`, longer than any fence inside it
+pub fn fenced(content: &str, language: &str) -> String {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{}{}\n{}\n{}\n", fence, language, content.trim_end(), fence)
+}
+
+/// Adornment character when `line` is a section over/underline or transition
+fn adornment(line: &str) -> Option<char> {
+    let line = line.trim_end();
+    let c = line.chars().next()?;
+    (line.len() >= 3 && ADORNMENT_CHARS.contains(c) && line.chars().all(|x| x == c)).then_some(c)
+}
+
+/// Markdown heading for a title, assigning levels in order of first use like docutils
+fn heading(styles: &mut Vec<(char, bool)>, style: (char, bool), title: &str) -> String {
+    let level = match styles.iter().position(|s| *s == style) {
+        Some(pos) => pos + 1,
+        None => {
+            styles.push(style);
+            styles.len()
+        }
+    };
+    format!("{} {}", "#".repeat(level.min(6)), inline(title))
+}
+
+/// Constructs without a faithful markdown equivalent in this converter
+fn is_unsupported(line: &str) -> bool {
+    static ROLE: OnceLock<Regex> = OnceLock::new();
+    let role = ROLE.get_or_init(|| Regex::new(r":[A-Za-z][\w:+.-]*:`|\[#?\w*\]_|\|\w[^|]*\|_?").unwrap());
+    let trimmed = line.trim();
+
+    // Grid tables (+---+) and simple tables (===  ===)
+    trimmed.starts_with("+-") || trimmed.starts_with("+=")
+        || (trimmed.contains("= ") && trimmed.chars().all(|c| c == '=' || c == ' '))
+        || role.is_match(line)
+}
+
+/// Indented lines starting at `start`, dedented, and the index of the first line after them
+/// With `options`, directive option lines (`:linenos:`) right after the directive are skipped.
+fn indented_block(lines: &[&str], start: usize, options: bool) -> (Vec<String>, usize) {
+    let mut end = start;
+    let mut block: Vec<&str> = Vec::new();
+    let mut in_options = options;
+
+    while end < lines.len() {
+        let line = lines[end];
+        if !line.trim().is_empty() && !line.starts_with(char::is_whitespace) {
+            break;
+        }
+        if in_options && line.trim().starts_with(':') {
+            end += 1;
+            continue;
+        }
+        in_options = false;
+        block.push(line);
+        end += 1;
+    }
+
+    // Trailing blank lines belong to the text after the block
+    while block.last().is_some_and(|line| line.trim().is_empty()) {
+        block.pop();
+        end -= 1;
+    }
+    while block.first().is_some_and(|line| line.trim().is_empty()) {
+        block.remove(0);
+    }
+
+    let indent = block.iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let block = block.iter()
+        .map(|line| line.get(indent..).unwrap_or("").to_string())
+        .collect();
+    (block, end)
+}
+
+fn push_fenced(out: &mut Vec<String>, language: &str, block: &[String]) {
+    if out.last().is_some_and(|line| !line.is_empty()) {
+        out.push(String::new());
+    }
+    out.push(fenced(&block.join("\n"), language).trim_end().to_string());
+}
+
+/// Bullet (`*`, `-`, `+`) and enumerated (`1.`, `#.`) list items as markdown items
+fn list_item(line: &str) -> Option<String> {
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let item = line.trim_start();
+
+    if let Some(text) = ["* ", "- ", "+ "].iter().find_map(|bullet| item.strip_prefix(bullet)) {
+        return Some(format!("{}- {}", indent, inline(text)));
+    }
+    if let Some(text) = item.strip_prefix("#. ") {
+        return Some(format!("{}1. {}", indent, inline(text)));
+    }
+    None
+}
+
+/// Inline markup: ``literal``, `text <url>`_ hyperlinks and `name`_ references
+fn inline(text: &str) -> String {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    static REFERENCE: OnceLock<Regex> = OnceLock::new();
+    static LITERAL: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| Regex::new(r"`([^`<]+?)\s*<([^`>]+)>`__?").unwrap());
+    let reference = REFERENCE.get_or_init(|| Regex::new(r"`([^`]+)`__?").unwrap());
+    let literal = LITERAL.get_or_init(|| Regex::new(r"``([^`]+)``").unwrap());
+
+    let text = link.replace_all(text, "[$1]($2)");
+    let text = reference.replace_all(&text, "$1");
+    literal.replace_all(&text, "`$1`").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_markdown() {
+        let rst = "\
+=====
+Guide
+=====
+
+Setup
+-----
+
+Install with ``cargo``, see `the docs <https://example.com>`_.
+
+* one
+* two
+
+#. first
+
+Run this::
+
+    teamturbo pull
+    teamturbo push
+
+.. code-block:: rust
+   :linenos:
+
+   fn main() {}
+
+Details
+~~~~~~~
+
+Done.
+";
+        let expected = "\
+# Guide
+
+## Setup
+
+Install with `cargo`, see [the docs](https://example.com).
+
+- one
+- two
+
+1. first
+
+Run this:
+
+```
+teamturbo pull
+teamturbo push
+```
+
+```rust
+fn main() {}
+```
+
+### Details
+
+Done.
+";
+        assert_eq!(to_markdown(rst).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_unsupported_constructs() {
+        assert!(to_markdown(".. note::\n\n   Careful\n").is_none());
+        assert!(to_markdown("See :ref:`setup`.\n").is_none());
+        assert!(to_markdown("=====  =====\nA      B\n=====  =====\n").is_none());
+        assert!(to_markdown("+---+---+\n| a | b |\n+---+---+\n").is_none());
+    }
+
+    #[test]
+    fn test_fenced_outgrows_inner_fence() {
+        assert_eq!(fenced("a\n```\nb\n```", "rst"), "````rst\na\n```\nb\n```\n````\n");
+    }
+}